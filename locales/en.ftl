@@ -0,0 +1,435 @@
+## Common
+
+ping-pong = 🏓 Pong!
+session-closed = 🔒️ Ordering is closed
+lottery-already-drawn = 🎟️ The lottery has already been drawn
+unknown-variant = 🚫 There is no variant called **{ $variant }**.
+invalid-time = 🚫 Times must be written as HH:MM, e.g. 14:00
+invalid-date = 🚫 Dates must be written as YYYY-MM-DD, e.g. 2026-12-24
+list-and = and
+access-denied = ❌ You do not have access to this command.
+channel-ordering-hint = 🧇 Waffles are ordered in { $channel }
+channel-oracle-hint = 🔮 This command is used in { $channel }
+channel-sent-to = 📣 Sent in { $channel }
+
+weekday-mon = Monday
+weekday-tue = Tuesday
+weekday-wed = Wednesday
+weekday-thu = Thursday
+weekday-fri = Friday
+weekday-sat = Saturday
+weekday-sun = Sunday
+
+## Announcements
+
+announce-opened = 🔓️ Ordering is now open
+announce-opened-lottery = 🎟️ Signup for the waffle lottery is now open. Use `/waffle` to join!
+announce-capacity = 🥣 There is batter for **{ $capacity }** waffles.
+announce-closes-at = ⏰ Ordering closes <t:{ $time }:t> (<t:{ $time }:R>).
+announce-closing-soon = ⏰ Ordering closes <t:{ $time }:R>! Use `/waffle` now if you want a waffle.
+announce-closed = 🔒️ Ordering is now closed
+announce-closed-idle = 💤 Nobody has baked waffles for { $minutes } minutes, so ordering was closed automatically.
+stats-title = 📊 **Today's stats**
+stats-total = Total baked: { $count } { $count ->
+        [one] waffle
+       *[other] waffles
+    }
+stats-top = 🏆 **Top orderers:**
+stats-top-user = { $medal } { $user } - { $count } { $count ->
+        [one] waffle
+       *[other] waffles
+    }
+presence-baking = 🧇 Making waffles
+presence-baking-guilds = 🧇 Making waffles in { $count } servers
+
+## Queue and ordering
+
+queue-closed = 🚨 Ordering is closed
+queue-waitlisted = 📝 You are number { $position } on the waitlist
+queue-not-in-lottery = 🚨 You are not in the lottery.
+queue-not-in-queue = 🚨 You are not in the queue.
+queue-in-lottery = 🎟️ You are in the lottery.
+queue-position = 😎 You are number { $position } in the queue
+queue-positions = 😎 You are queued in:
+queue-lane-position = - **{ $lane }**: number { $position }
+capacity-remaining = 🥣 There is batter for **{ $remaining }** more waffles.
+queue-name = the queue
+queue-name-lane = the queue for **{ $lane }**
+
+waffle-closed = 🏮 Ordering is closed
+waffle-already-waitlisted = 📝 You are **already** on the waitlist. You are number **{ $position }** on the list.
+waffle-already-in-lottery = 🎟️ You are **already** in the lottery.
+waffle-already-in-queue = ⏲️ You are **already** in { $queue }. You are number **{ $position }** in the queue.
+waffle-unknown-option = 🚫 **{ $option }** is not on the menu. See `/menu show` for what is available.
+waffle-out-of-batter = 🥣 Sorry, the batter has run out.
+waffle-batter-left = 🥣 There is only batter for **{ $remaining }** more waffles.
+waffle-waitlisted = 📝 There is not enough batter right now, but you are number **{ $position }** on the waitlist.
+waffle-joined-lottery = 🎟️ You are now in the lottery. Good luck!
+waffle-joined = { $amount ->
+        [1] ⏲️ You are now in { $queue }. You are number **{ $position }** in the queue.
+       *[other] ⏲️ You are now in { $queue } with **{ $amount }** waffles. You are number **{ $position }** in the queue.
+    }
+
+refusal-too-many = 🚫 You can order between **1** and **{ $max }** waffles at a time.
+refusal-limit = 🚫 You can get at most **{ $limit }** waffles{ $remaining ->
+        [0] {""}
+       *[other] , and have **{ $remaining }** left
+    }. { $retry ->
+        [none] You can order again the next time there is baking.
+       *[other] You can order again <t:{ $retry }:R>.
+    }
+refusal-cooldown = ⏳ You just got a waffle. You can order again <t:{ $until }:t> (<t:{ $until }:R>).
+
+## Oracles
+
+bake-not-drawn = 🎟️ The lottery has not been drawn yet. Use `/draw` first.
+bake-nobody = 😟 Nobody to bake waffles for.
+bake-baked = 🧇 Baked { $count ->
+        [one] a waffle
+       *[other] { $count } waffles
+    } for:
+
+capacity-set = 🥣 There is now batter for **{ $capacity }** waffles, and **{ $remaining }** are not ordered yet.
+capacity-unlimited = 🥣 There is no limit to how many waffles can be ordered.
+capacity-promoted = 📝 From the waitlist into the queue:
+
+close-already-closed = 🔒️ Ordering is already closed
+
+open-duration-and-until = 🚫 Choose either a duration or when ordering closes, not both
+open-invalid-duration = 🚫 Durations must be written as e.g. 90m, 2h or 1h30m, and be at most 24 hours
+open-time-passed = 🚫 It is already past { $time } today
+open-already-open = 🔓️ Ordering is already open
+open-deadline-save-failed = ❌ Failed to save when ordering closes. Remember `/close`!
+
+draw-not-lottery = 🚫 Ordering is not a lottery
+draw-nobody = 😟 Nobody signed up for the lottery.
+draw-winners = 🎟️ Drew { $winners } of { $participants } participants. Congratulations to:
+draw-seed-id = 🔎 Draw #{ $id }, seed `{ $seed }`
+draw-seed = 🔎 Seed `{ $seed }`
+
+menu-empty = 📋 The menu is empty. The waffles come plain.
+menu-title = 📋 **Menu**
+menu-invalid-name = 🚫 The name can not be empty or contain commas
+menu-added = 📋 Added **{ $name }** to the menu
+menu-already-added = 📋 **{ $name }** is already on the menu
+menu-removed = 📋 Removed **{ $name }** from the menu
+menu-not-added = 📋 **{ $name }** is not on the menu
+menu-save-failed = ❌ Failed to update the menu
+
+plan-empty = 📅 There is no schedule. Use `/schedule add` to make one.
+plan-title = 📅 **Schedule**
+plan-entry = `#{ $id }` Every { $weekday } { $opens }–{ $closes } in { $channel }
+plan-exceptions = 🏖️ **Exceptions**
+plan-closes-before-opens = 🚫 Ordering must close after it opens
+plan-added = 📅 Ordering opens every { $weekday } { $opens }–{ $closes } in { $channel } (`#{ $id }`)
+plan-removed = 📅 Removed `#{ $id }` from the schedule
+plan-not-found = 📅 Could not find `#{ $id }` in the schedule
+plan-exception-added = 🏖️ Ordering will not open automatically on { $date }
+plan-exception-exists = 🏖️ { $date } is already an exception
+plan-exception-removed = 📅 The schedule is followed as usual on { $date }
+plan-exception-not-found = 📅 { $date } is not an exception
+plan-save-failed = ❌ Failed to update the schedule
+
+## Settings
+
+settings-save-failed = ❌ Failed to save the settings
+settings-max-waffles = ⚙️ You can now order up to **{ $amount }** waffles at a time
+settings-variants-none = ⚙️ There is now only one queue
+settings-variants = ⚙️ These variants now have their own queue: **{ $variants }**
+settings-serving-fifo = ⚙️ The queue is now served in the order people joined
+settings-serving-first-timers-first = ⚙️ Those who have not had a waffle yet are now served before those who have
+settings-user-limit-session = ⚙️ Each person can now get at most **{ $max }** waffles each time there is baking
+settings-user-limit-day = ⚙️ Each person can now get at most **{ $max }** waffles per day
+settings-user-limit-none = ⚙️ There is no limit to how many waffles each person can get
+settings-cooldown = ⚙️ You now have to wait **{ $minutes }** minutes after getting a waffle before ordering again
+settings-cooldown-none = ⚙️ You can now order again as soon as you have had a waffle
+settings-overflow-refuse = ⚙️ Orders are refused when the batter runs out
+settings-overflow-waitlist = ⚙️ Orders are put on the waitlist when the batter runs out
+settings-idle-close = ⚙️ Ordering now closes automatically after **{ $minutes }** minutes without baking
+settings-idle-close-none = ⚙️ Ordering now never closes automatically
+settings-channels-all = every channel
+settings-channels = ⚙️ Waffles are now ordered in { $ordering }, and the oracle commands are used in { $oracle }
+settings-channels-audit = . Oracle actions are logged in { $channel }
+settings-language = ⚙️ Vaffelbot now speaks English in this server
+
+language-set = 🗣️ Vaffelbot now answers you in English
+language-follows-guild = 🗣️ Vaffelbot now answers you in the language of the server
+language-save-failed = ❌ Failed to save the language
+
+## Templates
+
+template-name-opened = Opened
+template-name-opened_lottery = Lottery opened
+template-name-closing_soon = Closing soon
+template-name-closed = Closed
+template-name-baked = Baked
+template-name-joined = Joined
+templates-title = 📝 **Templates**
+templates-standard = _Default_
+templates-placeholders = Placeholders: { $placeholders }
+templates-no-placeholders = No placeholders
+templates-error-empty = 🚫 The template can not be empty
+templates-error-too-long = 🚫 The template can not be longer than { $max } characters
+templates-error-unclosed = 🚫 A `{"{"}` is missing its `{"}"}`. Write `{"{{"}` to show a brace.
+templates-error-unopened = 🚫 A `{"}"}` is missing its `{"{"}`. Write `{"}}"}` to show a brace.
+templates-error-unknown = 🚫 The message has no placeholder called `{ $name }`.
+templates-preview = 👀 This is how the message looks:
+templates-saved = 📝 Saved the template for **{ $name }**. This is how the message looks:
+templates-reset = 📝 **{ $name }** now uses the default text
+templates-not-changed = 📝 **{ $name }** already uses the default text
+templates-save-failed = ❌ Failed to update the template
+
+## API tokens
+
+api-title = 🔑 **API tokens**
+api-empty = 🔑 The server has no API tokens
+api-entry = `#{ $id }` **{ $label }**, created <t:{ $time }:d>
+api-invalid-label = 🚫 The name must be between 1 and { $max } characters
+api-created = 🔑 Created API token `#{ $id }` **{ $label }**. Copy it now, it will not be shown again:
+api-revoked = 🔑 Revoked API token `#{ $id }`
+api-not-found = 🚫 Found no API token `#{ $id }`
+api-save-failed = ❌ Failed to update the API tokens
+
+## Audit log
+
+audit-title-open = 🔓️ Ordering opened
+audit-title-close = 🔒️ Ordering closed
+audit-title-bake = 🧇 Waffles baked
+audit-title-draw = 🎟️ Lottery drawn
+audit-title-capacity = 🥣 Capacity changed
+audit-title-remove = 🚪 Removed from the queue
+audit-title-move = ↕️ Moved in the queue
+audit-title-api_token = 🔑 API token
+audit-by = By
+audit-automatic = 🤖 Automatic
+audit-open-queue = Queue
+audit-open-lottery = Lottery
+audit-open-closes-at = closes <t:{ $time }:t>
+audit-capacity = Batter for { $capacity } waffles
+audit-capacity-unlimited = No limit
+audit-close = Closed with `/close`, { $count } waffles baked today
+audit-close-deadline = Time ran out
+audit-close-idle = Nobody baked for { $minutes } minutes
+audit-open-schedule = Opened from schedule `#{ $id }`
+audit-close-schedule = Closed from schedule `#{ $id }`
+audit-bake = { $count } in { $lane } for { $recipients }
+audit-bake-nobody = Nobody to bake for in { $lane }
+audit-draw = { $count } of { $participants } participants: { $winners }
+audit-draw-id = { $count } of { $participants } participants: { $winners } (draw #{ $id })
+audit-api = via API token **{ $label }**
+audit-close-api = Closed from the API, { $count } waffles baked today
+audit-remove = { $user } removed from { $lane }
+audit-move = { $user } moved to number { $position } in { $lane }
+audit-api-token-created = Created API token `#{ $id }` **{ $label }**
+audit-api-token-revoked = Revoked API token `#{ $id }`
+
+## Commands
+
+command-ping = ping
+command-ping-description = Ping vaffelbot
+
+command-waffle = waffle
+command-waffle-description = Get an oracle to bake you a waffle
+command-waffle-antall = amount
+command-waffle-antall-description = How many waffles?
+command-waffle-topping = topping
+command-waffle-topping-description = What do you want on it? Separate several with commas
+command-waffle-variant = variant
+command-waffle-variant-description = Which variant? E.g. gluten free
+
+command-queue = queue
+command-queue-description = See how many are ahead of you in the queue
+
+command-bake = baked
+command-bake-description = Bake waffles
+command-bake-amount = amount
+command-bake-amount-description = How many waffles?
+command-bake-variant = variant
+command-bake-variant-description = Which variant? Default if empty
+
+command-capacity = capacity
+command-capacity-description = Change how many waffles there is batter for
+command-capacity-antall = amount
+command-capacity-antall-description = How many waffles is there batter for in total? Empty for no limit
+
+command-open = open
+command-open-description = Open for waffle orders
+command-open-modus = mode
+command-open-modus-description = Queue or lottery? Queue by default
+command-open-modus-0 = Queue
+command-open-modus-1 = Lottery
+command-open-kapasitet = capacity
+command-open-kapasitet-description = How many waffles is there batter for? Empty for no limit
+command-open-varighet = duration
+command-open-varighet-description = How long ordering is open, e.g. 90m or 1h30m
+command-open-til = until
+command-open-til-description = When ordering closes, e.g. 14:00
+
+command-close = close
+command-close-description = Close waffle orders
+
+command-draw = draw
+command-draw-description = Draw winners in the waffle lottery
+command-draw-antall = amount
+command-draw-antall-description = How many winners?
+
+command-language = language
+command-language-description = Choose which language vaffelbot answers you in
+command-language-sprak = language
+command-language-sprak-description = The language you want answers in. Empty to use the language of the server
+
+command-menu = menu
+command-menu-description = See and change what you can have on your waffle
+command-menu-show = show
+command-menu-show-description = See what you can have on your waffle
+command-menu-add = add
+command-menu-add-description = Add something you can have on your waffle
+command-menu-add-navn = name
+command-menu-add-navn-description = Name of the topping
+command-menu-remove = remove
+command-menu-remove-description = Remove something from the menu
+command-menu-remove-navn = name
+command-menu-remove-navn-description = Name of the topping
+
+command-plan = schedule
+command-plan-description = Open and close ordering automatically every week
+command-plan-show = show
+command-plan-show-description = See when ordering opens and closes automatically
+command-plan-add = add
+command-plan-add-description = Open and close ordering automatically on a weekday
+command-plan-add-dag = day
+command-plan-add-dag-description = Which day?
+command-plan-add-dag-0 = Monday
+command-plan-add-dag-1 = Tuesday
+command-plan-add-dag-2 = Wednesday
+command-plan-add-dag-3 = Thursday
+command-plan-add-dag-4 = Friday
+command-plan-add-dag-5 = Saturday
+command-plan-add-dag-6 = Sunday
+command-plan-add-fra = from
+command-plan-add-fra-description = When ordering opens, e.g. 12:00
+command-plan-add-til = until
+command-plan-add-til-description = When ordering closes, e.g. 14:00
+command-plan-add-kanal = channel
+command-plan-add-kanal-description = Where to post the announcements. This channel by default
+command-plan-remove = remove
+command-plan-remove-description = Remove a day from the schedule
+command-plan-remove-id = id
+command-plan-remove-id-description = The number from `/schedule show`
+command-plan-add_exception = exception
+command-plan-add_exception-description = Do not open ordering automatically on a date, e.g. during holidays
+command-plan-add_exception-dato = date
+command-plan-add_exception-dato-description = The date, e.g. 2026-12-24
+command-plan-remove_exception = remove-exception
+command-plan-remove_exception-description = Follow the schedule as usual on a date again
+command-plan-remove_exception-dato = date
+command-plan-remove_exception-dato-description = The date, e.g. 2026-12-24
+
+command-settings = settings
+command-settings-description = Change the settings of vaffelbot
+command-settings-max_waffles = max-waffles
+command-settings-max_waffles-description = Set how many waffles you can order at a time
+command-settings-max_waffles-amount = amount
+command-settings-max_waffles-amount-description = Max waffles per order
+command-settings-variants = variants
+command-settings-variants-description = Set which variants have their own queue, e.g. gluten free
+command-settings-variants-varianter = variants
+command-settings-variants-varianter-description = Variants separated by commas. Empty for just the default
+command-settings-serving = serving
+command-settings-serving-description = Choose who gets waffles first when baking
+command-settings-serving-modus = mode
+command-settings-serving-modus-description = Who should get waffles first?
+command-settings-serving-modus-0 = First come, first served
+command-settings-serving-modus-1 = Those who have not had a waffle first
+command-settings-user_limit = limit
+command-settings-user_limit-description = Set how many waffles each person can get
+command-settings-user_limit-antall = amount
+command-settings-user_limit-antall-description = Max waffles per person. Empty for no limit
+command-settings-user_limit-periode = period
+command-settings-user_limit-periode-description = Does the limit apply per opening or per day?
+command-settings-user_limit-periode-0 = Per opening
+command-settings-user_limit-periode-1 = Per day
+command-settings-cooldown = cooldown
+command-settings-cooldown-description = Set how long to wait after getting a waffle before ordering again
+command-settings-cooldown-minutter = minutes
+command-settings-cooldown-minutter-description = Number of minutes. Empty for no cooldown
+command-settings-overflow = full
+command-settings-overflow-description = Choose what happens to orders when the batter runs out
+command-settings-overflow-modus = mode
+command-settings-overflow-modus-description = What should happen when there is no more batter?
+command-settings-overflow-modus-0 = Refuse the order
+command-settings-overflow-modus-1 = Put on the waitlist
+command-settings-idle_close = idle
+command-settings-idle_close-description = Set how long ordering can stay open without baking before it closes
+command-settings-idle_close-minutter = minutes
+command-settings-idle_close-minutter-description = Number of minutes. Empty to never close automatically
+command-settings-channels = channels
+command-settings-channels-description = Choose where waffles are ordered and where the oracle commands are used
+command-settings-channels-bestilling = ordering
+command-settings-channels-bestilling-description = The channel for /waffle and /queue. Empty to keep the one already chosen
+command-settings-channels-orakel = oracle
+command-settings-channels-orakel-description = The channel for /open, /baked and /close. Empty to keep the one already chosen
+command-settings-channels-logg = log
+command-settings-channels-logg-description = The channel oracle actions are logged in. Empty to keep the one already chosen
+command-settings-channels-fjern = clear
+command-settings-channels-fjern-description = The channel to remove, so every channel can be used or nothing is logged
+command-settings-channels-fjern-0 = Ordering
+command-settings-channels-fjern-1 = Oracle
+command-settings-channels-fjern-2 = Log
+command-settings-channels-fjern-3 = All
+command-settings-language = language
+command-settings-language-description = Choose which language vaffelbot uses in the server
+command-settings-language-sprak = language
+command-settings-language-sprak-description = The language for messages to everyone in the server
+
+command-templates = template
+command-templates-description = Change the wording of the messages from vaffelbot
+command-templates-show = show
+command-templates-show-description = See which messages have their own wording
+command-templates-preview = preview
+command-templates-preview-description = See how a template looks without saving it
+command-templates-preview-melding = message
+command-templates-preview-melding-description = Which message?
+command-templates-preview-melding-0 = Opened
+command-templates-preview-melding-1 = Lottery opened
+command-templates-preview-melding-2 = Closing soon
+command-templates-preview-melding-3 = Closed
+command-templates-preview-melding-4 = Baked
+command-templates-preview-melding-5 = Joined
+command-templates-preview-tekst = text
+command-templates-preview-tekst-description = New text, e.g. 🧇 {"{"}count{"}"} waffles for {"{"}mentions{"}"}
+command-templates-set = set
+command-templates-set-description = Give a message its own wording
+command-templates-set-melding = message
+command-templates-set-melding-description = Which message?
+command-templates-set-melding-0 = Opened
+command-templates-set-melding-1 = Lottery opened
+command-templates-set-melding-2 = Closing soon
+command-templates-set-melding-3 = Closed
+command-templates-set-melding-4 = Baked
+command-templates-set-melding-5 = Joined
+command-templates-set-tekst = text
+command-templates-set-tekst-description = New text, e.g. 🧇 {"{"}count{"}"} waffles for {"{"}mentions{"}"}
+command-templates-reset = reset
+command-templates-reset-description = Go back to the default text of a message
+command-templates-reset-melding = message
+command-templates-reset-melding-description = Which message?
+command-templates-reset-melding-0 = Opened
+command-templates-reset-melding-1 = Lottery opened
+command-templates-reset-melding-2 = Closing soon
+command-templates-reset-melding-3 = Closed
+command-templates-reset-melding-4 = Baked
+command-templates-reset-melding-5 = Joined
+
+command-api = api
+command-api-description = Let things outside Discord control the queue
+command-api-list = show
+command-api-list-description = See which API tokens the server has
+command-api-create = new
+command-api-create-description = Create a new API token
+command-api-create-navn = name
+command-api-create-navn-description = What is the token for? E.g. Tablet at the stand
+command-api-revoke = revoke
+command-api-revoke-description = Revoke an API token so it can no longer be used
+command-api-revoke-id = id
+command-api-revoke-id-description = Number of the token, see /api show
@@ -0,0 +1,435 @@
+## Felles
+
+ping-pong = 🏓 Pong!
+session-closed = 🔒️ Bestilling er stengt
+lottery-already-drawn = 🎟️ Trekningen er allerede gjort
+unknown-variant = 🚫 Finner ingen variant som heter **{ $variant }**.
+invalid-time = 🚫 Klokkeslett må skrives som TT:MM, f.eks. 14:00
+invalid-date = 🚫 Datoen må skrives som ÅÅÅÅ-MM-DD, f.eks. 2026-12-24
+list-and = og
+access-denied = ❌ Du har ikke tilgang til denne kommandoen.
+channel-ordering-hint = 🧇 Vafler bestilles i { $channel }
+channel-oracle-hint = 🔮 Denne kommandoen brukes i { $channel }
+channel-sent-to = 📣 Sendt i { $channel }
+
+weekday-mon = mandag
+weekday-tue = tirsdag
+weekday-wed = onsdag
+weekday-thu = torsdag
+weekday-fri = fredag
+weekday-sat = lørdag
+weekday-sun = søndag
+
+## Kunngjøringer
+
+announce-opened = 🔓️ Bestilling er nå åpnet
+announce-opened-lottery = 🎟️ Påmelding til vaffellotteriet er nå åpnet. Bruk `/vaffel` for å bli med!
+announce-capacity = 🥣 Det er røre til **{ $capacity }** vafler.
+announce-closes-at = ⏰ Bestilling stenger <t:{ $time }:t> (<t:{ $time }:R>).
+announce-closing-soon = ⏰ Bestilling stenger <t:{ $time }:R>! Bruk `/vaffel` nå hvis du vil ha en vaffel.
+announce-closed = 🔒️ Bestilling er nå stengt
+announce-closed-idle = 💤 Ingen har stekt vafler på { $minutes } minutter, så bestilling ble stengt automatisk.
+stats-title = 📊 **Dagens statistikk**
+stats-total = Totalt stekt: { $count } { $count ->
+        [one] vaffel
+       *[other] vafler
+    }
+stats-top = 🏆 **Topp bestillere:**
+stats-top-user = { $medal } { $user } - { $count } { $count ->
+        [one] vaffel
+       *[other] vafler
+    }
+presence-baking = 🧇 Lager vafler
+presence-baking-guilds = 🧇 Lager vafler i { $count } servere
+
+## Kø og bestilling
+
+queue-closed = 🚨 Bestilling er stengt
+queue-waitlisted = 📝 Du står på ventelisten som nummer { $position }
+queue-not-in-lottery = 🚨 Du er ikke med i trekningen.
+queue-not-in-queue = 🚨 Du er ikke i køen.
+queue-in-lottery = 🎟️ Du er med i trekningen.
+queue-position = 😎 Du er { $position } i køen
+queue-positions = 😎 Du står i kø:
+queue-lane-position = - **{ $lane }**: nummer { $position }
+capacity-remaining = 🥣 Det er røre til **{ $remaining }** vafler til.
+queue-name = køen
+queue-name-lane = køen for **{ $lane }**
+
+waffle-closed = 🏮 Bestilling er stengt
+waffle-already-waitlisted = 📝 Du står **allerede** på ventelisten. Du er nummer **{ $position }** på listen.
+waffle-already-in-lottery = 🎟️ Du er **allerede** med i trekningen.
+waffle-already-in-queue = ⏲️ Du er **allerede** i { $queue }. Du er nummer **{ $position }** i køen.
+waffle-unknown-option = 🚫 **{ $option }** er ikke på menyen. Se `/meny vis` for hva som finnes.
+waffle-out-of-batter = 🥣 Det er dessverre tomt for røre.
+waffle-batter-left = 🥣 Det er bare røre til **{ $remaining }** vafler til.
+waffle-waitlisted = 📝 Det er ikke nok røre akkurat nå, men du står på ventelisten som nummer **{ $position }**.
+waffle-joined-lottery = 🎟️ Du er nå med i trekningen. Lykke til!
+waffle-joined = { $amount ->
+        [1] ⏲️ Du er nå i { $queue }. Du er nummer **{ $position }** i køen.
+       *[other] ⏲️ Du er nå i { $queue } med **{ $amount }** vafler. Du er nummer **{ $position }** i køen.
+    }
+
+refusal-too-many = 🚫 Du kan bestille mellom **1** og **{ $max }** vafler om gangen.
+refusal-limit = 🚫 Du kan få maks **{ $limit }** vafler{ $remaining ->
+        [0] {""}
+       *[other] , og har **{ $remaining }** igjen
+    }. { $retry ->
+        [none] Du kan bestille igjen neste gang det stekes.
+       *[other] Du kan bestille igjen <t:{ $retry }:R>.
+    }
+refusal-cooldown = ⏳ Du fikk nettopp vaffel. Du kan bestille igjen <t:{ $until }:t> (<t:{ $until }:R>).
+
+## Orakler
+
+bake-not-drawn = 🎟️ Trekningen er ikke gjort ennå. Bruk `/trekk` først.
+bake-nobody = 😟 Ingen å steke vafler til.
+bake-baked = 🧇 Stekte { $count ->
+        [one] en vaffel
+       *[other] { $count } vafler
+    } til:
+
+capacity-set = 🥣 Det er nå røre til **{ $capacity }** vafler, og **{ $remaining }** er ikke bestilt ennå.
+capacity-unlimited = 🥣 Det er ingen grense for hvor mange vafler som kan bestilles.
+capacity-promoted = 📝 Fra ventelisten og inn i køen:
+
+close-already-closed = 🔒️ Bestilling er allerede stengt
+
+open-duration-and-until = 🚫 Velg enten varighet eller når bestilling stenges, ikke begge
+open-invalid-duration = 🚫 Varigheten må skrives som f.eks. 90m, 2t eller 1t30m, og være på høyst 24 timer
+open-time-passed = 🚫 Klokken har allerede passert { $time } i dag
+open-already-open = 🔓️ Bestilling er allerede åpnet
+open-deadline-save-failed = ❌ Klarte ikke å lagre når bestilling stenges. Husk `/stopp`!
+
+draw-not-lottery = 🚫 Bestillingen er ikke et lotteri
+draw-nobody = 😟 Ingen meldte seg på lotteriet.
+draw-winners = 🎟️ Trakk { $winners } av { $participants } påmeldte. Gratulerer til:
+draw-seed-id = 🔎 Trekning #{ $id }, frø `{ $seed }`
+draw-seed = 🔎 Frø `{ $seed }`
+
+menu-empty = 📋 Menyen er tom. Vaflene kommer naturell.
+menu-title = 📋 **Meny**
+menu-invalid-name = 🚫 Navnet kan ikke være tomt eller inneholde komma
+menu-added = 📋 La til **{ $name }** på menyen
+menu-already-added = 📋 **{ $name }** er allerede på menyen
+menu-removed = 📋 Fjernet **{ $name }** fra menyen
+menu-not-added = 📋 **{ $name }** er ikke på menyen
+menu-save-failed = ❌ Klarte ikke å oppdatere menyen
+
+plan-empty = 📅 Det er ingen plan. Bruk `/plan legg-til` for å lage en.
+plan-title = 📅 **Plan**
+plan-entry = `#{ $id }` Hver { $weekday } { $opens }–{ $closes } i { $channel }
+plan-exceptions = 🏖️ **Unntak**
+plan-closes-before-opens = 🚫 Bestilling må stenges etter at den åpnes
+plan-added = 📅 Bestilling åpnes hver { $weekday } { $opens }–{ $closes } i { $channel } (`#{ $id }`)
+plan-removed = 📅 Fjernet `#{ $id }` fra planen
+plan-not-found = 📅 Fant ikke `#{ $id }` i planen
+plan-exception-added = 🏖️ Bestilling åpnes ikke automatisk { $date }
+plan-exception-exists = 🏖️ { $date } er allerede et unntak
+plan-exception-removed = 📅 Planen følges som vanlig { $date }
+plan-exception-not-found = 📅 { $date } er ikke et unntak
+plan-save-failed = ❌ Klarte ikke å oppdatere planen
+
+## Innstillinger
+
+settings-save-failed = ❌ Klarte ikke å lagre innstillingene
+settings-max-waffles = ⚙️ Man kan nå bestille opptil **{ $amount }** vafler om gangen
+settings-variants-none = ⚙️ Det er nå bare én kø
+settings-variants = ⚙️ Disse variantene har nå egen kø: **{ $variants }**
+settings-serving-fifo = ⚙️ Køen serveres nå i den rekkefølgen folk ble med
+settings-serving-first-timers-first = ⚙️ De som ikke har fått vaffel ennå blir nå servert før de som har fått
+settings-user-limit-session = ⚙️ Hver person kan nå få maks **{ $max }** vafler hver gang det stekes
+settings-user-limit-day = ⚙️ Hver person kan nå få maks **{ $max }** vafler per dag
+settings-user-limit-none = ⚙️ Det er ingen grense for hvor mange vafler hver person kan få
+settings-cooldown = ⚙️ Man må nå vente **{ $minutes }** minutter etter å ha fått vaffel før man kan bestille igjen
+settings-cooldown-none = ⚙️ Man kan nå bestille igjen med en gang man har fått vaffel
+settings-overflow-refuse = ⚙️ Bestillinger avvises når det er tomt for røre
+settings-overflow-waitlist = ⚙️ Bestillinger settes på venteliste når det er tomt for røre
+settings-idle-close = ⚙️ Bestilling stenges nå automatisk etter **{ $minutes }** minutter uten steking
+settings-idle-close-none = ⚙️ Bestilling stenges nå aldri automatisk
+settings-channels-all = alle kanaler
+settings-channels = ⚙️ Vafler bestilles nå i { $ordering }, og orakelkommandoene brukes i { $oracle }
+settings-channels-audit = . Orakelhandlinger logges i { $channel }
+settings-language = ⚙️ Vaffelbot snakker nå bokmål i denne serveren
+
+language-set = 🗣️ Vaffelbot svarer deg nå på bokmål
+language-follows-guild = 🗣️ Vaffelbot svarer deg nå på språket til serveren
+language-save-failed = ❌ Klarte ikke å lagre språket
+
+## Maler
+
+template-name-opened = Åpnet
+template-name-opened_lottery = Lotteri åpnet
+template-name-closing_soon = Stenger snart
+template-name-closed = Stengt
+template-name-baked = Stekt
+template-name-joined = Bestilt
+templates-title = 📝 **Maler**
+templates-standard = _Standard_
+templates-placeholders = Plassholdere: { $placeholders }
+templates-no-placeholders = Ingen plassholdere
+templates-error-empty = 🚫 Malen kan ikke være tom
+templates-error-too-long = 🚫 Malen kan ikke være lengre enn { $max } tegn
+templates-error-unclosed = 🚫 En `{"{"}` mangler `{"}"}`. Skriv `{"{{"}` for å vise en klamme.
+templates-error-unopened = 🚫 En `{"}"}` mangler `{"{"}`. Skriv `{"}}"}` for å vise en klamme.
+templates-error-unknown = 🚫 Meldingen har ingen plassholder som heter `{ $name }`.
+templates-preview = 👀 Slik blir meldingen:
+templates-saved = 📝 Lagret malen for **{ $name }**. Slik blir meldingen:
+templates-reset = 📝 **{ $name }** bruker nå standardteksten
+templates-not-changed = 📝 **{ $name }** bruker allerede standardteksten
+templates-save-failed = ❌ Klarte ikke å oppdatere malen
+
+## API-nøkler
+
+api-title = 🔑 **API-nøkler**
+api-empty = 🔑 Serveren har ingen API-nøkler
+api-entry = `#{ $id }` **{ $label }**, laget <t:{ $time }:d>
+api-invalid-label = 🚫 Navnet må være mellom 1 og { $max } tegn
+api-created = 🔑 Laget API-nøkkel `#{ $id }` **{ $label }**. Kopier den nå, den vises ikke igjen:
+api-revoked = 🔑 Slettet API-nøkkel `#{ $id }`
+api-not-found = 🚫 Fant ingen API-nøkkel `#{ $id }`
+api-save-failed = ❌ Klarte ikke å oppdatere API-nøklene
+
+## Logg
+
+audit-title-open = 🔓️ Bestilling åpnet
+audit-title-close = 🔒️ Bestilling stengt
+audit-title-bake = 🧇 Vafler stekt
+audit-title-draw = 🎟️ Lotteri trukket
+audit-title-capacity = 🥣 Kapasitet endret
+audit-title-remove = 🚪 Fjernet fra køen
+audit-title-move = ↕️ Flyttet i køen
+audit-title-api_token = 🔑 API-nøkkel
+audit-by = Av
+audit-automatic = 🤖 Automatisk
+audit-open-queue = Kø
+audit-open-lottery = Lotteri
+audit-open-closes-at = stenger <t:{ $time }:t>
+audit-capacity = Røre til { $capacity } vafler
+audit-capacity-unlimited = Ingen grense
+audit-close = Stengt med `/stopp`, { $count } vafler stekt i dag
+audit-close-deadline = Tiden var ute
+audit-close-idle = Ingen har stekt på { $minutes } minutter
+audit-open-schedule = Åpnet fra plan `#{ $id }`
+audit-close-schedule = Stengt fra plan `#{ $id }`
+audit-bake = { $count } i { $lane } til { $recipients }
+audit-bake-nobody = Ingen å steke til i { $lane }
+audit-draw = { $count } av { $participants } påmeldte: { $winners }
+audit-draw-id = { $count } av { $participants } påmeldte: { $winners } (trekning #{ $id })
+audit-api = via API-nøkkel **{ $label }**
+audit-close-api = Stengt via API, { $count } vafler stekt i dag
+audit-remove = { $user } fjernet fra { $lane }
+audit-move = { $user } flyttet til nummer { $position } i { $lane }
+audit-api-token-created = Laget API-nøkkel `#{ $id }` **{ $label }**
+audit-api-token-revoked = Slettet API-nøkkel `#{ $id }`
+
+## Kommandoer
+
+command-ping = ping
+command-ping-description = Ping vaffelbot
+
+command-waffle = vaffel
+command-waffle-description = Få en orakel til å steke vaffel til deg
+command-waffle-antall = antall
+command-waffle-antall-description = Hvor mange vafler?
+command-waffle-topping = topping
+command-waffle-topping-description = Hva vil du ha på? Skill flere med komma
+command-waffle-variant = variant
+command-waffle-variant-description = Hvilken variant? F.eks. glutenfri
+
+command-queue = kø
+command-queue-description = Se hvor mange som er foran deg i køen
+
+command-bake = stekt
+command-bake-description = Stek vaffel
+command-bake-amount = antall
+command-bake-amount-description = Hvor mange vafler?
+command-bake-variant = variant
+command-bake-variant-description = Hvilken variant? Standard hvis tom
+
+command-capacity = kapasitet
+command-capacity-description = Endre hvor mange vafler det er røre til
+command-capacity-antall = antall
+command-capacity-antall-description = Hvor mange vafler er det røre til totalt? Tom for ingen grense
+
+command-open = start
+command-open-description = Åpne for bestilling av vafler
+command-open-modus = modus
+command-open-modus-description = Kø eller lotteri? Standard er kø
+command-open-modus-0 = Kø
+command-open-modus-1 = Lotteri
+command-open-kapasitet = kapasitet
+command-open-kapasitet-description = Hvor mange vafler er det røre til? Tom for ingen grense
+command-open-varighet = varighet
+command-open-varighet-description = Hvor lenge bestilling er åpen, f.eks. 90m eller 1t30m
+command-open-til = til
+command-open-til-description = Når bestilling stenges, f.eks. 14:00
+
+command-close = stopp
+command-close-description = Steng for bestilling av vafler
+
+command-draw = trekk
+command-draw-description = Trekk vinnere i vaffellotteriet
+command-draw-antall = antall
+command-draw-antall-description = Hvor mange vinnere?
+
+command-language = språk
+command-language-description = Velg hvilket språk vaffelbot svarer deg på
+command-language-sprak = språk
+command-language-sprak-description = Språket du vil ha svar på. Tom for å bruke serverens språk
+
+command-menu = meny
+command-menu-description = Se og endre hva man kan ha på vaffelen
+command-menu-show = vis
+command-menu-show-description = Se hva man kan ha på vaffelen
+command-menu-add = legg-til
+command-menu-add-description = Legg til noe man kan ha på vaffelen
+command-menu-add-navn = navn
+command-menu-add-navn-description = Navn på tilbehøret
+command-menu-remove = fjern
+command-menu-remove-description = Fjern noe fra menyen
+command-menu-remove-navn = navn
+command-menu-remove-navn-description = Navn på tilbehøret
+
+command-plan = plan
+command-plan-description = Åpne og stenge bestilling automatisk hver uke
+command-plan-show = vis
+command-plan-show-description = Se når bestilling åpnes og stenges automatisk
+command-plan-add = legg-til
+command-plan-add-description = Åpne og stenge bestilling automatisk på en ukedag
+command-plan-add-dag = dag
+command-plan-add-dag-description = Hvilken dag?
+command-plan-add-dag-0 = Mandag
+command-plan-add-dag-1 = Tirsdag
+command-plan-add-dag-2 = Onsdag
+command-plan-add-dag-3 = Torsdag
+command-plan-add-dag-4 = Fredag
+command-plan-add-dag-5 = Lørdag
+command-plan-add-dag-6 = Søndag
+command-plan-add-fra = fra
+command-plan-add-fra-description = Når bestilling åpnes, f.eks. 12:00
+command-plan-add-til = til
+command-plan-add-til-description = Når bestilling stenges, f.eks. 14:00
+command-plan-add-kanal = kanal
+command-plan-add-kanal-description = Hvor beskjedene skal sendes. Standard er denne kanalen
+command-plan-remove = fjern
+command-plan-remove-description = Fjern en dag fra planen
+command-plan-remove-id = id
+command-plan-remove-id-description = Nummeret fra `/plan vis`
+command-plan-add_exception = unntak
+command-plan-add_exception-description = Ikke åpne bestilling automatisk på en dato, f.eks. i ferien
+command-plan-add_exception-dato = dato
+command-plan-add_exception-dato-description = Datoen, f.eks. 2026-12-24
+command-plan-remove_exception = fjern-unntak
+command-plan-remove_exception-description = Følg planen som vanlig på en dato igjen
+command-plan-remove_exception-dato = dato
+command-plan-remove_exception-dato-description = Datoen, f.eks. 2026-12-24
+
+command-settings = innstillinger
+command-settings-description = Endre innstillinger for vaffelbot
+command-settings-max_waffles = maks-vafler
+command-settings-max_waffles-description = Sett hvor mange vafler man kan bestille om gangen
+command-settings-max_waffles-amount = antall
+command-settings-max_waffles-amount-description = Maks antall vafler per bestilling
+command-settings-variants = varianter
+command-settings-variants-description = Sett hvilke varianter som har egen kø, f.eks. glutenfri
+command-settings-variants-varianter = varianter
+command-settings-variants-varianter-description = Varianter skilt med komma. Tom for bare standard
+command-settings-serving = servering
+command-settings-serving-description = Velg hvem som får vafler først når det stekes
+command-settings-serving-modus = modus
+command-settings-serving-modus-description = Hvem skal få vafler først?
+command-settings-serving-modus-0 = Først til mølla
+command-settings-serving-modus-1 = De som ikke har fått vaffel først
+command-settings-user_limit = grense
+command-settings-user_limit-description = Sett hvor mange vafler hver person kan få
+command-settings-user_limit-antall = antall
+command-settings-user_limit-antall-description = Maks antall vafler per person. Tom for ingen grense
+command-settings-user_limit-periode = periode
+command-settings-user_limit-periode-description = Gjelder grensen per åpning eller per dag?
+command-settings-user_limit-periode-0 = Per åpning
+command-settings-user_limit-periode-1 = Per dag
+command-settings-cooldown = pause
+command-settings-cooldown-description = Sett hvor lenge man må vente etter å ha fått vaffel før man kan bestille igjen
+command-settings-cooldown-minutter = minutter
+command-settings-cooldown-minutter-description = Antall minutter. Tom for ingen pause
+command-settings-overflow = fullt
+command-settings-overflow-description = Velg hva som skjer med bestillinger når det er tomt for røre
+command-settings-overflow-modus = modus
+command-settings-overflow-modus-description = Hva skal skje når det ikke er mer røre?
+command-settings-overflow-modus-0 = Avvis bestillingen
+command-settings-overflow-modus-1 = Sett på venteliste
+command-settings-idle_close = inaktiv
+command-settings-idle_close-description = Sett hvor lenge bestilling kan stå åpen uten at noen steker før den stenges
+command-settings-idle_close-minutter = minutter
+command-settings-idle_close-minutter-description = Antall minutter. Tom for å aldri stenge automatisk
+command-settings-channels = kanaler
+command-settings-channels-description = Velg hvor vafler bestilles og hvor orakelkommandoene brukes
+command-settings-channels-bestilling = bestilling
+command-settings-channels-bestilling-description = Kanalen for /vaffel og /kø. Tom for å beholde den som er valgt
+command-settings-channels-orakel = orakel
+command-settings-channels-orakel-description = Kanalen for /start, /stekt og /stopp. Tom for å beholde den som er valgt
+command-settings-channels-logg = logg
+command-settings-channels-logg-description = Kanalen orakelhandlinger logges i. Tom for å beholde den som er valgt
+command-settings-channels-fjern = fjern
+command-settings-channels-fjern-description = Kanalen som skal fjernes, så alle kanaler kan brukes eller ingenting logges
+command-settings-channels-fjern-0 = Bestilling
+command-settings-channels-fjern-1 = Orakel
+command-settings-channels-fjern-2 = Logg
+command-settings-channels-fjern-3 = Alle
+command-settings-language = språk
+command-settings-language-description = Velg hvilket språk vaffelbot bruker i serveren
+command-settings-language-sprak = språk
+command-settings-language-sprak-description = Språket for beskjeder til alle i serveren
+
+command-templates = mal
+command-templates-description = Endre ordlyden i meldingene til vaffelbot
+command-templates-show = vis
+command-templates-show-description = Se hvilke meldinger som har egen ordlyd
+command-templates-preview = forhåndsvis
+command-templates-preview-description = Se hvordan en mal blir uten å lagre den
+command-templates-preview-melding = melding
+command-templates-preview-melding-description = Hvilken melding?
+command-templates-preview-melding-0 = Åpnet
+command-templates-preview-melding-1 = Lotteri åpnet
+command-templates-preview-melding-2 = Stenger snart
+command-templates-preview-melding-3 = Stengt
+command-templates-preview-melding-4 = Stekt
+command-templates-preview-melding-5 = Bestilt
+command-templates-preview-tekst = tekst
+command-templates-preview-tekst-description = Ny tekst, f.eks. 🧇 {"{"}count{"}"} vafler til {"{"}mentions{"}"}
+command-templates-set = sett
+command-templates-set-description = Gi en melding egen ordlyd
+command-templates-set-melding = melding
+command-templates-set-melding-description = Hvilken melding?
+command-templates-set-melding-0 = Åpnet
+command-templates-set-melding-1 = Lotteri åpnet
+command-templates-set-melding-2 = Stenger snart
+command-templates-set-melding-3 = Stengt
+command-templates-set-melding-4 = Stekt
+command-templates-set-melding-5 = Bestilt
+command-templates-set-tekst = tekst
+command-templates-set-tekst-description = Ny tekst, f.eks. 🧇 {"{"}count{"}"} vafler til {"{"}mentions{"}"}
+command-templates-reset = tilbakestill
+command-templates-reset-description = Gå tilbake til standardteksten for en melding
+command-templates-reset-melding = melding
+command-templates-reset-melding-description = Hvilken melding?
+command-templates-reset-melding-0 = Åpnet
+command-templates-reset-melding-1 = Lotteri åpnet
+command-templates-reset-melding-2 = Stenger snart
+command-templates-reset-melding-3 = Stengt
+command-templates-reset-melding-4 = Stekt
+command-templates-reset-melding-5 = Bestilt
+
+command-api = api
+command-api-description = La ting utenfor Discord styre køen
+command-api-list = vis
+command-api-list-description = Se hvilke API-nøkler serveren har
+command-api-create = ny
+command-api-create-description = Lag en ny API-nøkkel
+command-api-create-navn = navn
+command-api-create-navn-description = Hva skal nøkkelen brukes til? F.eks. Nettbrett i standen
+command-api-revoke = slett
+command-api-revoke-description = Slett en API-nøkkel så den ikke kan brukes lenger
+command-api-revoke-id = id
+command-api-revoke-id-description = Nummeret til nøkkelen, se /api vis
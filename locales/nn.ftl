@@ -0,0 +1,435 @@
+## Felles
+
+ping-pong = 🏓 Pong!
+session-closed = 🔒️ Bestilling er stengd
+lottery-already-drawn = 🎟️ Trekninga er allereie gjord
+unknown-variant = 🚫 Finn ingen variant som heiter **{ $variant }**.
+invalid-time = 🚫 Klokkeslett må skrivast som TT:MM, t.d. 14:00
+invalid-date = 🚫 Datoen må skrivast som ÅÅÅÅ-MM-DD, t.d. 2026-12-24
+list-and = og
+access-denied = ❌ Du har ikkje tilgang til denne kommandoen.
+channel-ordering-hint = 🧇 Vaflar vert bestilte i { $channel }
+channel-oracle-hint = 🔮 Denne kommandoen vert brukt i { $channel }
+channel-sent-to = 📣 Sendt i { $channel }
+
+weekday-mon = måndag
+weekday-tue = tysdag
+weekday-wed = onsdag
+weekday-thu = torsdag
+weekday-fri = fredag
+weekday-sat = laurdag
+weekday-sun = sundag
+
+## Kunngjeringar
+
+announce-opened = 🔓️ Bestilling er no opna
+announce-opened-lottery = 🎟️ Påmelding til vaffellotteriet er no opna. Bruk `/vaffel` for å vere med!
+announce-capacity = 🥣 Det er røre til **{ $capacity }** vaflar.
+announce-closes-at = ⏰ Bestilling stengjer <t:{ $time }:t> (<t:{ $time }:R>).
+announce-closing-soon = ⏰ Bestilling stengjer <t:{ $time }:R>! Bruk `/vaffel` no viss du vil ha ein vaffel.
+announce-closed = 🔒️ Bestilling er no stengd
+announce-closed-idle = 💤 Ingen har steikt vaflar på { $minutes } minutt, så bestilling vart stengd automatisk.
+stats-title = 📊 **Statistikk for dagen**
+stats-total = Totalt steikt: { $count } { $count ->
+        [one] vaffel
+       *[other] vaflar
+    }
+stats-top = 🏆 **Flest bestillingar:**
+stats-top-user = { $medal } { $user } - { $count } { $count ->
+        [one] vaffel
+       *[other] vaflar
+    }
+presence-baking = 🧇 Lagar vaflar
+presence-baking-guilds = 🧇 Lagar vaflar i { $count } serverar
+
+## Kø og bestilling
+
+queue-closed = 🚨 Bestilling er stengd
+queue-waitlisted = 📝 Du står på ventelista som nummer { $position }
+queue-not-in-lottery = 🚨 Du er ikkje med i trekninga.
+queue-not-in-queue = 🚨 Du er ikkje i køen.
+queue-in-lottery = 🎟️ Du er med i trekninga.
+queue-position = 😎 Du er { $position } i køen
+queue-positions = 😎 Du står i kø:
+queue-lane-position = - **{ $lane }**: nummer { $position }
+capacity-remaining = 🥣 Det er røre til **{ $remaining }** vaflar til.
+queue-name = køen
+queue-name-lane = køen for **{ $lane }**
+
+waffle-closed = 🏮 Bestilling er stengd
+waffle-already-waitlisted = 📝 Du står **allereie** på ventelista. Du er nummer **{ $position }** på lista.
+waffle-already-in-lottery = 🎟️ Du er **allereie** med i trekninga.
+waffle-already-in-queue = ⏲️ Du er **allereie** i { $queue }. Du er nummer **{ $position }** i køen.
+waffle-unknown-option = 🚫 **{ $option }** er ikkje på menyen. Sjå `/meny vis` for kva som finst.
+waffle-out-of-batter = 🥣 Det er dessverre tomt for røre.
+waffle-batter-left = 🥣 Det er berre røre til **{ $remaining }** vaflar til.
+waffle-waitlisted = 📝 Det er ikkje nok røre akkurat no, men du står på ventelista som nummer **{ $position }**.
+waffle-joined-lottery = 🎟️ Du er no med i trekninga. Lukke til!
+waffle-joined = { $amount ->
+        [1] ⏲️ Du er no i { $queue }. Du er nummer **{ $position }** i køen.
+       *[other] ⏲️ Du er no i { $queue } med **{ $amount }** vaflar. Du er nummer **{ $position }** i køen.
+    }
+
+refusal-too-many = 🚫 Du kan bestille mellom **1** og **{ $max }** vaflar om gongen.
+refusal-limit = 🚫 Du kan få maks **{ $limit }** vaflar{ $remaining ->
+        [0] {""}
+       *[other] , og har **{ $remaining }** att
+    }. { $retry ->
+        [none] Du kan bestille att neste gong det vert steikt.
+       *[other] Du kan bestille att <t:{ $retry }:R>.
+    }
+refusal-cooldown = ⏳ Du fekk nett vaffel. Du kan bestille att <t:{ $until }:t> (<t:{ $until }:R>).
+
+## Orakel
+
+bake-not-drawn = 🎟️ Trekninga er ikkje gjord enno. Bruk `/trekk` først.
+bake-nobody = 😟 Ingen å steikje vaflar til.
+bake-baked = 🧇 Steikte { $count ->
+        [one] ein vaffel
+       *[other] { $count } vaflar
+    } til:
+
+capacity-set = 🥣 Det er no røre til **{ $capacity }** vaflar, og **{ $remaining }** er ikkje bestilte enno.
+capacity-unlimited = 🥣 Det er inga grense for kor mange vaflar som kan bestillast.
+capacity-promoted = 📝 Frå ventelista og inn i køen:
+
+close-already-closed = 🔒️ Bestilling er allereie stengd
+
+open-duration-and-until = 🚫 Vel anten varigheit eller når bestilling vert stengd, ikkje begge
+open-invalid-duration = 🚫 Varigheita må skrivast som t.d. 90m, 2t eller 1t30m, og vere på høgst 24 timar
+open-time-passed = 🚫 Klokka har allereie passert { $time } i dag
+open-already-open = 🔓️ Bestilling er allereie opna
+open-deadline-save-failed = ❌ Klarte ikkje å lagre når bestilling vert stengd. Hugs `/stopp`!
+
+draw-not-lottery = 🚫 Bestillinga er ikkje eit lotteri
+draw-nobody = 😟 Ingen melde seg på lotteriet.
+draw-winners = 🎟️ Trekte { $winners } av { $participants } påmelde. Gratulerer til:
+draw-seed-id = 🔎 Trekning #{ $id }, frø `{ $seed }`
+draw-seed = 🔎 Frø `{ $seed }`
+
+menu-empty = 📋 Menyen er tom. Vaflane kjem naturell.
+menu-title = 📋 **Meny**
+menu-invalid-name = 🚫 Namnet kan ikkje vere tomt eller innehalde komma
+menu-added = 📋 La til **{ $name }** på menyen
+menu-already-added = 📋 **{ $name }** er allereie på menyen
+menu-removed = 📋 Fjerna **{ $name }** frå menyen
+menu-not-added = 📋 **{ $name }** er ikkje på menyen
+menu-save-failed = ❌ Klarte ikkje å oppdatere menyen
+
+plan-empty = 📅 Det er ingen plan. Bruk `/plan legg-til` for å lage ein.
+plan-title = 📅 **Plan**
+plan-entry = `#{ $id }` Kvar { $weekday } { $opens }–{ $closes } i { $channel }
+plan-exceptions = 🏖️ **Unntak**
+plan-closes-before-opens = 🚫 Bestilling må stengjast etter at ho vert opna
+plan-added = 📅 Bestilling vert opna kvar { $weekday } { $opens }–{ $closes } i { $channel } (`#{ $id }`)
+plan-removed = 📅 Fjerna `#{ $id }` frå planen
+plan-not-found = 📅 Fann ikkje `#{ $id }` i planen
+plan-exception-added = 🏖️ Bestilling vert ikkje opna automatisk { $date }
+plan-exception-exists = 🏖️ { $date } er allereie eit unntak
+plan-exception-removed = 📅 Planen vert følgd som vanleg { $date }
+plan-exception-not-found = 📅 { $date } er ikkje eit unntak
+plan-save-failed = ❌ Klarte ikkje å oppdatere planen
+
+## Innstillingar
+
+settings-save-failed = ❌ Klarte ikkje å lagre innstillingane
+settings-max-waffles = ⚙️ Ein kan no bestille opptil **{ $amount }** vaflar om gongen
+settings-variants-none = ⚙️ Det er no berre éin kø
+settings-variants = ⚙️ Desse variantane har no eigen kø: **{ $variants }**
+settings-serving-fifo = ⚙️ Køen vert no servert i den rekkjefølgja folk vart med
+settings-serving-first-timers-first = ⚙️ Dei som ikkje har fått vaffel enno vert no serverte før dei som har fått
+settings-user-limit-session = ⚙️ Kvar person kan no få maks **{ $max }** vaflar kvar gong det vert steikt
+settings-user-limit-day = ⚙️ Kvar person kan no få maks **{ $max }** vaflar per dag
+settings-user-limit-none = ⚙️ Det er inga grense for kor mange vaflar kvar person kan få
+settings-cooldown = ⚙️ Ein må no vente **{ $minutes }** minutt etter å ha fått vaffel før ein kan bestille att
+settings-cooldown-none = ⚙️ Ein kan no bestille att med ein gong ein har fått vaffel
+settings-overflow-refuse = ⚙️ Bestillingar vert avviste når det er tomt for røre
+settings-overflow-waitlist = ⚙️ Bestillingar vert sette på venteliste når det er tomt for røre
+settings-idle-close = ⚙️ Bestilling vert no stengd automatisk etter **{ $minutes }** minutt utan steiking
+settings-idle-close-none = ⚙️ Bestilling vert no aldri stengd automatisk
+settings-channels-all = alle kanalar
+settings-channels = ⚙️ Vaflar vert no bestilte i { $ordering }, og orakelkommandoane vert brukte i { $oracle }
+settings-channels-audit = . Orakelhandlingar vert logga i { $channel }
+settings-language = ⚙️ Vaffelbot snakkar no nynorsk i denne serveren
+
+language-set = 🗣️ Vaffelbot svarar deg no på nynorsk
+language-follows-guild = 🗣️ Vaffelbot svarar deg no på språket til serveren
+language-save-failed = ❌ Klarte ikkje å lagre språket
+
+## Malar
+
+template-name-opened = Opna
+template-name-opened_lottery = Lotteri opna
+template-name-closing_soon = Stengjer snart
+template-name-closed = Stengd
+template-name-baked = Steikt
+template-name-joined = Bestilt
+templates-title = 📝 **Malar**
+templates-standard = _Standard_
+templates-placeholders = Plasshaldarar: { $placeholders }
+templates-no-placeholders = Ingen plasshaldarar
+templates-error-empty = 🚫 Malen kan ikkje vere tom
+templates-error-too-long = 🚫 Malen kan ikkje vere lengre enn { $max } teikn
+templates-error-unclosed = 🚫 Ei `{"{"}` manglar `{"}"}`. Skriv `{"{{"}` for å vise ei klamme.
+templates-error-unopened = 🚫 Ei `{"}"}` manglar `{"{"}`. Skriv `{"}}"}` for å vise ei klamme.
+templates-error-unknown = 🚫 Meldinga har ingen plasshaldar som heiter `{ $name }`.
+templates-preview = 👀 Slik vert meldinga:
+templates-saved = 📝 Lagra malen for **{ $name }**. Slik vert meldinga:
+templates-reset = 📝 **{ $name }** brukar no standardteksten
+templates-not-changed = 📝 **{ $name }** brukar allereie standardteksten
+templates-save-failed = ❌ Klarte ikkje å oppdatere malen
+
+## API-nøklar
+
+api-title = 🔑 **API-nøklar**
+api-empty = 🔑 Serveren har ingen API-nøklar
+api-entry = `#{ $id }` **{ $label }**, laga <t:{ $time }:d>
+api-invalid-label = 🚫 Namnet må vere mellom 1 og { $max } teikn
+api-created = 🔑 Laga API-nøkkel `#{ $id }` **{ $label }**. Kopier han no, han blir ikkje vist igjen:
+api-revoked = 🔑 Sletta API-nøkkel `#{ $id }`
+api-not-found = 🚫 Fann ingen API-nøkkel `#{ $id }`
+api-save-failed = ❌ Klarte ikkje å oppdatere API-nøklane
+
+## Logg
+
+audit-title-open = 🔓️ Bestilling opna
+audit-title-close = 🔒️ Bestilling stengd
+audit-title-bake = 🧇 Vaflar steikte
+audit-title-draw = 🎟️ Lotteri trekt
+audit-title-capacity = 🥣 Kapasitet endra
+audit-title-remove = 🚪 Fjerna frå køen
+audit-title-move = ↕️ Flytta i køen
+audit-title-api_token = 🔑 API-nøkkel
+audit-by = Av
+audit-automatic = 🤖 Automatisk
+audit-open-queue = Kø
+audit-open-lottery = Lotteri
+audit-open-closes-at = stengjer <t:{ $time }:t>
+audit-capacity = Røre til { $capacity } vaflar
+audit-capacity-unlimited = Inga grense
+audit-close = Stengd med `/stopp`, { $count } vaflar steikte i dag
+audit-close-deadline = Tida var ute
+audit-close-idle = Ingen har steikt på { $minutes } minutt
+audit-open-schedule = Opna frå plan `#{ $id }`
+audit-close-schedule = Stengd frå plan `#{ $id }`
+audit-bake = { $count } i { $lane } til { $recipients }
+audit-bake-nobody = Ingen å steikje til i { $lane }
+audit-draw = { $count } av { $participants } påmelde: { $winners }
+audit-draw-id = { $count } av { $participants } påmelde: { $winners } (trekning #{ $id })
+audit-api = via API-nøkkel **{ $label }**
+audit-close-api = Stengd via API, { $count } vaflar steikte i dag
+audit-remove = { $user } fjerna frå { $lane }
+audit-move = { $user } flytta til nummer { $position } i { $lane }
+audit-api-token-created = Laga API-nøkkel `#{ $id }` **{ $label }**
+audit-api-token-revoked = Sletta API-nøkkel `#{ $id }`
+
+## Kommandoar
+
+command-ping = ping
+command-ping-description = Ping vaffelbot
+
+command-waffle = vaffel
+command-waffle-description = Få eit orakel til å steikje vaffel til deg
+command-waffle-antall = tal
+command-waffle-antall-description = Kor mange vaflar?
+command-waffle-topping = topping
+command-waffle-topping-description = Kva vil du ha på? Skil fleire med komma
+command-waffle-variant = variant
+command-waffle-variant-description = Kva variant? T.d. glutenfri
+
+command-queue = kø
+command-queue-description = Sjå kor mange som er framfor deg i køen
+
+command-bake = steikt
+command-bake-description = Steik vaffel
+command-bake-amount = tal
+command-bake-amount-description = Kor mange vaflar?
+command-bake-variant = variant
+command-bake-variant-description = Kva variant? Standard viss tom
+
+command-capacity = kapasitet
+command-capacity-description = Endre kor mange vaflar det er røre til
+command-capacity-antall = tal
+command-capacity-antall-description = Kor mange vaflar er det røre til totalt? Tom for inga grense
+
+command-open = start
+command-open-description = Opne for bestilling av vaflar
+command-open-modus = modus
+command-open-modus-description = Kø eller lotteri? Standard er kø
+command-open-modus-0 = Kø
+command-open-modus-1 = Lotteri
+command-open-kapasitet = kapasitet
+command-open-kapasitet-description = Kor mange vaflar er det røre til? Tom for inga grense
+command-open-varighet = varigheit
+command-open-varighet-description = Kor lenge bestilling er open, t.d. 90m eller 1t30m
+command-open-til = til
+command-open-til-description = Når bestilling vert stengd, t.d. 14:00
+
+command-close = stopp
+command-close-description = Steng for bestilling av vaflar
+
+command-draw = trekk
+command-draw-description = Trekk vinnarar i vaffellotteriet
+command-draw-antall = tal
+command-draw-antall-description = Kor mange vinnarar?
+
+command-language = språk
+command-language-description = Vel kva språk vaffelbot svarar deg på
+command-language-sprak = språk
+command-language-sprak-description = Språket du vil ha svar på. Tom for å bruke språket til serveren
+
+command-menu = meny
+command-menu-description = Sjå og endre kva ein kan ha på vaffelen
+command-menu-show = vis
+command-menu-show-description = Sjå kva ein kan ha på vaffelen
+command-menu-add = legg-til
+command-menu-add-description = Legg til noko ein kan ha på vaffelen
+command-menu-add-navn = namn
+command-menu-add-navn-description = Namnet på tilbehøyret
+command-menu-remove = fjern
+command-menu-remove-description = Fjern noko frå menyen
+command-menu-remove-navn = namn
+command-menu-remove-navn-description = Namnet på tilbehøyret
+
+command-plan = plan
+command-plan-description = Opne og stengje bestilling automatisk kvar veke
+command-plan-show = vis
+command-plan-show-description = Sjå når bestilling vert opna og stengd automatisk
+command-plan-add = legg-til
+command-plan-add-description = Opne og stengje bestilling automatisk på ein vekedag
+command-plan-add-dag = dag
+command-plan-add-dag-description = Kva dag?
+command-plan-add-dag-0 = Måndag
+command-plan-add-dag-1 = Tysdag
+command-plan-add-dag-2 = Onsdag
+command-plan-add-dag-3 = Torsdag
+command-plan-add-dag-4 = Fredag
+command-plan-add-dag-5 = Laurdag
+command-plan-add-dag-6 = Sundag
+command-plan-add-fra = frå
+command-plan-add-fra-description = Når bestilling vert opna, t.d. 12:00
+command-plan-add-til = til
+command-plan-add-til-description = Når bestilling vert stengd, t.d. 14:00
+command-plan-add-kanal = kanal
+command-plan-add-kanal-description = Kvar meldingane skal sendast. Standard er denne kanalen
+command-plan-remove = fjern
+command-plan-remove-description = Fjern ein dag frå planen
+command-plan-remove-id = id
+command-plan-remove-id-description = Nummeret frå `/plan vis`
+command-plan-add_exception = unntak
+command-plan-add_exception-description = Ikkje opne bestilling automatisk på ein dato, t.d. i ferien
+command-plan-add_exception-dato = dato
+command-plan-add_exception-dato-description = Datoen, t.d. 2026-12-24
+command-plan-remove_exception = fjern-unntak
+command-plan-remove_exception-description = Følg planen som vanleg på ein dato att
+command-plan-remove_exception-dato = dato
+command-plan-remove_exception-dato-description = Datoen, t.d. 2026-12-24
+
+command-settings = innstillingar
+command-settings-description = Endre innstillingane til vaffelbot
+command-settings-max_waffles = maks-vaflar
+command-settings-max_waffles-description = Set kor mange vaflar ein kan bestille om gongen
+command-settings-max_waffles-amount = tal
+command-settings-max_waffles-amount-description = Maks tal på vaflar per bestilling
+command-settings-variants = variantar
+command-settings-variants-description = Set kva variantar som har eigen kø, t.d. glutenfri
+command-settings-variants-varianter = variantar
+command-settings-variants-varianter-description = Variantar skilde med komma. Tom for berre standard
+command-settings-serving = servering
+command-settings-serving-description = Vel kven som får vaflar først når det vert steikt
+command-settings-serving-modus = modus
+command-settings-serving-modus-description = Kven skal få vaflar først?
+command-settings-serving-modus-0 = Først til mølla
+command-settings-serving-modus-1 = Dei som ikkje har fått vaffel først
+command-settings-user_limit = grense
+command-settings-user_limit-description = Set kor mange vaflar kvar person kan få
+command-settings-user_limit-antall = tal
+command-settings-user_limit-antall-description = Maks tal på vaflar per person. Tom for inga grense
+command-settings-user_limit-periode = periode
+command-settings-user_limit-periode-description = Gjeld grensa per opning eller per dag?
+command-settings-user_limit-periode-0 = Per opning
+command-settings-user_limit-periode-1 = Per dag
+command-settings-cooldown = pause
+command-settings-cooldown-description = Set kor lenge ein må vente etter å ha fått vaffel før ein kan bestille att
+command-settings-cooldown-minutter = minutt
+command-settings-cooldown-minutter-description = Tal på minutt. Tom for ingen pause
+command-settings-overflow = fullt
+command-settings-overflow-description = Vel kva som skjer med bestillingar når det er tomt for røre
+command-settings-overflow-modus = modus
+command-settings-overflow-modus-description = Kva skal skje når det ikkje er meir røre?
+command-settings-overflow-modus-0 = Avvis bestillinga
+command-settings-overflow-modus-1 = Set på venteliste
+command-settings-idle_close = inaktiv
+command-settings-idle_close-description = Set kor lenge bestilling kan stå open utan at nokon steikjer før ho vert stengd
+command-settings-idle_close-minutter = minutt
+command-settings-idle_close-minutter-description = Tal på minutt. Tom for å aldri stengje automatisk
+command-settings-channels = kanalar
+command-settings-channels-description = Vel kvar vaflar vert bestilte og kvar orakelkommandoane vert brukte
+command-settings-channels-bestilling = bestilling
+command-settings-channels-bestilling-description = Kanalen for /vaffel og /kø. Tom for å halde på den som er vald
+command-settings-channels-orakel = orakel
+command-settings-channels-orakel-description = Kanalen for /start, /stekt og /stopp. Tom for å halde på den som er vald
+command-settings-channels-logg = logg
+command-settings-channels-logg-description = Kanalen orakelhandlingar vert logga i. Tom for å halde på den som er vald
+command-settings-channels-fjern = fjern
+command-settings-channels-fjern-description = Kanalen som skal fjernast, så alle kanalar kan brukast eller ingenting loggast
+command-settings-channels-fjern-0 = Bestilling
+command-settings-channels-fjern-1 = Orakel
+command-settings-channels-fjern-2 = Logg
+command-settings-channels-fjern-3 = Alle
+command-settings-language = språk
+command-settings-language-description = Vel kva språk vaffelbot brukar i serveren
+command-settings-language-sprak = språk
+command-settings-language-sprak-description = Språket for meldingar til alle i serveren
+
+command-templates = mal
+command-templates-description = Endre ordlyden i meldingane til vaffelbot
+command-templates-show = vis
+command-templates-show-description = Sjå kva meldingar som har eigen ordlyd
+command-templates-preview = førehandsvis
+command-templates-preview-description = Sjå korleis ein mal vert utan å lagre han
+command-templates-preview-melding = melding
+command-templates-preview-melding-description = Kva melding?
+command-templates-preview-melding-0 = Opna
+command-templates-preview-melding-1 = Lotteri opna
+command-templates-preview-melding-2 = Stengjer snart
+command-templates-preview-melding-3 = Stengd
+command-templates-preview-melding-4 = Steikt
+command-templates-preview-melding-5 = Bestilt
+command-templates-preview-tekst = tekst
+command-templates-preview-tekst-description = Ny tekst, t.d. 🧇 {"{"}count{"}"} vaflar til {"{"}mentions{"}"}
+command-templates-set = set
+command-templates-set-description = Gi ei melding eigen ordlyd
+command-templates-set-melding = melding
+command-templates-set-melding-description = Kva melding?
+command-templates-set-melding-0 = Opna
+command-templates-set-melding-1 = Lotteri opna
+command-templates-set-melding-2 = Stengjer snart
+command-templates-set-melding-3 = Stengd
+command-templates-set-melding-4 = Steikt
+command-templates-set-melding-5 = Bestilt
+command-templates-set-tekst = tekst
+command-templates-set-tekst-description = Ny tekst, t.d. 🧇 {"{"}count{"}"} vaflar til {"{"}mentions{"}"}
+command-templates-reset = tilbakestill
+command-templates-reset-description = Gå tilbake til standardteksten for ei melding
+command-templates-reset-melding = melding
+command-templates-reset-melding-description = Kva melding?
+command-templates-reset-melding-0 = Opna
+command-templates-reset-melding-1 = Lotteri opna
+command-templates-reset-melding-2 = Stengjer snart
+command-templates-reset-melding-3 = Stengd
+command-templates-reset-melding-4 = Steikt
+command-templates-reset-melding-5 = Bestilt
+
+command-api = api
+command-api-description = La ting utanfor Discord styre køen
+command-api-list = vis
+command-api-list-description = Sjå kva API-nøklar serveren har
+command-api-create = ny
+command-api-create-description = Lag ein ny API-nøkkel
+command-api-create-navn = namn
+command-api-create-navn-description = Kva skal nøkkelen brukast til? T.d. Nettbrett i standen
+command-api-revoke = slett
+command-api-revoke-description = Slett ein API-nøkkel så han ikkje kan brukast lenger
+command-api-revoke-id = id
+command-api-revoke-id-description = Nummeret til nøkkelen, sjå /api vis
@@ -0,0 +1 @@
+ALTER TABLE orders ADD COLUMN quantity INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS guild_settings (
+    guild_id TEXT PRIMARY KEY,
+    max_waffles_per_order INTEGER NOT NULL DEFAULT 1
+);
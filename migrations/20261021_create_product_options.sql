@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS product_options (
+    guild_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (guild_id, name)
+);
+
+ALTER TABLE orders ADD COLUMN options TEXT[] NOT NULL DEFAULT '{}';
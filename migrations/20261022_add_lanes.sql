@@ -0,0 +1,3 @@
+ALTER TABLE orders ADD COLUMN lane TEXT NOT NULL DEFAULT 'standard';
+
+ALTER TABLE guild_settings ADD COLUMN variants TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1 @@
+ALTER TABLE guild_settings ADD COLUMN serving_mode TEXT NOT NULL DEFAULT 'fifo';
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS lottery_draws (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id TEXT NOT NULL,
+    -- The u64 seed stored with the same bits as a signed integer
+    seed BIGINT NOT NULL,
+    participants TEXT[] NOT NULL,
+    winners TEXT[] NOT NULL,
+    drawn_by TEXT NOT NULL,
+    drawn_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_lottery_draws_guild_id ON lottery_draws (guild_id);
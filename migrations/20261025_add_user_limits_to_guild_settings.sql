@@ -0,0 +1,5 @@
+ALTER TABLE guild_settings ADD COLUMN max_waffles_per_user INTEGER;
+ALTER TABLE guild_settings ADD COLUMN limit_period TEXT NOT NULL DEFAULT 'session';
+ALTER TABLE guild_settings ADD COLUMN rejoin_cooldown_minutes INTEGER;
+
+CREATE INDEX idx_orders_guild_id_discord_user_id ON orders (guild_id, discord_user_id);
@@ -0,0 +1 @@
+ALTER TABLE guild_settings ADD COLUMN overflow_mode TEXT NOT NULL DEFAULT 'refuse';
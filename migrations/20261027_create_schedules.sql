@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS schedules (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    -- ISO weekday, 1 is Monday and 7 is Sunday
+    weekday SMALLINT NOT NULL CHECK (weekday BETWEEN 1 AND 7),
+    opens_at TIME NOT NULL,
+    closes_at TIME NOT NULL CHECK (closes_at > opens_at)
+);
+
+CREATE INDEX idx_schedules_guild_id ON schedules (guild_id);
+
+CREATE TABLE IF NOT EXISTS schedule_exceptions (
+    guild_id TEXT NOT NULL,
+    date DATE NOT NULL,
+    PRIMARY KEY (guild_id, date)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS session_deadlines (
+    guild_id TEXT PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    mode TEXT NOT NULL DEFAULT 'queue',
+    capacity INTEGER,
+    closes_at TIMESTAMPTZ NOT NULL,
+    reminded BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,2 @@
+-- NULL means the queue is never closed for being idle
+ALTER TABLE guild_settings ADD COLUMN idle_close_minutes INTEGER DEFAULT 180;
@@ -0,0 +1,2 @@
+ALTER TABLE guild_settings ADD COLUMN ordering_channel_id TEXT;
+ALTER TABLE guild_settings ADD COLUMN oracle_channel_id TEXT;
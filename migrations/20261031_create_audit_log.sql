@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id TEXT NOT NULL,
+    -- NULL when the bot did it by itself, e.g. from a schedule
+    actor_id TEXT,
+    action TEXT NOT NULL,
+    details TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_log_guild_id_created_at ON audit_log (guild_id, created_at);
+
+ALTER TABLE guild_settings ADD COLUMN audit_channel_id TEXT;
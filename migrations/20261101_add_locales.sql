@@ -0,0 +1,6 @@
+ALTER TABLE guild_settings ADD COLUMN locale TEXT NOT NULL DEFAULT 'nb';
+
+CREATE TABLE IF NOT EXISTS user_locales (
+    user_id TEXT PRIMARY KEY,
+    locale TEXT NOT NULL
+);
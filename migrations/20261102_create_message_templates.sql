@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS message_templates (
+    guild_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    template TEXT NOT NULL,
+    PRIMARY KEY (guild_id, kind)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    -- SHA-256 of the token, the token itself is only shown when it is created
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_api_tokens_guild_id ON api_tokens (guild_id);
@@ -0,0 +1,4 @@
+-- Closing idle queues is opt-in. Guilds that only have the old default get it
+-- turned off.
+ALTER TABLE guild_settings ALTER COLUMN idle_close_minutes SET DEFAULT NULL;
+UPDATE guild_settings SET idle_close_minutes = NULL WHERE idle_close_minutes = 180;
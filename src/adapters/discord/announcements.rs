@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use serenity::all::{ActivityData, Mentionable, MessageBuilder, OnlineStatus, UserId};
+
+use crate::adapters::discord::i18n::t;
+use crate::adapters::discord::templates::Templates;
+use crate::domain::session::{Batch, ClosedSession};
+use crate::domain::{Locale, QueueEntry, QueueRepository, SessionMode, TemplateKind};
+
+/// Message posted when a session is opened
+pub fn opened(
+    templates: &Templates,
+    mode: SessionMode,
+    capacity: Option<u32>,
+    closes_at: Option<DateTime<Utc>>,
+) -> String {
+    let locale = templates.locale;
+    let mut message = match mode {
+        SessionMode::LotterySignup => templates.render(TemplateKind::OpenedLottery, &[], || {
+            t!(locale, "announce-opened-lottery")
+        }),
+        _ => templates.render(TemplateKind::Opened, &[], || t!(locale, "announce-opened")),
+    };
+
+    if let Some(capacity) = capacity {
+        message.push('\n');
+        message.push_str(&t!(locale, "announce-capacity", capacity = capacity));
+    }
+
+    if let Some(closes_at) = closes_at {
+        message.push('\n');
+        message.push_str(&t!(
+            locale,
+            "announce-closes-at",
+            time = closes_at.timestamp().to_string()
+        ));
+    }
+
+    message
+}
+
+/// Message posted a little while before a session closes by itself
+pub fn closing_soon(templates: &Templates, closes_at: DateTime<Utc>) -> String {
+    let timestamp = closes_at.timestamp().to_string();
+    templates.render(
+        TemplateKind::ClosingSoon,
+        &[("time", &format!("<t:{timestamp}:R>"))],
+        || {
+            t!(
+                templates.locale,
+                "announce-closing-soon",
+                time = timestamp.as_str()
+            )
+        },
+    )
+}
+
+/// Message posted when a session is closed, with the stats of the day
+pub fn closed(templates: &Templates, session: &ClosedSession) -> String {
+    let locale = templates.locale;
+    let mut message = templates.render(TemplateKind::Closed, &[], || t!(locale, "announce-closed"));
+
+    let Some(stats) = session
+        .stats
+        .as_ref()
+        .filter(|stats| stats.total_orders > 0)
+    else {
+        return message;
+    };
+
+    message.push_str(&format!(
+        "\n\n{}\n{}\n",
+        t!(locale, "stats-title"),
+        t!(locale, "stats-total", count = stats.total_orders)
+    ));
+
+    if stats.lanes.len() > 1 {
+        for (lane, count) in &stats.lanes {
+            message.push_str(&format!("- {lane}: {count}\n"));
+        }
+    }
+
+    if !stats.top_users.is_empty() {
+        message.push_str(&format!("\n{}\n", t!(locale, "stats-top")));
+        let medals = ["🥇", "🥈", "🥉"];
+        for (i, (user_id, count)) in stats.top_users.iter().enumerate() {
+            if let Ok(id) = user_id.parse::<u64>() {
+                message.push_str(&t!(
+                    locale,
+                    "stats-top-user",
+                    medal = medals[i],
+                    user = UserId::new(id).mention().to_string(),
+                    count = *count
+                ));
+                message.push('\n');
+            }
+        }
+    }
+
+    message
+}
+
+/// Message posted when a batch is baked, pinging who gets it
+pub fn baked(templates: &Templates, batch: &Batch) -> String {
+    let locale = templates.locale;
+    if batch.baked.is_empty() {
+        return t!(locale, "bake-nobody");
+    }
+
+    let mut msg = MessageBuilder::new();
+    for (i, entry) in batch.baked.iter().enumerate() {
+        if i > 0 && i == batch.baked.len() - 1 {
+            msg.push(format!(" {} ", t!(locale, "list-and")));
+        } else if i > 0 {
+            msg.push(", ");
+        }
+        push_recipient(&mut msg, entry);
+    }
+    let mentions = msg.build();
+
+    templates.render(
+        TemplateKind::Baked,
+        &[
+            ("count", &batch.waffles.to_string()),
+            ("mentions", &mentions),
+        ],
+        || {
+            format!(
+                "{} {mentions}",
+                t!(locale, "bake-baked", count = batch.waffles)
+            )
+        },
+    )
+}
+
+/// Mention the user of an entry, or name the guest and their ticket if the
+/// entry is not from Discord
+pub fn push_name(msg: &mut MessageBuilder, entry: &QueueEntry) {
+    match entry.user_id.parse::<u64>().ok().filter(|&id| id != 0) {
+        Some(id) => {
+            msg.mention(&UserId::new(id));
+        }
+        None => {
+            msg.push_bold_safe(&entry.display_name);
+            if let Some(ticket) = entry.ticket {
+                msg.push(format!(" #{ticket}"));
+            }
+        }
+    }
+}
+
+fn push_recipient(msg: &mut MessageBuilder, entry: &QueueEntry) {
+    push_name(msg, entry);
+
+    let mut details = Vec::new();
+    if entry.quantity > 1 {
+        details.push(format!("x{}", entry.quantity));
+    }
+    if !entry.options.is_empty() {
+        details.push(entry.options.join(" + "));
+    }
+    if !details.is_empty() {
+        msg.push(format!(" ({})", details.join(", ")));
+    }
+}
+
+/// Message posted when a session is closed for being idle
+pub fn closed_for_inactivity(
+    templates: &Templates,
+    session: &ClosedSession,
+    minutes: u32,
+) -> String {
+    format!(
+        "{}\n{}",
+        t!(templates.locale, "announce-closed-idle", minutes = minutes),
+        closed(templates, session)
+    )
+}
+
+/// Show how many guilds the bot is making waffles in.
+///
+/// Presence is shared by every guild, so it is derived from all open queues
+/// instead of the guild that just opened or closed.
+pub fn update_presence(ctx: &serenity::all::Context, queue: &dyn QueueRepository) {
+    match queue.sessions().len() {
+        0 => ctx.set_presence(None, OnlineStatus::Offline),
+        1 => ctx.set_presence(
+            Some(ActivityData::playing(t!(
+                Locale::default(),
+                "presence-baking"
+            ))),
+            OnlineStatus::Online,
+        ),
+        open => ctx.set_presence(
+            Some(ActivityData::playing(t!(
+                Locale::default(),
+                "presence-baking-guilds",
+                count = open
+            ))),
+            OnlineStatus::Online,
+        ),
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serenity::all::{ChannelId, CreateEmbed, CreateMessage, Http, Mentionable, UserId};
+use tracing::error;
+
+use crate::adapters::discord::Context;
+use crate::adapters::discord::i18n::{t, tr};
+use crate::domain::session::Batch;
+use crate::domain::{
+    AuditAction, AuditEntry, AuditLogRepository, Locale, SessionMode, SettingsRepository,
+};
+
+fn title(locale: Locale, action: AuditAction) -> String {
+    tr(locale, &format!("audit-title-{}", action.as_str()), None)
+}
+
+/// Mention a list of users, e.g. for the details of an entry.
+/// Guests are not in Discord, so their ids are written as they are.
+pub fn mentions<'a>(user_ids: impl IntoIterator<Item = &'a str>) -> String {
+    user_ids
+        .into_iter()
+        .map(|id| match id.parse::<u64>().ok().filter(|&id| id != 0) {
+            Some(id) => UserId::new(id).mention().to_string(),
+            None => id.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Details of an opened session
+pub fn open_details(
+    locale: Locale,
+    mode: SessionMode,
+    capacity: Option<u32>,
+    closes_at: Option<DateTime<Utc>>,
+) -> String {
+    let mut details = vec![match mode {
+        SessionMode::LotterySignup => t!(locale, "audit-open-lottery"),
+        _ => t!(locale, "audit-open-queue"),
+    }];
+    if let Some(capacity) = capacity {
+        details.push(t!(locale, "audit-capacity", capacity = capacity));
+    }
+    if let Some(closes_at) = closes_at {
+        details.push(t!(
+            locale,
+            "audit-open-closes-at",
+            time = closes_at.timestamp().to_string()
+        ));
+    }
+    details.join(", ")
+}
+
+/// Details of a baked batch
+pub fn bake_details(locale: Locale, batch: &Batch) -> String {
+    if batch.baked.is_empty() {
+        t!(locale, "audit-bake-nobody", lane = batch.lane.as_str())
+    } else {
+        t!(
+            locale,
+            "audit-bake",
+            count = batch.waffles,
+            lane = batch.lane.as_str(),
+            recipients = mentions(batch.baked.iter().map(|entry| entry.user_id.as_str()))
+        )
+    }
+}
+
+/// Store a privileged action in the audit log and post it in the audit
+/// channel of the guild, if it has one.
+///
+/// Failing to log is only reported, so it never stops the action itself.
+pub async fn record(
+    http: &Http,
+    audit: &dyn AuditLogRepository,
+    settings: &dyn SettingsRepository,
+    entry: AuditEntry,
+) {
+    if let Err(e) = audit.record(&entry).await {
+        error!(guild_id = %entry.guild_id, error = ?e, "Failed to record audit log entry");
+    }
+
+    let (channel_id, locale) = match settings.get(&entry.guild_id).await {
+        Ok(settings) => (settings.audit_channel_id, settings.locale),
+        Err(e) => {
+            error!(guild_id = %entry.guild_id, error = ?e, "Failed to fetch guild settings");
+            return;
+        }
+    };
+    let Some(channel_id) = channel_id.and_then(|id| id.parse::<u64>().ok()) else {
+        return;
+    };
+
+    let actor = match entry
+        .actor_id
+        .as_deref()
+        .and_then(|id| id.parse::<u64>().ok())
+    {
+        Some(id) => UserId::new(id).mention().to_string(),
+        None => t!(locale, "audit-automatic"),
+    };
+    let embed = CreateEmbed::new()
+        .title(title(locale, entry.action))
+        .description(&entry.details)
+        .field(t!(locale, "audit-by"), actor, true)
+        .timestamp(entry.created_at);
+
+    if let Err(e) = ChannelId::new(channel_id)
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await
+    {
+        error!(channel_id, error = ?e, "Failed to post audit log entry");
+    }
+}
+
+/// Log an action done by the user running the command
+pub async fn record_command(ctx: Context<'_>, action: AuditAction, details: impl Into<String>) {
+    let Some(guild_id) = ctx.guild_id() else {
+        return;
+    };
+
+    let entry = AuditEntry {
+        guild_id: guild_id.to_string(),
+        actor_id: Some(ctx.author().id.to_string()),
+        action,
+        details: details.into(),
+        created_at: Utc::now(),
+    };
+
+    record(
+        ctx.http(),
+        ctx.data().audit.as_ref(),
+        ctx.data().settings.as_ref(),
+        entry,
+    )
+    .await;
+}
@@ -0,0 +1,146 @@
+use tracing::error;
+
+use crate::adapters::discord::i18n::{guild_locale, locale, t};
+use crate::adapters::discord::{Context, Error, audit, check_is_oracle};
+use crate::domain::AuditAction;
+use crate::domain::api_token::{MAX_LABEL_LENGTH, generate_token, hash_token};
+
+/// La ting utenfor Discord styre køen
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "api",
+    subcommands("list", "create", "revoke"),
+    subcommand_required,
+    check = "check_is_oracle"
+)]
+pub async fn api(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Se hvilke API-nøkler serveren har
+#[tracing::instrument(name = "api_list", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "vis")]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let tokens = ctx.data().api_tokens.list(&guild_id).await?;
+    if tokens.is_empty() {
+        ctx.say(t!(locale, "api-empty")).await?;
+        return Ok(());
+    }
+
+    let mut message = t!(locale, "api-title");
+    for token in tokens {
+        message.push('\n');
+        message.push_str(&t!(
+            locale,
+            "api-entry",
+            id = token.id,
+            label = token.label,
+            time = token.created_at.timestamp().to_string()
+        ));
+    }
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Lag en ny API-nøkkel
+// Only a slash command, since a prefix command cannot answer ephemerally and
+// would post the token for the whole channel to see
+#[tracing::instrument(name = "api_create", skip(ctx))]
+#[poise::command(slash_command, rename = "ny")]
+pub async fn create(
+    ctx: Context<'_>,
+    #[description = "Hva skal nøkkelen brukes til? F.eks. Nettbrett i standen"]
+    #[rename = "navn"]
+    label: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_LENGTH {
+        ctx.say(t!(locale, "api-invalid-label", max = MAX_LABEL_LENGTH))
+            .await?;
+        return Ok(());
+    }
+
+    let token = generate_token();
+    let id = match ctx
+        .data()
+        .api_tokens
+        .create(&guild_id, label, &hash_token(&token))
+        .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to create API token");
+            ctx.say(t!(locale, "api-save-failed")).await?;
+            return Ok(());
+        }
+    };
+
+    // The token is only ever shown here, so only the oracle gets to see it
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "{}\n`{token}`",
+                t!(locale, "api-created", id = id, label = label)
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    audit::record_command(
+        ctx,
+        AuditAction::ApiToken,
+        t!(
+            guild_locale(ctx).await,
+            "audit-api-token-created",
+            id = id,
+            label = label
+        ),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Slett en API-nøkkel så den ikke kan brukes lenger
+#[tracing::instrument(name = "api_revoke", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "slett")]
+pub async fn revoke(
+    ctx: Context<'_>,
+    #[description = "Nummeret til nøkkelen, se /api vis"] id: i64,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    match ctx.data().api_tokens.revoke(&guild_id, id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            ctx.say(t!(locale, "api-not-found", id = id)).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to revoke API token");
+            ctx.say(t!(locale, "api-save-failed")).await?;
+            return Ok(());
+        }
+    }
+
+    ctx.say(t!(locale, "api-revoked", id = id)).await?;
+
+    audit::record_command(
+        ctx,
+        AuditAction::ApiToken,
+        t!(guild_locale(ctx).await, "audit-api-token-revoked", id = id),
+    )
+    .await;
+
+    Ok(())
+}
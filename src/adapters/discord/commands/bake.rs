@@ -1,7 +1,11 @@
-use serenity::all::{MessageBuilder, UserId};
-use tracing::error;
-
-use crate::adapters::discord::{Context, Error, check_is_oracle};
+use crate::adapters::discord::i18n::{locale, t};
+use crate::adapters::discord::templates::guild_templates;
+use crate::adapters::discord::{
+    Context, Error, announcements, audit, autocomplete_variant, check_is_oracle,
+    check_oracle_channel, say_in_ordering_channel,
+};
+use crate::domain::AuditAction;
+use crate::domain::session::{BakeError, bake as bake_batch};
 
 /// Stek vaffel
 #[tracing::instrument(name = "bake", skip(ctx))]
@@ -9,58 +13,58 @@ use crate::adapters::discord::{Context, Error, check_is_oracle};
     prefix_command,
     slash_command,
     rename = "stekt",
-    check = "check_is_oracle"
+    check = "check_is_oracle",
+    check = "check_oracle_channel"
 )]
 pub async fn bake(
     ctx: Context<'_>,
     #[description = "Hvor mange vafler?"] amount: usize,
+    #[description = "Hvilken variant? Standard hvis tom"]
+    #[autocomplete = "autocomplete_variant"]
+    variant: Option<String>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
 
-    if !ctx.data().queue.is_open(&guild_id) {
-        ctx.say("🔒️ Bestilling er stengt").await?;
-        return Ok(());
-    }
-
-    let baked = ctx.data().queue.pop_n(&guild_id, amount).await;
-
-    let message = if baked.is_empty() {
-        "😟 Ingen å steke vafler til.".to_string()
-    } else {
-        let mut msg = MessageBuilder::new();
-        msg.push("🧇 Stekte ").push(baked.len().to_string());
-
-        if baked.len() == 1 {
-            msg.push(" en vaffel til: ");
-            let user_id = UserId::new(baked[0].user_id.parse::<u64>().unwrap());
-            msg.mention(&user_id);
-        } else {
-            msg.push(" vafler til: ");
-
-            for (i, entry) in baked.iter().enumerate() {
-                let user_id = UserId::new(entry.user_id.parse::<u64>().unwrap());
-
-                if i == baked.len() - 1 {
-                    msg.push(" og ").mention(&user_id);
-                } else {
-                    msg.mention(&user_id).push(", ");
-                }
-            }
+    let settings = ctx.data().settings.get(&guild_id).await?;
+    let batch = match bake_batch(
+        ctx.data().queue.as_ref(),
+        ctx.data().orders.as_ref(),
+        &settings,
+        &guild_id,
+        variant.as_deref(),
+        amount,
+    )
+    .await
+    {
+        Ok(batch) => batch,
+        Err(BakeError::Closed) => {
+            ctx.say(t!(locale, "session-closed")).await?;
+            return Ok(());
+        }
+        Err(BakeError::NotDrawn) => {
+            ctx.say(t!(locale, "bake-not-drawn")).await?;
+            return Ok(());
+        }
+        Err(BakeError::UnknownVariant(variant)) => {
+            ctx.say(t!(locale, "unknown-variant", variant = variant))
+                .await?;
+            return Ok(());
         }
-
-        msg.build()
     };
 
-    let user_ids: Vec<&str> = baked.iter().map(|e| e.user_id.as_str()).collect();
-    if let Err(e) = ctx.data().orders.record_orders(&user_ids, &guild_id).await {
-        error!(
-            guild_id = %guild_id,
-            error = ?e,
-            "Failed to record orders"
-        );
-    }
+    ctx.data().events.publish(&guild_id, batch.event());
+
+    // Everyone sees this, so it follows the guild and not the oracle
+    let templates = guild_templates(ctx).await;
+    say_in_ordering_channel(ctx, announcements::baked(&templates, &batch)).await?;
 
-    ctx.say(message).await?;
+    audit::record_command(
+        ctx,
+        AuditAction::Bake,
+        audit::bake_details(templates.locale, &batch),
+    )
+    .await;
 
     Ok(())
 }
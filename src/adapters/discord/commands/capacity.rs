@@ -0,0 +1,86 @@
+use serenity::all::MessageBuilder;
+
+use crate::adapters::discord::i18n::{guild_locale, locale, t};
+use crate::adapters::discord::{
+    Context, Error, announcements, audit, check_is_oracle, check_oracle_channel,
+};
+use crate::domain::capacity::capacity_status;
+use crate::domain::{AuditAction, QueueEvent};
+
+/// Endre hvor mange vafler det er røre til
+#[tracing::instrument(name = "capacity", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "kapasitet",
+    check = "check_is_oracle",
+    check = "check_oracle_channel"
+)]
+pub async fn capacity(
+    ctx: Context<'_>,
+    #[description = "Hvor mange vafler er det røre til totalt? Tom for ingen grense"]
+    #[rename = "antall"]
+    amount: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+
+    if !ctx.data().queue.is_open(&guild_id) {
+        ctx.say(t!(locale(ctx).await, "session-closed")).await?;
+        return Ok(());
+    }
+
+    ctx.data().queue.set_capacity(&guild_id, amount);
+
+    let locale = guild_locale(ctx).await;
+    let status = capacity_status(ctx.data().queue.as_ref(), &guild_id).await;
+    let mut msg = MessageBuilder::new();
+    match status {
+        Some(status) => msg.push(t!(
+            locale,
+            "capacity-set",
+            capacity = status.capacity,
+            remaining = status.remaining()
+        )),
+        None => msg.push(t!(locale, "capacity-unlimited")),
+    };
+
+    let room = status.map(|status| status.remaining()).unwrap_or(u32::MAX);
+    let promoted = ctx.data().queue.promote_waitlist(&guild_id, room).await;
+    for waiting in &promoted {
+        let Some(index) = ctx
+            .data()
+            .queue
+            .index_of(&guild_id, &waiting.lane, &waiting.entry.user_id)
+            .await
+        else {
+            continue;
+        };
+        ctx.data().events.publish(
+            &guild_id,
+            QueueEvent::Joined {
+                lane: waiting.lane.clone(),
+                entry: waiting.entry.clone(),
+                position: index + 1,
+            },
+        );
+    }
+    if !promoted.is_empty() {
+        msg.push(format!("\n{} ", t!(locale, "capacity-promoted")));
+        for (i, waiting) in promoted.iter().enumerate() {
+            if i > 0 {
+                msg.push(", ");
+            }
+            announcements::push_name(&mut msg, &waiting.entry);
+        }
+    }
+
+    ctx.say(msg.build()).await?;
+
+    let details = match amount {
+        Some(amount) => t!(locale, "audit-capacity", capacity = amount),
+        None => t!(locale, "audit-capacity-unlimited"),
+    };
+    audit::record_command(ctx, AuditAction::Capacity, details).await;
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use serenity::all::{Mentionable, OnlineStatus, UserId};
-use tracing::error;
-
-use crate::adapters::discord::{Context, Error, check_is_oracle};
+use crate::adapters::discord::i18n::{locale, t};
+use crate::adapters::discord::templates::guild_templates;
+use crate::adapters::discord::{
+    Context, Error, announcements, audit, check_is_oracle, check_oracle_channel,
+    say_in_ordering_channel,
+};
+use crate::domain::session::close_session;
+use crate::domain::{AuditAction, QueueEvent};
 
 /// Steng for bestilling av vafler
 #[tracing::instrument(name = "close", skip(ctx))]
@@ -9,57 +13,41 @@ use crate::adapters::discord::{Context, Error, check_is_oracle};
     prefix_command,
     slash_command,
     rename = "stopp",
-    check = "check_is_oracle"
+    check = "check_is_oracle",
+    check = "check_oracle_channel"
 )]
 pub async fn close(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap().to_string();
 
-    if !ctx.data().queue.is_open(&guild_id) {
-        ctx.say("🔒️ Bestilling er allerede stengt").await?;
+    let Some(session) = close_session(
+        ctx.data().queue.as_ref(),
+        ctx.data().orders.as_ref(),
+        ctx.data().deadlines.as_ref(),
+        &guild_id,
+        &ctx.data().timezone,
+    )
+    .await
+    else {
+        ctx.say(t!(locale(ctx).await, "close-already-closed"))
+            .await?;
         return Ok(());
-    }
-
-    ctx.data().queue.close(&guild_id).await;
-
-    let mut message = "🔒️ Bestilling er nå stengt".to_string();
+    };
 
-    match ctx.data().orders.daily_stats(&guild_id).await {
-        Ok(stats) if stats.total_orders > 0 => {
-            let vafler = if stats.total_orders == 1 {
-                "vaffel"
-            } else {
-                "vafler"
-            };
-            message.push_str(&format!(
-                "\n\n📊 **Dagens statistikk**\nTotalt stekt: {} {}\n",
-                stats.total_orders, vafler
-            ));
+    ctx.data().events.publish(&guild_id, QueueEvent::Closed);
 
-            if !stats.top_users.is_empty() {
-                message.push_str("\n🏆 **Topp bestillere:**\n");
-                let medals = ["🥇", "🥈", "🥉"];
-                for (i, (user_id, count)) in stats.top_users.iter().enumerate() {
-                    if let Ok(id) = user_id.parse::<u64>() {
-                        let mention = UserId::new(id).mention();
-                        let vafler = if *count == 1 { "vaffel" } else { "vafler" };
-                        message.push_str(&format!(
-                            "{} {} - {} {}\n",
-                            medals[i], mention, count, vafler
-                        ));
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            error!(guild_id = %guild_id, error = ?e, "Failed to fetch daily stats");
-        }
-        _ => {}
-    }
+    let templates = guild_templates(ctx).await;
+    let locale = templates.locale;
+    say_in_ordering_channel(ctx, announcements::closed(&templates, &session)).await?;
 
-    ctx.say(message).await?;
+    let baked = session.stats.as_ref().map_or(0, |stats| stats.total_orders);
+    audit::record_command(
+        ctx,
+        AuditAction::Close,
+        t!(locale, "audit-close", count = baked),
+    )
+    .await;
 
-    ctx.serenity_context()
-        .set_presence(None, OnlineStatus::Offline);
+    announcements::update_presence(ctx.serenity_context(), ctx.data().queue.as_ref());
 
     Ok(())
 }
@@ -0,0 +1,169 @@
+use serenity::all::MessageBuilder;
+use tracing::error;
+
+use crate::adapters::discord::i18n::{guild_locale, locale, t};
+use crate::adapters::discord::{
+    Context, Error, announcements, audit, check_is_oracle, check_oracle_channel,
+    say_in_ordering_channel,
+};
+use crate::domain::{
+    AuditAction, LaneQueue, LotteryDraw, QueueEntry, QueueEvent, SessionMode, lottery::draw_winners,
+};
+
+/// Trekk vinnere i vaffellotteriet
+#[tracing::instrument(name = "draw", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "trekk",
+    check = "check_is_oracle",
+    check = "check_oracle_channel"
+)]
+pub async fn draw(
+    ctx: Context<'_>,
+    #[description = "Hvor mange vinnere?"]
+    #[rename = "antall"]
+    amount: usize,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    match ctx.data().queue.mode(&guild_id) {
+        None => {
+            ctx.say(t!(locale, "session-closed")).await?;
+            return Ok(());
+        }
+        Some(SessionMode::Queue) => {
+            ctx.say(t!(locale, "draw-not-lottery")).await?;
+            return Ok(());
+        }
+        Some(SessionMode::LotteryDrawn) => {
+            ctx.say(t!(locale, "lottery-already-drawn")).await?;
+            return Ok(());
+        }
+        Some(SessionMode::LotterySignup) => {}
+    }
+
+    // Close signup before reading the entries so nobody joins mid draw
+    ctx.data()
+        .queue
+        .set_mode(&guild_id, SessionMode::LotteryDrawn);
+
+    // A user only gets one ticket, even if they are in several lanes
+    let mut participants: Vec<(String, QueueEntry)> = Vec::new();
+    for lane in ctx.data().queue.lanes(&guild_id).await {
+        for entry in ctx.data().queue.list(&guild_id, &lane).await {
+            if !participants
+                .iter()
+                .any(|(_, participant)| participant.user_id == entry.user_id)
+            {
+                participants.push((lane.clone(), entry));
+            }
+        }
+    }
+
+    let seed: u64 = rand::random();
+    let winners = draw_winners(participants.len(), amount, seed);
+
+    // Rebuild every lane with only the winners, in the order they were drawn
+    let lanes: Vec<String> = ctx.data().queue.lanes(&guild_id).await;
+    let mut drawn = Vec::new();
+    for lane in &lanes {
+        let entries: Vec<QueueEntry> = winners
+            .iter()
+            .map(|&i| &participants[i])
+            .filter(|(winner_lane, _)| winner_lane == lane)
+            .map(|(_, entry)| entry.clone())
+            .collect();
+        ctx.data()
+            .queue
+            .replace(&guild_id, lane, entries.clone())
+            .await;
+        drawn.push(LaneQueue {
+            lane: lane.clone(),
+            entries,
+        });
+    }
+    ctx.data()
+        .events
+        .publish(&guild_id, QueueEvent::Drawn { lanes: drawn });
+
+    let record = LotteryDraw {
+        guild_id: guild_id.clone(),
+        seed,
+        participants: participants
+            .iter()
+            .map(|(_, entry)| entry.user_id.clone())
+            .collect(),
+        winners: winners
+            .iter()
+            .map(|&i| participants[i].1.user_id.clone())
+            .collect(),
+        drawn_by: ctx.author().id.to_string(),
+    };
+    let draw_id = match ctx.data().lottery.record_draw(&record).await {
+        Ok(id) => Some(id),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to record lottery draw");
+            None
+        }
+    };
+
+    let guild_locale = guild_locale(ctx).await;
+    let mut msg = MessageBuilder::new();
+    if winners.is_empty() {
+        msg.push(t!(guild_locale, "draw-nobody"));
+    } else {
+        msg.push(t!(
+            guild_locale,
+            "draw-winners",
+            winners = winners.len(),
+            participants = participants.len()
+        ))
+        .push(" ");
+        for (i, &winner) in winners.iter().enumerate() {
+            if i > 0 && i == winners.len() - 1 {
+                msg.push(format!(" {} ", t!(guild_locale, "list-and")));
+            } else if i > 0 {
+                msg.push(", ");
+            }
+            announcements::push_name(&mut msg, &participants[winner].1);
+        }
+    }
+
+    match draw_id {
+        Some(id) => msg.push("\n\n").push(t!(
+            guild_locale,
+            "draw-seed-id",
+            id = id,
+            seed = seed.to_string()
+        )),
+        None => msg
+            .push("\n\n")
+            .push(t!(guild_locale, "draw-seed", seed = seed.to_string())),
+    };
+
+    say_in_ordering_channel(ctx, msg.build()).await?;
+
+    let mentions = audit::mentions(winners.iter().map(|&i| participants[i].1.user_id.as_str()));
+    let details = match draw_id {
+        Some(id) => t!(
+            guild_locale,
+            "audit-draw-id",
+            count = winners.len(),
+            participants = participants.len(),
+            winners = mentions.as_str(),
+            id = id
+        ),
+        None => t!(
+            guild_locale,
+            "audit-draw",
+            count = winners.len(),
+            participants = participants.len(),
+            winners = mentions.as_str()
+        ),
+    };
+    audit::record_command(ctx, AuditAction::Draw, details).await;
+
+    Ok(())
+}
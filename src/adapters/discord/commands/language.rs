@@ -0,0 +1,59 @@
+use tracing::error;
+
+use crate::adapters::discord::i18n::{guild_locale, t};
+use crate::adapters::discord::{Context, Error};
+use crate::domain::Locale;
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum LanguageChoice {
+    #[name = "Bokmål"]
+    Nb,
+    #[name = "Nynorsk"]
+    Nn,
+    #[name = "English"]
+    En,
+}
+
+impl From<LanguageChoice> for Locale {
+    fn from(choice: LanguageChoice) -> Self {
+        match choice {
+            LanguageChoice::Nb => Locale::Nb,
+            LanguageChoice::Nn => Locale::Nn,
+            LanguageChoice::En => Locale::En,
+        }
+    }
+}
+
+/// Velg hvilket språk vaffelbot svarer deg på
+#[tracing::instrument(name = "language", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "språk")]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "Språket du vil ha svar på. Tom for å bruke serverens språk"]
+    #[rename = "språk"]
+    choice: Option<LanguageChoice>,
+) -> Result<(), Error> {
+    let user_id = ctx.author().id.to_string();
+    let locale = choice.map(Locale::from);
+
+    if let Err(e) = ctx.data().user_locales.set(&user_id, locale).await {
+        error!(user_id, error = ?e, "Failed to save user locale");
+        ctx.say(t!(guild_locale(ctx).await, "language-save-failed"))
+            .await?;
+        return Ok(());
+    }
+
+    let message = match locale {
+        Some(locale) => t!(locale, "language-set"),
+        None => t!(guild_locale(ctx).await, "language-follows-guild"),
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(message)
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
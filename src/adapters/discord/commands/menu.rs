@@ -0,0 +1,124 @@
+use tracing::error;
+
+use crate::adapters::discord::i18n::{locale, t};
+use crate::adapters::discord::{Context, Error, check_is_oracle};
+
+/// Se og endre hva man kan ha på vaffelen
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "meny",
+    subcommands("show", "add", "remove"),
+    subcommand_required
+)]
+pub async fn menu(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Se hva man kan ha på vaffelen
+#[tracing::instrument(name = "menu_show", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "vis")]
+pub async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let options = ctx.data().products.list(&guild_id).await?;
+    let message = if options.is_empty() {
+        t!(locale, "menu-empty")
+    } else {
+        let mut message = format!("{}\n", t!(locale, "menu-title"));
+        for option in &options {
+            message.push_str(&format!("- {option}\n"));
+        }
+        message
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Legg til noe man kan ha på vaffelen
+#[tracing::instrument(name = "menu_add", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "legg-til",
+    check = "check_is_oracle"
+)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Navn på tilbehøret"]
+    #[rename = "navn"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let name = name.trim();
+    let locale = locale(ctx).await;
+
+    if name.is_empty() || name.contains(',') {
+        ctx.say(t!(locale, "menu-invalid-name")).await?;
+        return Ok(());
+    }
+
+    let message = match ctx.data().products.add(&guild_id, name).await {
+        Ok(true) => t!(locale, "menu-added", name = name),
+        Ok(false) => t!(locale, "menu-already-added", name = name),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to add product option");
+            t!(locale, "menu-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Fjern noe fra menyen
+#[tracing::instrument(name = "menu_remove", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "fjern",
+    check = "check_is_oracle"
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Navn på tilbehøret"]
+    #[rename = "navn"]
+    #[autocomplete = "autocomplete_option"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let message = match ctx.data().products.remove(&guild_id, &name).await {
+        Ok(true) => t!(locale, "menu-removed", name = name.as_str()),
+        Ok(false) => t!(locale, "menu-not-added", name = name.as_str()),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to remove product option");
+            t!(locale, "menu-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+async fn autocomplete_option(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return vec![];
+    };
+
+    let partial = partial.to_lowercase();
+    ctx.data()
+        .products
+        .list(&guild_id.to_string())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|option| option.to_lowercase().starts_with(&partial))
+        .collect()
+}
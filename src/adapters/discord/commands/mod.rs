@@ -1,6 +1,14 @@
+pub mod api;
 pub mod bake;
+pub mod capacity;
 pub mod close;
+pub mod draw;
+pub mod language;
+pub mod menu;
 pub mod open;
 pub mod ping;
+pub mod plan;
 pub mod queue_size;
+pub mod settings;
+pub mod templates;
 pub mod waffle;
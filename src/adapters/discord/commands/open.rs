@@ -1,6 +1,23 @@
-use serenity::all::{ActivityData, OnlineStatus};
+use chrono::{NaiveTime, Utc};
+use tracing::error;
 
-use crate::adapters::discord::{Context, Error, check_is_oracle};
+use crate::adapters::discord::i18n::{locale, t};
+use crate::adapters::discord::templates::guild_templates;
+use crate::adapters::discord::{
+    Context, Error, announcements, audit, check_is_oracle, check_oracle_channel, ordering_channel,
+    say_in_ordering_channel,
+};
+use crate::domain::deadline::{parse_duration, today_at};
+use crate::domain::session::open_session;
+use crate::domain::{AuditAction, QueueEvent, SessionDeadline, SessionMode};
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum ModeChoice {
+    #[name = "Kø"]
+    Queue,
+    #[name = "Lotteri"]
+    Lottery,
+}
 
 /// Åpne for bestilling av vafler
 #[tracing::instrument(name = "open", skip(ctx))]
@@ -8,23 +25,122 @@ use crate::adapters::discord::{Context, Error, check_is_oracle};
     prefix_command,
     slash_command,
     rename = "start",
-    check = "check_is_oracle"
+    check = "check_is_oracle",
+    check = "check_oracle_channel"
 )]
-pub async fn open(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn open(
+    ctx: Context<'_>,
+    #[description = "Kø eller lotteri? Standard er kø"]
+    #[rename = "modus"]
+    mode: Option<ModeChoice>,
+    #[description = "Hvor mange vafler er det røre til? Tom for ingen grense"]
+    #[rename = "kapasitet"]
+    #[min = 1]
+    capacity: Option<u32>,
+    #[description = "Hvor lenge bestilling er åpen, f.eks. 90m eller 1t30m"]
+    #[rename = "varighet"]
+    duration: Option<String>,
+    #[description = "Når bestilling stenges, f.eks. 14:00"]
+    #[rename = "til"]
+    until: Option<String>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+    let now = Utc::now();
+
+    let closes_at = match (duration, until) {
+        (None, None) => None,
+        (Some(_), Some(_)) => {
+            ctx.say(t!(locale, "open-duration-and-until")).await?;
+            return Ok(());
+        }
+        (Some(duration), None) => {
+            match parse_duration(&duration).and_then(|duration| now.checked_add_signed(duration)) {
+                Some(closes_at) => Some(closes_at),
+                None => {
+                    ctx.say(t!(locale, "open-invalid-duration")).await?;
+                    return Ok(());
+                }
+            }
+        }
+        (None, Some(until)) => {
+            let Ok(time) = NaiveTime::parse_from_str(until.trim(), "%H:%M") else {
+                ctx.say(t!(locale, "invalid-time")).await?;
+                return Ok(());
+            };
+            match today_at(time, now, &ctx.data().timezone) {
+                Some(closes_at) => Some(closes_at),
+                None => {
+                    ctx.say(t!(locale, "open-time-passed", time = until.trim()))
+                        .await?;
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    let mode = match mode.unwrap_or(ModeChoice::Queue) {
+        ModeChoice::Queue => SessionMode::Queue,
+        ModeChoice::Lottery => SessionMode::LotterySignup,
+    };
+
+    // Reminders and automatic closing are announced where people order
+    let channel_id = ordering_channel(ctx)
+        .await
+        .unwrap_or(ctx.channel_id())
+        .to_string();
 
-    if ctx.data().queue.is_open(&guild_id) {
-        ctx.say("🔓️ Bestilling er allerede åpnet").await?;
+    if !open_session(
+        ctx.data().queue.as_ref(),
+        &guild_id,
+        &channel_id,
+        mode,
+        capacity,
+    ) {
+        ctx.say(t!(locale, "open-already-open")).await?;
         return Ok(());
     }
 
-    ctx.data().queue.open(&guild_id);
-    ctx.say("🔓️ Bestilling er nå åpnet").await?;
+    if let Some(closes_at) = closes_at {
+        let deadline = SessionDeadline {
+            guild_id: guild_id.clone(),
+            channel_id,
+            mode,
+            capacity,
+            closes_at,
+            reminded: false,
+        };
+        if let Err(e) = ctx.data().deadlines.set(&deadline).await {
+            error!(guild_id = %guild_id, error = ?e, "Failed to save session deadline");
+            ctx.say(t!(locale, "open-deadline-save-failed")).await?;
+        }
+    }
 
-    ctx.serenity_context().set_presence(
-        Some(ActivityData::playing("🧇 Lager vafler")),
-        OnlineStatus::Online,
+    ctx.data().events.publish(
+        &guild_id,
+        QueueEvent::Opened {
+            mode,
+            capacity,
+            closes_at,
+        },
     );
 
+    let templates = guild_templates(ctx).await;
+    let locale = templates.locale;
+    say_in_ordering_channel(
+        ctx,
+        announcements::opened(&templates, mode, capacity, closes_at),
+    )
+    .await?;
+
+    audit::record_command(
+        ctx,
+        AuditAction::Open,
+        audit::open_details(locale, mode, capacity, closes_at),
+    )
+    .await;
+
+    announcements::update_presence(ctx.serenity_context(), ctx.data().queue.as_ref());
+
     Ok(())
 }
@@ -1,9 +1,10 @@
+use crate::adapters::discord::i18n::{locale, t};
 use crate::adapters::discord::{Context, Error};
 
 /// Ping vaffelbot
 #[tracing::instrument(name = "ping", skip(ctx))]
 #[poise::command(prefix_command, slash_command, rename = "ping")]
 pub async fn ping(ctx: Context<'_>) -> Result<(), Error> {
-    ctx.say("🏓 Pong!").await?;
+    ctx.say(t!(locale(ctx).await, "ping-pong")).await?;
     Ok(())
 }
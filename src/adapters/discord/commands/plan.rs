@@ -0,0 +1,275 @@
+use chrono::{Datelike, NaiveDate, NaiveTime, Utc, Weekday};
+use serenity::all::{ChannelId, Mentionable};
+use tracing::error;
+
+use crate::adapters::discord::i18n::{locale, t, tr};
+use crate::adapters::discord::{Context, Error, check_is_oracle};
+use crate::domain::Locale;
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum WeekdayChoice {
+    #[name = "Mandag"]
+    Monday,
+    #[name = "Tirsdag"]
+    Tuesday,
+    #[name = "Onsdag"]
+    Wednesday,
+    #[name = "Torsdag"]
+    Thursday,
+    #[name = "Fredag"]
+    Friday,
+    #[name = "Lørdag"]
+    Saturday,
+    #[name = "Søndag"]
+    Sunday,
+}
+
+impl From<WeekdayChoice> for Weekday {
+    fn from(choice: WeekdayChoice) -> Self {
+        match choice {
+            WeekdayChoice::Monday => Weekday::Mon,
+            WeekdayChoice::Tuesday => Weekday::Tue,
+            WeekdayChoice::Wednesday => Weekday::Wed,
+            WeekdayChoice::Thursday => Weekday::Thu,
+            WeekdayChoice::Friday => Weekday::Fri,
+            WeekdayChoice::Saturday => Weekday::Sat,
+            WeekdayChoice::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+fn weekday_name(locale: Locale, weekday: Weekday) -> String {
+    let id = match weekday {
+        Weekday::Mon => "weekday-mon",
+        Weekday::Tue => "weekday-tue",
+        Weekday::Wed => "weekday-wed",
+        Weekday::Thu => "weekday-thu",
+        Weekday::Fri => "weekday-fri",
+        Weekday::Sat => "weekday-sat",
+        Weekday::Sun => "weekday-sun",
+    };
+    tr(locale, id, None)
+}
+
+fn parse_time(input: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(input.trim(), "%H:%M").ok()
+}
+
+fn parse_date(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").ok()
+}
+
+/// Åpne og stenge bestilling automatisk hver uke
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "plan",
+    subcommands("show", "add", "remove", "add_exception", "remove_exception"),
+    subcommand_required,
+    check = "check_is_oracle"
+)]
+pub async fn plan(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Se når bestilling åpnes og stenges automatisk
+#[tracing::instrument(name = "plan_show", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "vis")]
+pub async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+    let today = Utc::now().with_timezone(&ctx.data().timezone).date_naive();
+
+    let schedules = ctx.data().schedules.list(&guild_id).await?;
+    if schedules.is_empty() {
+        ctx.say(t!(locale, "plan-empty")).await?;
+        return Ok(());
+    }
+
+    let mut message = format!("{}\n", t!(locale, "plan-title"));
+    for schedule in &schedules {
+        let channel = schedule
+            .channel_id
+            .parse::<u64>()
+            .map(|id| ChannelId::new(id).mention().to_string())
+            .unwrap_or_default();
+        message.push_str(&t!(
+            locale,
+            "plan-entry",
+            id = schedule.id,
+            weekday = weekday_name(locale, schedule.weekday),
+            opens = schedule.opens_at.format("%H:%M").to_string(),
+            closes = schedule.closes_at.format("%H:%M").to_string(),
+            channel = channel
+        ));
+        message.push('\n');
+    }
+
+    let exceptions = ctx.data().schedules.exceptions(&guild_id, today).await?;
+    if !exceptions.is_empty() {
+        message.push_str(&format!("\n{}\n", t!(locale, "plan-exceptions")));
+        for date in &exceptions {
+            message.push_str(&format!(
+                "- {} {}\n",
+                weekday_name(locale, date.weekday()),
+                date.format("%Y-%m-%d")
+            ));
+        }
+    }
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Åpne og stenge bestilling automatisk på en ukedag
+#[tracing::instrument(name = "plan_add", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "legg-til")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Hvilken dag?"]
+    #[rename = "dag"]
+    weekday: WeekdayChoice,
+    #[description = "Når bestilling åpnes, f.eks. 12:00"]
+    #[rename = "fra"]
+    opens_at: String,
+    #[description = "Når bestilling stenges, f.eks. 14:00"]
+    #[rename = "til"]
+    closes_at: String,
+    #[description = "Hvor beskjedene skal sendes. Standard er denne kanalen"]
+    #[rename = "kanal"]
+    channel: Option<ChannelId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let (Some(opens_at), Some(closes_at)) = (parse_time(&opens_at), parse_time(&closes_at)) else {
+        ctx.say(t!(locale, "invalid-time")).await?;
+        return Ok(());
+    };
+
+    if closes_at <= opens_at {
+        ctx.say(t!(locale, "plan-closes-before-opens")).await?;
+        return Ok(());
+    }
+
+    let weekday = Weekday::from(weekday);
+    let channel = channel.unwrap_or(ctx.channel_id());
+
+    let message = match ctx
+        .data()
+        .schedules
+        .add(
+            &guild_id,
+            &channel.to_string(),
+            weekday,
+            opens_at,
+            closes_at,
+        )
+        .await
+    {
+        Ok(id) => t!(
+            locale,
+            "plan-added",
+            id = id,
+            weekday = weekday_name(locale, weekday),
+            opens = opens_at.format("%H:%M").to_string(),
+            closes = closes_at.format("%H:%M").to_string(),
+            channel = channel.mention().to_string()
+        ),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to add schedule");
+            t!(locale, "plan-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Fjern en dag fra planen
+#[tracing::instrument(name = "plan_remove", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "fjern")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Nummeret fra `/plan vis`"] id: i64,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let message = match ctx.data().schedules.remove(&guild_id, id).await {
+        Ok(true) => t!(locale, "plan-removed", id = id),
+        Ok(false) => t!(locale, "plan-not-found", id = id),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to remove schedule");
+            t!(locale, "plan-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Ikke åpne bestilling automatisk på en dato, f.eks. i ferien
+#[tracing::instrument(name = "plan_add_exception", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "unntak")]
+pub async fn add_exception(
+    ctx: Context<'_>,
+    #[description = "Datoen, f.eks. 2026-12-24"]
+    #[rename = "dato"]
+    date: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let Some(date) = parse_date(&date) else {
+        ctx.say(t!(locale, "invalid-date")).await?;
+        return Ok(());
+    };
+
+    let message = match ctx.data().schedules.add_exception(&guild_id, date).await {
+        Ok(true) => t!(locale, "plan-exception-added", date = date.to_string()),
+        Ok(false) => t!(locale, "plan-exception-exists", date = date.to_string()),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to add schedule exception");
+            t!(locale, "plan-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Følg planen som vanlig på en dato igjen
+#[tracing::instrument(name = "plan_remove_exception", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "fjern-unntak")]
+pub async fn remove_exception(
+    ctx: Context<'_>,
+    #[description = "Datoen, f.eks. 2026-12-24"]
+    #[rename = "dato"]
+    date: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let Some(date) = parse_date(&date) else {
+        ctx.say(t!(locale, "invalid-date")).await?;
+        return Ok(());
+    };
+
+    let message = match ctx.data().schedules.remove_exception(&guild_id, date).await {
+        Ok(true) => t!(locale, "plan-exception-removed", date = date.to_string()),
+        Ok(false) => t!(locale, "plan-exception-not-found", date = date.to_string()),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to remove schedule exception");
+            t!(locale, "plan-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
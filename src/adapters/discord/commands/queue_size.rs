@@ -1,22 +1,82 @@
-use crate::adapters::discord::{Context, Error};
+use crate::adapters::discord::i18n::{locale, t};
+use crate::adapters::discord::{Context, Error, check_ordering_channel};
+use crate::domain::{SessionMode, capacity::capacity_status, queue::DEFAULT_LANE};
 
 /// Se hvor mange som er foran deg i køen
 #[tracing::instrument(name = "queue", skip(ctx))]
-#[poise::command(prefix_command, slash_command, rename = "kø")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "kø",
+    check = "check_ordering_channel"
+)]
 pub async fn queue(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
 
     if !ctx.data().queue.is_open(&guild_id) {
-        ctx.say("🚨 Bestilling er stengt").await?;
+        ctx.say(t!(locale, "queue-closed")).await?;
         return Ok(());
     }
 
+    // Lanes can be in use even if the variant was removed from the settings
+    let mut lanes = ctx.data().settings.get(&guild_id).await?.lanes();
+    for lane in ctx.data().queue.lanes(&guild_id).await {
+        if !lanes.contains(&lane) {
+            lanes.push(lane);
+        }
+    }
+
     let user_id = ctx.author().id.to_string();
-    let message = match ctx.data().queue.index_of(&guild_id, &user_id).await {
-        Some(index) => format!("😎 Du er {} i køen", index + 1),
-        None => "🚨 Du er ikke i køen.".to_string(),
+    let mut positions = Vec::new();
+    for lane in &lanes {
+        if let Some(index) = ctx.data().queue.index_of(&guild_id, lane, &user_id).await {
+            positions.push((lane, index));
+        }
+    }
+
+    let lottery = ctx.data().queue.mode(&guild_id) == Some(SessionMode::LotterySignup);
+
+    let waitlisted = ctx
+        .data()
+        .queue
+        .waitlist(&guild_id)
+        .await
+        .iter()
+        .position(|waiting| waiting.entry.user_id == user_id);
+
+    let mut message = match (positions.as_slice(), waitlisted) {
+        ([], Some(index)) => t!(locale, "queue-waitlisted", position = index + 1),
+        ([], None) if lottery => t!(locale, "queue-not-in-lottery"),
+        ([], None) => t!(locale, "queue-not-in-queue"),
+        _ if lottery => t!(locale, "queue-in-lottery"),
+        ([(lane, index)], _) if lane.as_str() == DEFAULT_LANE => {
+            t!(locale, "queue-position", position = index + 1)
+        }
+        _ => {
+            let mut message = t!(locale, "queue-positions");
+            for (lane, index) in positions {
+                message.push('\n');
+                message.push_str(&t!(
+                    locale,
+                    "queue-lane-position",
+                    lane = lane.as_str(),
+                    position = index + 1
+                ));
+            }
+            message
+        }
     };
 
+    if let Some(status) = capacity_status(ctx.data().queue.as_ref(), &guild_id).await {
+        message.push('\n');
+        message.push_str(&t!(
+            locale,
+            "capacity-remaining",
+            remaining = status.remaining()
+        ));
+    }
+
     ctx.say(message).await?;
 
     Ok(())
@@ -0,0 +1,424 @@
+use serenity::all::ChannelId;
+use tracing::error;
+
+use crate::adapters::discord::commands::language::LanguageChoice;
+use crate::adapters::discord::i18n::{locale, t};
+use crate::adapters::discord::{Context, Error, check_is_oracle};
+use crate::domain::{
+    GuildSettings, LimitPeriod, Locale, OverflowMode, ServingMode, queue::DEFAULT_LANE,
+};
+
+/// Endre innstillinger for vaffelbot
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "innstillinger",
+    subcommands(
+        "max_waffles",
+        "variants",
+        "serving",
+        "user_limit",
+        "cooldown",
+        "overflow",
+        "idle_close",
+        "channels",
+        "language"
+    ),
+    subcommand_required
+)]
+pub async fn settings(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Sett hvor mange vafler man kan bestille om gangen
+#[tracing::instrument(name = "settings_max_waffles", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "maks-vafler",
+    check = "check_is_oracle"
+)]
+pub async fn max_waffles(
+    ctx: Context<'_>,
+    #[description = "Maks antall vafler per bestilling"]
+    #[min = 1]
+    #[max = 10]
+    amount: u32,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| settings.max_waffles_per_order = amount.clamp(1, 10),
+        |settings, locale| {
+            t!(
+                locale,
+                "settings-max-waffles",
+                amount = settings.max_waffles_per_order
+            )
+        },
+    )
+    .await
+}
+
+/// Sett hvilke varianter som har egen kø, f.eks. glutenfri
+#[tracing::instrument(name = "settings_variants", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "varianter",
+    check = "check_is_oracle"
+)]
+pub async fn variants(
+    ctx: Context<'_>,
+    #[description = "Varianter skilt med komma. Tom for bare standard"]
+    #[rename = "varianter"]
+    names: Option<String>,
+) -> Result<(), Error> {
+    let mut variants: Vec<String> = Vec::new();
+    for name in names.as_deref().unwrap_or_default().split(',') {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() || name == DEFAULT_LANE || variants.contains(&name) {
+            continue;
+        }
+        variants.push(name);
+    }
+
+    update_settings(
+        ctx,
+        |settings| settings.variants = variants,
+        |settings, locale| {
+            if settings.variants.is_empty() {
+                t!(locale, "settings-variants-none")
+            } else {
+                t!(
+                    locale,
+                    "settings-variants",
+                    variants = settings.variants.join("**, **")
+                )
+            }
+        },
+    )
+    .await
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum ServingChoice {
+    #[name = "Først til mølla"]
+    Fifo,
+    #[name = "De som ikke har fått vaffel først"]
+    FirstTimersFirst,
+}
+
+impl From<ServingChoice> for ServingMode {
+    fn from(choice: ServingChoice) -> Self {
+        match choice {
+            ServingChoice::Fifo => ServingMode::Fifo,
+            ServingChoice::FirstTimersFirst => ServingMode::FirstTimersFirst,
+        }
+    }
+}
+
+/// Velg hvem som får vafler først når det stekes
+#[tracing::instrument(name = "settings_serving", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "servering",
+    check = "check_is_oracle"
+)]
+pub async fn serving(
+    ctx: Context<'_>,
+    #[description = "Hvem skal få vafler først?"]
+    #[rename = "modus"]
+    mode: ServingChoice,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| settings.serving_mode = mode.into(),
+        |settings, locale| match settings.serving_mode {
+            ServingMode::Fifo => t!(locale, "settings-serving-fifo"),
+            ServingMode::FirstTimersFirst => t!(locale, "settings-serving-first-timers-first"),
+        },
+    )
+    .await
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum PeriodChoice {
+    #[name = "Per åpning"]
+    Session,
+    #[name = "Per dag"]
+    Day,
+}
+
+impl From<PeriodChoice> for LimitPeriod {
+    fn from(choice: PeriodChoice) -> Self {
+        match choice {
+            PeriodChoice::Session => LimitPeriod::Session,
+            PeriodChoice::Day => LimitPeriod::Day,
+        }
+    }
+}
+
+/// Sett hvor mange vafler hver person kan få
+#[tracing::instrument(name = "settings_user_limit", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "grense",
+    check = "check_is_oracle"
+)]
+pub async fn user_limit(
+    ctx: Context<'_>,
+    #[description = "Maks antall vafler per person. Tom for ingen grense"]
+    #[rename = "antall"]
+    #[min = 1]
+    amount: Option<u32>,
+    #[description = "Gjelder grensen per åpning eller per dag?"]
+    #[rename = "periode"]
+    period: Option<PeriodChoice>,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| {
+            settings.max_waffles_per_user = amount.filter(|&amount| amount > 0);
+            if let Some(period) = period {
+                settings.limit_period = period.into();
+            }
+        },
+        |settings, locale| match settings.max_waffles_per_user {
+            Some(max) => match settings.limit_period {
+                LimitPeriod::Session => t!(locale, "settings-user-limit-session", max = max),
+                LimitPeriod::Day => t!(locale, "settings-user-limit-day", max = max),
+            },
+            None => t!(locale, "settings-user-limit-none"),
+        },
+    )
+    .await
+}
+
+/// Sett hvor lenge man må vente etter å ha fått vaffel før man kan bestille igjen
+#[tracing::instrument(name = "settings_cooldown", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "pause",
+    check = "check_is_oracle"
+)]
+pub async fn cooldown(
+    ctx: Context<'_>,
+    #[description = "Antall minutter. Tom for ingen pause"]
+    #[rename = "minutter"]
+    #[min = 1]
+    minutes: Option<u32>,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| settings.rejoin_cooldown_minutes = minutes.filter(|&minutes| minutes > 0),
+        |settings, locale| match settings.rejoin_cooldown_minutes {
+            Some(minutes) => t!(locale, "settings-cooldown", minutes = minutes),
+            None => t!(locale, "settings-cooldown-none"),
+        },
+    )
+    .await
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum OverflowChoice {
+    #[name = "Avvis bestillingen"]
+    Refuse,
+    #[name = "Sett på venteliste"]
+    Waitlist,
+}
+
+impl From<OverflowChoice> for OverflowMode {
+    fn from(choice: OverflowChoice) -> Self {
+        match choice {
+            OverflowChoice::Refuse => OverflowMode::Refuse,
+            OverflowChoice::Waitlist => OverflowMode::Waitlist,
+        }
+    }
+}
+
+/// Velg hva som skjer med bestillinger når det er tomt for røre
+#[tracing::instrument(name = "settings_overflow", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "fullt",
+    check = "check_is_oracle"
+)]
+pub async fn overflow(
+    ctx: Context<'_>,
+    #[description = "Hva skal skje når det ikke er mer røre?"]
+    #[rename = "modus"]
+    mode: OverflowChoice,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| settings.overflow_mode = mode.into(),
+        |settings, locale| match settings.overflow_mode {
+            OverflowMode::Refuse => t!(locale, "settings-overflow-refuse"),
+            OverflowMode::Waitlist => t!(locale, "settings-overflow-waitlist"),
+        },
+    )
+    .await
+}
+
+/// Sett hvor lenge bestilling kan stå åpen uten at noen steker før den stenges
+#[tracing::instrument(name = "settings_idle_close", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "inaktiv",
+    check = "check_is_oracle"
+)]
+pub async fn idle_close(
+    ctx: Context<'_>,
+    #[description = "Antall minutter. Tom for å aldri stenge automatisk"]
+    #[rename = "minutter"]
+    #[min = 1]
+    minutes: Option<u32>,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| settings.idle_close_minutes = minutes.filter(|&minutes| minutes > 0),
+        |settings, locale| match settings.idle_close_minutes {
+            Some(minutes) => t!(locale, "settings-idle-close", minutes = minutes),
+            None => t!(locale, "settings-idle-close-none"),
+        },
+    )
+    .await
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum ChannelChoice {
+    #[name = "Bestilling"]
+    Ordering,
+    #[name = "Orakel"]
+    Oracle,
+    #[name = "Logg"]
+    Audit,
+    #[name = "Alle"]
+    All,
+}
+
+/// Velg hvor vafler bestilles og hvor orakelkommandoene brukes
+#[tracing::instrument(name = "settings_channels", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "kanaler",
+    check = "check_is_oracle"
+)]
+pub async fn channels(
+    ctx: Context<'_>,
+    #[description = "Kanalen for /vaffel og /kø. Tom for å beholde den som er valgt"]
+    #[rename = "bestilling"]
+    ordering: Option<ChannelId>,
+    #[description = "Kanalen for /start, /stekt og /stopp. Tom for å beholde den som er valgt"]
+    #[rename = "orakel"]
+    oracle: Option<ChannelId>,
+    #[description = "Kanalen orakelhandlinger logges i. Tom for å beholde den som er valgt"]
+    #[rename = "logg"]
+    audit: Option<ChannelId>,
+    #[description = "Kanalen som skal fjernes, så alle kanaler kan brukes eller ingenting logges"]
+    #[rename = "fjern"]
+    clear: Option<ChannelChoice>,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| {
+            // Clearing comes first, so a channel given in the same command is kept
+            match clear {
+                Some(ChannelChoice::Ordering) => settings.ordering_channel_id = None,
+                Some(ChannelChoice::Oracle) => settings.oracle_channel_id = None,
+                Some(ChannelChoice::Audit) => settings.audit_channel_id = None,
+                Some(ChannelChoice::All) => {
+                    settings.ordering_channel_id = None;
+                    settings.oracle_channel_id = None;
+                    settings.audit_channel_id = None;
+                }
+                None => {}
+            }
+            if let Some(ordering) = ordering {
+                settings.ordering_channel_id = Some(ordering.to_string());
+            }
+            if let Some(oracle) = oracle {
+                settings.oracle_channel_id = Some(oracle.to_string());
+            }
+            if let Some(audit) = audit {
+                settings.audit_channel_id = Some(audit.to_string());
+            }
+        },
+        |settings, locale| {
+            let describe = |channel: &Option<String>| match channel {
+                Some(channel_id) => format!("<#{channel_id}>"),
+                None => t!(locale, "settings-channels-all"),
+            };
+
+            let mut message = t!(
+                locale,
+                "settings-channels",
+                ordering = describe(&settings.ordering_channel_id),
+                oracle = describe(&settings.oracle_channel_id)
+            );
+            if let Some(audit_channel_id) = &settings.audit_channel_id {
+                message.push_str(&t!(
+                    locale,
+                    "settings-channels-audit",
+                    channel = format!("<#{audit_channel_id}>")
+                ));
+            }
+            message
+        },
+    )
+    .await
+}
+
+/// Velg hvilket språk vaffelbot bruker i serveren
+#[tracing::instrument(name = "settings_language", skip(ctx))]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "språk",
+    check = "check_is_oracle"
+)]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "Språket for beskjeder til alle i serveren"]
+    #[rename = "språk"]
+    language: LanguageChoice,
+) -> Result<(), Error> {
+    update_settings(
+        ctx,
+        |settings| settings.locale = Locale::from(language),
+        // Confirm in the new language, so the oracle sees what everyone will see
+        |settings, _| t!(settings.locale, "settings-language"),
+    )
+    .await
+}
+
+/// Change the settings of the guild and reply with what they are now.
+///
+/// If saving fails, the oracle is told so instead.
+async fn update_settings(
+    ctx: Context<'_>,
+    change: impl FnOnce(&mut GuildSettings) + Send,
+    reply: impl FnOnce(&GuildSettings, Locale) -> String + Send,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let mut settings = ctx.data().settings.get(&guild_id).await?;
+    change(&mut settings);
+
+    if let Err(e) = ctx.data().settings.save(&guild_id, &settings).await {
+        error!(guild_id = %guild_id, error = ?e, "Failed to save settings");
+        ctx.say(t!(locale, "settings-save-failed")).await?;
+        return Ok(());
+    }
+
+    ctx.say(reply(&settings, locale)).await?;
+
+    Ok(())
+}
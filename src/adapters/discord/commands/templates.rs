@@ -0,0 +1,301 @@
+use chrono::{Duration, Utc};
+use serenity::all::Mentionable;
+use tracing::error;
+
+use crate::adapters::discord::i18n::{locale, t, tr};
+use crate::adapters::discord::{Context, Error, check_is_oracle};
+use crate::domain::template::{MAX_TEMPLATE_LENGTH, render, validate};
+use crate::domain::{Locale, TemplateError, TemplateKind};
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum TemplateChoice {
+    #[name = "Åpnet"]
+    Opened,
+    #[name = "Lotteri åpnet"]
+    OpenedLottery,
+    #[name = "Stenger snart"]
+    ClosingSoon,
+    #[name = "Stengt"]
+    Closed,
+    #[name = "Stekt"]
+    Baked,
+    #[name = "Bestilt"]
+    Joined,
+}
+
+impl From<TemplateChoice> for TemplateKind {
+    fn from(choice: TemplateChoice) -> Self {
+        match choice {
+            TemplateChoice::Opened => TemplateKind::Opened,
+            TemplateChoice::OpenedLottery => TemplateKind::OpenedLottery,
+            TemplateChoice::ClosingSoon => TemplateKind::ClosingSoon,
+            TemplateChoice::Closed => TemplateKind::Closed,
+            TemplateChoice::Baked => TemplateKind::Baked,
+            TemplateChoice::Joined => TemplateKind::Joined,
+        }
+    }
+}
+
+fn template_name(locale: Locale, kind: TemplateKind) -> String {
+    tr(locale, &format!("template-name-{}", kind.as_str()), None)
+}
+
+fn placeholders(locale: Locale, kind: TemplateKind) -> String {
+    if kind.placeholders().is_empty() {
+        return t!(locale, "templates-no-placeholders");
+    }
+
+    let names: Vec<String> = kind
+        .placeholders()
+        .iter()
+        .map(|name| format!("`{{{name}}}`"))
+        .collect();
+    t!(
+        locale,
+        "templates-placeholders",
+        placeholders = names.join(", ")
+    )
+}
+
+fn error_message(locale: Locale, kind: TemplateKind, error: &TemplateError) -> String {
+    match error {
+        TemplateError::Empty => t!(locale, "templates-error-empty"),
+        TemplateError::TooLong => t!(
+            locale,
+            "templates-error-too-long",
+            max = MAX_TEMPLATE_LENGTH
+        ),
+        TemplateError::Unclosed => t!(locale, "templates-error-unclosed"),
+        TemplateError::UnopenedBrace => t!(locale, "templates-error-unopened"),
+        TemplateError::UnknownPlaceholder(name) => format!(
+            "{} {}",
+            t!(locale, "templates-error-unknown", name = name.as_str()),
+            placeholders(locale, kind)
+        ),
+    }
+}
+
+/// Render a template with made up values, so it can be checked before use
+fn render_preview(ctx: Context<'_>, locale: Locale, template: &str) -> String {
+    let time = format!("<t:{}:R>", (Utc::now() + Duration::minutes(10)).timestamp());
+    let mention = ctx.author().mention().to_string();
+    let queue = t!(locale, "queue-name");
+
+    render(
+        template,
+        &[
+            ("time", &time),
+            ("count", "3"),
+            ("mentions", &mention),
+            ("position", "2"),
+            ("queue", &queue),
+        ],
+    )
+}
+
+/// Longest message Discord accepts
+const MAX_MESSAGE_LENGTH: usize = 2000;
+
+/// Templates longer than this are cut short in `/mal vis`
+const MAX_LISTED_TEMPLATE_LENGTH: usize = 300;
+
+/// Quote every line of a template, cut short if it is long
+fn quote(template: &str) -> String {
+    let mut shown: String = template.chars().take(MAX_LISTED_TEMPLATE_LENGTH).collect();
+    if shown.len() < template.len() {
+        shown.push('…');
+    }
+    shown
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Join sections into as few messages as fit within `max` characters
+fn pack(sections: Vec<String>, max: usize) -> Vec<String> {
+    let mut messages: Vec<String> = Vec::new();
+    for section in sections {
+        match messages.last_mut() {
+            Some(message) if message.chars().count() + 2 + section.chars().count() <= max => {
+                message.push_str("\n\n");
+                message.push_str(&section);
+            }
+            _ => messages.push(section),
+        }
+    }
+    messages
+}
+
+/// Endre ordlyden i meldingene til vaffelbot
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "mal",
+    subcommands("show", "preview", "set", "reset"),
+    subcommand_required,
+    check = "check_is_oracle"
+)]
+pub async fn templates(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Se hvilke meldinger som har egen ordlyd
+#[tracing::instrument(name = "templates_show", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "vis")]
+pub async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+
+    let overrides = ctx.data().templates.list(&guild_id).await?;
+
+    let mut sections = vec![t!(locale, "templates-title")];
+    for kind in TemplateKind::ALL {
+        let template = match overrides.get(&kind) {
+            Some(template) => quote(template),
+            None => t!(locale, "templates-standard"),
+        };
+        sections.push(format!(
+            "**{}** · {}\n{template}",
+            template_name(locale, kind),
+            placeholders(locale, kind)
+        ));
+    }
+
+    for message in pack(sections, MAX_MESSAGE_LENGTH) {
+        ctx.say(message).await?;
+    }
+
+    Ok(())
+}
+
+/// Se hvordan en mal blir uten å lagre den
+#[tracing::instrument(name = "templates_preview", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "forhåndsvis")]
+pub async fn preview(
+    ctx: Context<'_>,
+    #[description = "Hvilken melding?"]
+    #[rename = "melding"]
+    kind: TemplateChoice,
+    #[description = "Ny tekst, f.eks. 🧇 {count} vafler til {mentions}"]
+    #[rename = "tekst"]
+    template: String,
+) -> Result<(), Error> {
+    let locale = locale(ctx).await;
+    let kind = TemplateKind::from(kind);
+
+    let message = match validate(kind, &template) {
+        Ok(()) => format!(
+            "{}\n{}",
+            t!(locale, "templates-preview"),
+            render_preview(ctx, locale, &template)
+        ),
+        Err(e) => error_message(locale, kind, &e),
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Gi en melding egen ordlyd
+#[tracing::instrument(name = "templates_set", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "sett")]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Hvilken melding?"]
+    #[rename = "melding"]
+    kind: TemplateChoice,
+    #[description = "Ny tekst, f.eks. 🧇 {count} vafler til {mentions}"]
+    #[rename = "tekst"]
+    template: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+    let kind = TemplateKind::from(kind);
+
+    if let Err(e) = validate(kind, &template) {
+        ctx.say(error_message(locale, kind, &e)).await?;
+        return Ok(());
+    }
+
+    let message = match ctx.data().templates.set(&guild_id, kind, &template).await {
+        Ok(()) => format!(
+            "{}\n{}",
+            t!(
+                locale,
+                "templates-saved",
+                name = template_name(locale, kind)
+            ),
+            render_preview(ctx, locale, &template)
+        ),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to save message template");
+            t!(locale, "templates-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Gå tilbake til standardteksten for en melding
+#[tracing::instrument(name = "templates_reset", skip(ctx))]
+#[poise::command(prefix_command, slash_command, rename = "tilbakestill")]
+pub async fn reset(
+    ctx: Context<'_>,
+    #[description = "Hvilken melding?"]
+    #[rename = "melding"]
+    kind: TemplateChoice,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
+    let kind = TemplateKind::from(kind);
+    let name = template_name(locale, kind);
+
+    let message = match ctx.data().templates.remove(&guild_id, kind).await {
+        Ok(true) => t!(locale, "templates-reset", name = name),
+        Ok(false) => t!(locale, "templates-not-changed", name = name),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = ?e, "Failed to remove message template");
+            t!(locale, "templates-save-failed")
+        }
+    };
+
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_every_line() {
+        assert_eq!(
+            quote("🧇 {count}\ntil {mentions}"),
+            "> 🧇 {count}\n> til {mentions}"
+        );
+
+        let long = "a".repeat(MAX_TEMPLATE_LENGTH);
+        let quoted = quote(&long);
+        assert_eq!(quoted.chars().count(), 2 + MAX_LISTED_TEMPLATE_LENGTH + 1);
+        assert!(quoted.ends_with('…'));
+    }
+
+    #[test]
+    fn test_pack_stays_within_limit() {
+        let sections = vec!["a".repeat(900), "b".repeat(900), "c".repeat(900)];
+        let messages = pack(sections, MAX_MESSAGE_LENGTH);
+
+        assert_eq!(messages.len(), 2);
+        assert!(
+            messages
+                .iter()
+                .all(|m| m.chars().count() <= MAX_MESSAGE_LENGTH)
+        );
+        assert_eq!(messages[1], "c".repeat(900));
+    }
+}
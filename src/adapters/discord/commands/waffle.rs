@@ -1,30 +1,123 @@
-use crate::adapters::discord::{Context, Error};
-use crate::domain::QueueEntry;
+use crate::adapters::discord::i18n::{locale, t};
+use crate::adapters::discord::templates::Templates;
+use crate::adapters::discord::{Context, Error, autocomplete_variant, check_ordering_channel};
+use crate::domain::ordering::{JoinOutcome, Order, join};
+use crate::domain::{Locale, QueueEvent, Refusal, TemplateKind, queue::DEFAULT_LANE};
 
 /// Få en orakel til å steke vaffel til deg
 #[tracing::instrument(name = "waffle", skip(ctx))]
-#[poise::command(prefix_command, slash_command, rename = "vaffel")]
-pub async fn waffle(ctx: Context<'_>) -> Result<(), Error> {
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "vaffel",
+    check = "check_ordering_channel"
+)]
+pub async fn waffle(
+    ctx: Context<'_>,
+    #[description = "Hvor mange vafler?"]
+    #[rename = "antall"]
+    #[min = 1]
+    amount: Option<u32>,
+    #[description = "Hva vil du ha på? Skill flere med komma"]
+    #[autocomplete = "autocomplete_toppings"]
+    topping: Option<String>,
+    #[description = "Hvilken variant? F.eks. glutenfri"]
+    #[autocomplete = "autocomplete_variant"]
+    variant: Option<String>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap().to_string();
+    let locale = locale(ctx).await;
 
-    if !ctx.data().queue.is_open(&guild_id) {
-        ctx.say("🏮 Bestilling er stengt").await?;
-        return Ok(());
-    }
+    let settings = ctx.data().settings.get(&guild_id).await?;
+    let order = Order {
+        user_id: ctx.author().id.to_string(),
+        display_name: ctx.author().name.clone(),
+        amount: amount.unwrap_or(1),
+        toppings: topping,
+        variant,
+        ticket: None,
+    };
+
+    let outcome = join(
+        ctx.data().queue.as_ref(),
+        ctx.data().orders.as_ref(),
+        ctx.data().products.as_ref(),
+        &settings,
+        &guild_id,
+        order,
+        &ctx.data().timezone,
+    )
+    .await?;
 
-    let user_id = ctx.author().id.to_string();
-    let display_name = ctx.author().name.clone();
+    let message = match outcome {
+        JoinOutcome::Joined {
+            lane,
+            entry,
+            position,
+            lottery,
+        } => {
+            let amount = entry.quantity;
+            ctx.data().events.publish(
+                &guild_id,
+                QueueEvent::Joined {
+                    lane: lane.clone(),
+                    entry,
+                    position,
+                },
+            );
 
-    let message = match ctx.data().queue.index_of(&guild_id, &user_id).await {
-        Some(index) => format!(
-            "⏲️ Du er **allerede** i køen. Du er nummer **{}** i køen.",
-            index + 1
+            if lottery {
+                t!(locale, "waffle-joined-lottery")
+            } else {
+                let queue_name = queue_name(locale, &lane);
+                let templates =
+                    Templates::load(ctx.data().templates.as_ref(), &guild_id, locale).await;
+                templates.render(
+                    TemplateKind::Joined,
+                    &[
+                        ("count", &amount.to_string()),
+                        ("position", &position.to_string()),
+                        ("queue", &queue_name),
+                    ],
+                    || {
+                        t!(
+                            locale,
+                            "waffle-joined",
+                            queue = queue_name.as_str(),
+                            amount = amount,
+                            position = position
+                        )
+                    },
+                )
+            }
+        }
+        JoinOutcome::Waitlisted { position, .. } => {
+            t!(locale, "waffle-waitlisted", position = position)
+        }
+        JoinOutcome::Closed => t!(locale, "waffle-closed"),
+        JoinOutcome::AlreadyDrawn => t!(locale, "lottery-already-drawn"),
+        JoinOutcome::UnknownVariant(variant) => {
+            t!(locale, "unknown-variant", variant = variant)
+        }
+        JoinOutcome::AlreadyWaitlisted { position } => {
+            t!(locale, "waffle-already-waitlisted", position = position)
+        }
+        JoinOutcome::AlreadyQueued { lottery: true, .. } => {
+            t!(locale, "waffle-already-in-lottery")
+        }
+        JoinOutcome::AlreadyQueued { lane, position, .. } => t!(
+            locale,
+            "waffle-already-in-queue",
+            queue = queue_name(locale, &lane),
+            position = position
         ),
-        None => {
-            let size = ctx.data().queue.size(&guild_id).await;
-            let entry = QueueEntry::new(user_id, display_name);
-            ctx.data().queue.push(&guild_id, entry).await;
-            format!("⏲️ Du er nå i køen. Du er nummer **{}** i køen.", size + 1)
+        JoinOutcome::Refused(refusal) => refusal_message(locale, &refusal),
+        JoinOutcome::UnknownOption(unknown) => {
+            t!(locale, "waffle-unknown-option", option = unknown)
+        }
+        JoinOutcome::OutOfBatter => t!(locale, "waffle-out-of-batter"),
+        JoinOutcome::BatterLeft { remaining } => {
+            t!(locale, "waffle-batter-left", remaining = remaining)
         }
     };
 
@@ -32,3 +125,60 @@ pub async fn waffle(ctx: Context<'_>) -> Result<(), Error> {
 
     Ok(())
 }
+
+fn queue_name(locale: Locale, lane: &str) -> String {
+    if lane == DEFAULT_LANE {
+        t!(locale, "queue-name")
+    } else {
+        t!(locale, "queue-name-lane", lane = lane)
+    }
+}
+
+fn refusal_message(locale: Locale, refusal: &Refusal) -> String {
+    match refusal {
+        Refusal::TooManyInOrder { max } => t!(locale, "refusal-too-many", max = *max),
+        Refusal::LimitReached {
+            limit,
+            remaining,
+            retry_at,
+        } => t!(
+            locale,
+            "refusal-limit",
+            limit = *limit,
+            remaining = *remaining,
+            retry = retry_at
+                .map(|retry_at| retry_at.timestamp().to_string())
+                .unwrap_or_else(|| "none".to_string())
+        ),
+        Refusal::Cooldown { until } => t!(
+            locale,
+            "refusal-cooldown",
+            until = until.timestamp().to_string()
+        ),
+    }
+}
+
+/// Suggest options for the last item in a comma separated list
+async fn autocomplete_toppings(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return vec![];
+    };
+
+    let (picked, current) = match partial.rsplit_once(',') {
+        Some((picked, current)) => (Some(picked.trim()), current.trim().to_lowercase()),
+        None => (None, partial.trim().to_lowercase()),
+    };
+
+    ctx.data()
+        .products
+        .list(&guild_id.to_string())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|option| option.to_lowercase().starts_with(&current))
+        .map(|option| match picked {
+            Some(picked) => format!("{picked}, {option}"),
+            None => option,
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use serenity::all::{ConnectionStage, ShardManager};
+
+use crate::domain::HealthCheck;
+
+/// Tells whether the bot is connected to the Discord gateway.
+///
+/// The shards only exist once the client is built, so this is handed out
+/// before the bot starts and filled in when it does.
+#[derive(Clone, Default)]
+pub struct GatewayHealth {
+    shard_manager: Arc<OnceLock<Arc<ShardManager>>>,
+}
+
+impl GatewayHealth {
+    pub(super) fn set(&self, shard_manager: Arc<ShardManager>) {
+        let _ = self.shard_manager.set(shard_manager);
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for GatewayHealth {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    /// Every shard has to be connected. The latency is the slowest heartbeat.
+    async fn check(&self) -> anyhow::Result<Duration> {
+        let Some(shard_manager) = self.shard_manager.get() else {
+            anyhow::bail!("The bot has not started yet");
+        };
+
+        let runners = shard_manager.runners.lock().await;
+        if runners.is_empty() {
+            anyhow::bail!("No shards are running");
+        }
+
+        let mut latency = Duration::ZERO;
+        for (id, runner) in runners.iter() {
+            if runner.stage != ConnectionStage::Connected {
+                anyhow::bail!("Shard {id} is {}", runner.stage);
+            }
+            latency = latency.max(runner.latency.unwrap_or_default());
+        }
+        Ok(latency)
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::Instant;
+
+use metrics::{counter, histogram};
+use poise::FrameworkError;
+use tracing::error;
+
+use crate::adapters::discord::{Context, Data, Error};
+use crate::telemetry::{COMMAND_DURATION, COMMANDS};
+
+/// When the command being handled started, kept as invocation data
+struct Started(Instant);
+
+/// Runs after the checks of a command have passed, before the command itself
+pub async fn pre_command(ctx: Context<'_>) {
+    ctx.set_invocation_data(Started(Instant::now())).await;
+}
+
+/// Runs after a command has finished without an error
+pub async fn post_command(ctx: Context<'_>) {
+    record(ctx, "success").await;
+}
+
+pub async fn on_error(error: FrameworkError<'_, Data, Error>) {
+    if let Some(ctx) = error.ctx() {
+        let outcome = match &error {
+            FrameworkError::Command { .. } | FrameworkError::CommandPanic { .. } => "error",
+            FrameworkError::ArgumentParse { .. }
+            | FrameworkError::CommandStructureMismatch { .. }
+            | FrameworkError::SubcommandRequired { .. } => "invalid",
+            _ => "refused",
+        };
+        record(ctx, outcome).await;
+    }
+
+    if let Err(e) = poise::builtins::on_error(error).await {
+        error!(error = ?e, "Failed to handle command error");
+    }
+}
+
+async fn record(ctx: Context<'_>, outcome: &'static str) {
+    let command = ctx.command().qualified_name.clone();
+    counter!(COMMANDS, "command" => command.clone(), "outcome" => outcome).increment(1);
+
+    // Commands refused by a check never started
+    if let Some(started) = ctx.invocation_data::<Started>().await {
+        histogram!(COMMAND_DURATION, "command" => command)
+            .record(started.0.elapsed().as_secs_f64());
+    }
+}
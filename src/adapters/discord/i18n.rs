@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use tracing::{error, warn};
+
+use crate::adapters::discord::{Command, Context};
+use crate::domain::Locale;
+
+/// Message catalogs, embedded so the binary does not depend on the working directory
+const SOURCES: [(Locale, &str); 3] = [
+    (Locale::Nb, include_str!("../../../locales/nb.ftl")),
+    (Locale::Nn, include_str!("../../../locales/nn.ftl")),
+    (Locale::En, include_str!("../../../locales/en.ftl")),
+];
+
+static CATALOG: LazyLock<Catalog> = LazyLock::new(Catalog::load);
+
+struct Catalog {
+    bundles: HashMap<Locale, FluentBundle<FluentResource>>,
+}
+
+impl Catalog {
+    fn load() -> Self {
+        let bundles = SOURCES
+            .into_iter()
+            .map(|(locale, source)| {
+                let resource = FluentResource::try_new(source.to_string())
+                    .expect("Failed to parse message catalog");
+                let language = locale
+                    .as_str()
+                    .parse()
+                    .expect("Locale is not a valid language identifier");
+
+                let mut bundle = FluentBundle::new_concurrent(vec![language]);
+                // Discord shows the isolation marks around arguments as boxes
+                bundle.set_use_isolating(false);
+                bundle
+                    .add_resource(resource)
+                    .expect("Message catalog has duplicate keys");
+                (locale, bundle)
+            })
+            .collect();
+
+        Self { bundles }
+    }
+
+    fn format(&self, locale: Locale, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let bundle = self.bundles.get(&locale)?;
+        let pattern = bundle.get_message(id)?.value()?;
+
+        let mut errors = vec![];
+        let message = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            warn!(locale = locale.as_str(), id, errors = ?errors, "Failed to format message");
+        }
+        Some(message.into_owned())
+    }
+}
+
+/// Look up a message in the catalog of a locale.
+///
+/// Falls back to bokmål, and then to the id itself, so a missing message
+/// never stops a reply.
+pub fn tr(locale: Locale, id: &str, args: Option<&FluentArgs>) -> String {
+    CATALOG
+        .format(locale, id, args)
+        .or_else(|| CATALOG.format(Locale::default(), id, args))
+        .unwrap_or_else(|| {
+            error!(locale = locale.as_str(), id, "Missing message in catalog");
+            id.to_string()
+        })
+}
+
+/// Translate a message, e.g. `t!(locale, "queue-position", position = 3)`
+macro_rules! t {
+    ($locale:expr, $id:expr) => {
+        $crate::adapters::discord::i18n::tr($locale, $id, None)
+    };
+    ($locale:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::adapters::discord::i18n::tr($locale, $id, Some(&args))
+    }};
+}
+pub(crate) use t;
+
+/// Language to reply to the user running the command in.
+/// The language the user picked wins over the one of the guild.
+pub async fn locale(ctx: Context<'_>) -> Locale {
+    let user_id = ctx.author().id.to_string();
+    match ctx.data().user_locales.get(&user_id).await {
+        Ok(Some(locale)) => return locale,
+        Ok(None) => {}
+        Err(e) => error!(user_id, error = ?e, "Failed to fetch user locale"),
+    }
+
+    guild_locale(ctx).await
+}
+
+/// Language of the guild the command was run in, used for messages meant
+/// for everyone
+pub async fn guild_locale(ctx: Context<'_>) -> Locale {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Locale::default();
+    };
+
+    ctx.data()
+        .settings
+        .get(&guild_id.to_string())
+        .await
+        .map(|settings| settings.locale)
+        .unwrap_or_default()
+}
+
+/// Locales Discord uses for the clients of a language
+fn discord_locales(locale: Locale) -> &'static [&'static str] {
+    match locale {
+        Locale::Nb => &["no"],
+        // Discord has no nynorsk, so those users see bokmål command names
+        Locale::Nn => &[],
+        Locale::En => &["en-US", "en-GB"],
+    }
+}
+
+/// Add the names and descriptions from the catalogs to the slash commands.
+///
+/// A command is looked up as `command-<path>`, where the path is made of the
+/// function names of the command and its parents, e.g. `command-menu-show`.
+/// Parameters add their name to that, and choices their index. Fluent keys
+/// are ASCII, so `språk` is looked up as `sprak`.
+pub fn apply_translations(commands: &mut [Command]) {
+    for command in commands {
+        translate_command(command, "command");
+    }
+}
+
+fn translate_command(command: &mut Command, parent: &str) {
+    let key = format!("{parent}-{}", key_part(&command.identifying_name));
+
+    for locale in Locale::ALL {
+        for &discord_locale in discord_locales(locale) {
+            if let Some(name) = CATALOG.format(locale, &key, None) {
+                command
+                    .name_localizations
+                    .insert(discord_locale.to_string(), name);
+            }
+            if let Some(description) = CATALOG.format(locale, &format!("{key}-description"), None) {
+                command
+                    .description_localizations
+                    .insert(discord_locale.to_string(), description);
+            }
+
+            for parameter in &mut command.parameters {
+                let key = format!("{key}-{}", key_part(&parameter.name));
+                if let Some(name) = CATALOG.format(locale, &key, None) {
+                    parameter
+                        .name_localizations
+                        .insert(discord_locale.to_string(), name);
+                }
+                if let Some(description) =
+                    CATALOG.format(locale, &format!("{key}-description"), None)
+                {
+                    parameter
+                        .description_localizations
+                        .insert(discord_locale.to_string(), description);
+                }
+                for (i, choice) in parameter.choices.iter_mut().enumerate() {
+                    if let Some(name) = CATALOG.format(locale, &format!("{key}-{i}"), None) {
+                        choice
+                            .localizations
+                            .insert(discord_locale.to_string(), name);
+                    }
+                }
+            }
+        }
+    }
+
+    for subcommand in &mut command.subcommands {
+        translate_command(subcommand, &key);
+    }
+}
+
+fn key_part(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'æ' => "ae".to_string(),
+            'ø' => "o".to_string(),
+            'å' => "a".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use fluent_syntax::ast::Entry;
+
+    use super::*;
+
+    fn keys(source: &str) -> BTreeSet<String> {
+        fluent_syntax::parser::parse(source)
+            .expect("Failed to parse message catalog")
+            .body
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message.id.name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_key_exists_in_every_locale() {
+        let all: BTreeSet<String> = SOURCES
+            .iter()
+            .flat_map(|(_, source)| keys(source))
+            .collect();
+
+        for (locale, source) in SOURCES {
+            let keys = keys(source);
+            let missing: Vec<_> = all.difference(&keys).collect();
+            assert!(
+                missing.is_empty(),
+                "{} is missing {missing:?}",
+                locale.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn test_catalogs_load() {
+        for locale in Locale::ALL {
+            assert_eq!(tr(locale, "ping-pong", None), "🏓 Pong!");
+        }
+    }
+
+    #[test]
+    fn test_arguments_and_plurals() {
+        assert_eq!(
+            t!(Locale::En, "stats-total", count = 1),
+            "Total baked: 1 waffle"
+        );
+        assert_eq!(
+            t!(Locale::Nb, "stats-total", count = 3),
+            "Totalt stekt: 3 vafler"
+        );
+    }
+}
@@ -1,58 +1,109 @@
+pub mod announcements;
+pub mod audit;
 pub mod commands;
+pub mod gateway;
+mod hooks;
+pub mod i18n;
+pub mod scheduler;
+pub mod templates;
 
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+use chrono_tz::Tz;
 use poise::FrameworkOptions;
 use serenity::Error as SerenityError;
-use serenity::all::{GatewayIntents, GuildId, RoleId};
+use serenity::all::{ChannelId, GatewayIntents, GuildId, Mentionable, RoleId};
 
-use crate::domain::{OrderRepository, QueueRepository};
+use crate::adapters::discord::gateway::GatewayHealth;
+use crate::adapters::discord::i18n::{locale, t};
+use crate::domain::{
+    ApiTokenRepository, AuditLogRepository, DeadlineRepository, EventBroadcaster, GuildSettings,
+    LotteryRepository, OrderRepository, ProductOptionRepository, QueueRepository, Repositories,
+    ScheduleRepository, SettingsRepository, TemplateRepository, UserLocaleRepository,
+};
 
 const PREFIX: &str = "!";
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Context<'a> = poise::Context<'a, Data, Error>;
+pub type Command = poise::Command<Data, Error>;
 
 pub struct Data {
     pub queue: Arc<dyn QueueRepository>,
     pub orders: Arc<dyn OrderRepository>,
+    pub settings: Arc<dyn SettingsRepository>,
+    pub products: Arc<dyn ProductOptionRepository>,
+    pub lottery: Arc<dyn LotteryRepository>,
+    pub schedules: Arc<dyn ScheduleRepository>,
+    pub deadlines: Arc<dyn DeadlineRepository>,
+    pub audit: Arc<dyn AuditLogRepository>,
+    pub user_locales: Arc<dyn UserLocaleRepository>,
+    pub templates: Arc<dyn TemplateRepository>,
+    pub api_tokens: Arc<dyn ApiTokenRepository>,
+    /// Where changes to the queues are published for the HTTP adapter to stream
+    pub events: EventBroadcaster,
+    pub timezone: Tz,
     pub oracle_roles: RwLock<HashMap<GuildId, RoleId>>,
 }
 
 pub struct DiscordAdapter {
     token: String,
-    queue: Arc<dyn QueueRepository>,
-    orders: Arc<dyn OrderRepository>,
+    repositories: Repositories,
+    events: EventBroadcaster,
+    timezone: Tz,
+    gateway: GatewayHealth,
 }
 
 impl DiscordAdapter {
     pub fn new(
         token: String,
-        queue: Arc<dyn QueueRepository>,
-        orders: Arc<dyn OrderRepository>,
+        repositories: Repositories,
+        events: EventBroadcaster,
+        timezone: Tz,
     ) -> Self {
         Self {
             token,
-            queue,
-            orders,
+            repositories,
+            events,
+            timezone,
+            gateway: GatewayHealth::default(),
         }
     }
 
+    /// Health of the gateway connection, known once the bot has started
+    pub fn gateway(&self) -> GatewayHealth {
+        self.gateway.clone()
+    }
+
     pub async fn start(self) -> Result<(), SerenityError> {
+        let mut commands = vec![
+            commands::api::api(),
+            commands::bake::bake(),
+            commands::capacity::capacity(),
+            commands::close::close(),
+            commands::draw::draw(),
+            commands::language::language(),
+            commands::menu::menu(),
+            commands::open::open(),
+            commands::ping::ping(),
+            commands::plan::plan(),
+            commands::queue_size::queue(),
+            commands::settings::settings(),
+            commands::templates::templates(),
+            commands::waffle::waffle(),
+        ];
+        i18n::apply_translations(&mut commands);
+
         let options: FrameworkOptions<Data, Error> = poise::FrameworkOptions {
-            commands: vec![
-                commands::bake::bake(),
-                commands::close::close(),
-                commands::open::open(),
-                commands::ping::ping(),
-                commands::queue_size::queue(),
-                commands::waffle::waffle(),
-            ],
+            commands,
             prefix_options: poise::PrefixFrameworkOptions {
                 prefix: Some(PREFIX.into()),
                 ..Default::default()
             },
+            pre_command: |ctx| Box::pin(hooks::pre_command(ctx)),
+            post_command: |ctx| Box::pin(hooks::post_command(ctx)),
+            on_error: |error| Box::pin(hooks::on_error(error)),
             ..Default::default()
         };
 
@@ -60,9 +111,31 @@ impl DiscordAdapter {
             .setup(move |ctx, _ready, framework| {
                 Box::pin(async move {
                     poise::builtins::register_globally(ctx, &framework.options().commands).await?;
+
+                    let repositories = self.repositories;
+
+                    scheduler::Scheduler::new(
+                        ctx.clone(),
+                        repositories.clone(),
+                        self.events.clone(),
+                        self.timezone,
+                    )
+                    .spawn();
+
                     Ok(Data {
-                        queue: self.queue.clone(),
-                        orders: self.orders.clone(),
+                        queue: repositories.queue,
+                        orders: repositories.orders,
+                        settings: repositories.settings,
+                        products: repositories.products,
+                        lottery: repositories.lottery,
+                        schedules: repositories.schedules,
+                        deadlines: repositories.deadlines,
+                        audit: repositories.audit,
+                        user_locales: repositories.user_locales,
+                        templates: repositories.templates,
+                        api_tokens: repositories.api_tokens,
+                        events: self.events,
+                        timezone: self.timezone,
                         oracle_roles: RwLock::new(HashMap::new()),
                     })
                 })
@@ -70,12 +143,14 @@ impl DiscordAdapter {
             .options(options)
             .build();
 
+        let gateway = self.gateway.clone();
         let mut client = serenity::Client::builder(
             self.token.clone(),
             GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT,
         )
         .framework(framework)
         .await?;
+        gateway.set(client.shard_manager.clone());
 
         client.start().await
     }
@@ -145,13 +220,126 @@ pub async fn check_is_oracle(ctx: Context<'_>) -> Result<bool, Error> {
     Ok(false)
 }
 
+/// Suggest the lanes of the guild the command was run in
+pub async fn autocomplete_variant(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return vec![];
+    };
+
+    let partial = partial.to_lowercase();
+    ctx.data()
+        .settings
+        .get(&guild_id.to_string())
+        .await
+        .map(|settings| settings.lanes())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|lane| lane.to_lowercase().starts_with(&partial))
+        .collect()
+}
+
+/// Only allow the command in the ordering channel of the guild, if it has one
+pub async fn check_ordering_channel(ctx: Context<'_>) -> Result<bool, Error> {
+    check_channel(
+        ctx,
+        |settings| settings.ordering_channel_id.as_deref(),
+        "channel-ordering-hint",
+    )
+    .await
+}
+
+/// Only allow the command in the oracle channel of the guild, if it has one
+pub async fn check_oracle_channel(ctx: Context<'_>) -> Result<bool, Error> {
+    check_channel(
+        ctx,
+        |settings| settings.oracle_channel_id.as_deref(),
+        "channel-oracle-hint",
+    )
+    .await
+}
+
+async fn check_channel(
+    ctx: Context<'_>,
+    channel: fn(&GuildSettings) -> Option<&str>,
+    hint_id: &str,
+) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let settings = ctx.data().settings.get(&guild_id.to_string()).await?;
+    let Some(channel_id) = channel(&settings)
+        .and_then(|id| id.parse::<u64>().ok())
+        .map(ChannelId::new)
+    else {
+        return Ok(true);
+    };
+
+    if channel_id == ctx.channel_id() {
+        return Ok(true);
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(t!(
+                locale(ctx).await,
+                hint_id,
+                channel = channel_id.mention().to_string()
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
+
+/// Get the ordering channel of the guild the command was run in
+pub async fn ordering_channel(ctx: Context<'_>) -> Option<ChannelId> {
+    let guild_id = ctx.guild_id()?;
+    ctx.data()
+        .settings
+        .get(&guild_id.to_string())
+        .await
+        .ok()?
+        .ordering_channel_id?
+        .parse::<u64>()
+        .ok()
+        .map(ChannelId::new)
+}
+
+/// Post a message in the ordering channel, so mentions end up where people
+/// order no matter where the oracle used the command
+pub async fn say_in_ordering_channel(
+    ctx: Context<'_>,
+    message: impl Into<String>,
+) -> Result<(), Error> {
+    match ordering_channel(ctx).await {
+        Some(channel_id) if channel_id != ctx.channel_id() => {
+            channel_id.say(ctx.http(), message).await?;
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(t!(
+                        locale(ctx).await,
+                        "channel-sent-to",
+                        channel = channel_id.mention().to_string()
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+        }
+        _ => {
+            ctx.say(message).await?;
+        }
+    }
+    Ok(())
+}
+
 async fn deny(ctx: Context<'_>) -> Result<(), Error> {
     // Send message to discord to prevent timeout.
     // Discord expects a response within 3 seconds. Just
     // returning false does not respond to the interaction.
     ctx.send(
         poise::CreateReply::default()
-            .content("❌ Du har ikke tilgang til denne kommandoen.")
+            .content(t!(locale(ctx).await, "access-denied"))
             .ephemeral(true),
     )
     .await?;
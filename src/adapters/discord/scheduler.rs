@@ -0,0 +1,392 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::{NaiveDate, TimeDelta, Utc};
+use chrono_tz::Tz;
+use serenity::all::{ChannelId, Context};
+use tracing::{error, info, instrument, warn};
+
+use crate::adapters::discord::i18n::t;
+use crate::adapters::discord::templates::Templates;
+use crate::adapters::discord::{announcements, audit};
+use crate::domain::session::{close_session, is_idle, open_session};
+use crate::domain::{
+    AuditAction, AuditEntry, EventBroadcaster, QueueEvent, Repositories, Schedule, ScheduleEvent,
+    SessionDeadline, SessionMode,
+};
+
+/// How often the schedules are checked
+const TICK: Duration = Duration::from_secs(30);
+
+/// How long after its time an event is still carried out
+const GRACE: TimeDelta = TimeDelta::minutes(5);
+
+/// Opens and closes the queues of the guilds following their schedules and
+/// deadlines, and closes the ones left idle
+pub struct Scheduler {
+    ctx: Context,
+    repositories: Repositories,
+    events: EventBroadcaster,
+    timezone: Tz,
+    /// Events already carried out today, so they only happen once
+    fired: HashSet<(i64, NaiveDate, ScheduleEvent)>,
+}
+
+impl Scheduler {
+    pub fn new(
+        ctx: Context,
+        repositories: Repositories,
+        events: EventBroadcaster,
+        timezone: Tz,
+    ) -> Self {
+        Self {
+            ctx,
+            repositories,
+            events,
+            timezone,
+            fired: HashSet::new(),
+        }
+    }
+
+    /// Run the scheduler in the background
+    pub fn spawn(self) {
+        tokio::spawn(self.run());
+    }
+
+    async fn run(mut self) {
+        self.restore_deadlines().await;
+
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            self.tick().await;
+        }
+    }
+
+    async fn tick(&mut self) {
+        self.run_schedules().await;
+        self.run_deadlines().await;
+        self.close_idle_sessions().await;
+    }
+
+    /// Reopen the sessions that still had a deadline when the bot stopped.
+    /// The queues themselves are kept in Redis, so only the session is lost.
+    #[instrument(skip(self))]
+    async fn restore_deadlines(&self) {
+        let deadlines = match self.repositories.deadlines.list_all().await {
+            Ok(deadlines) => deadlines,
+            Err(e) => {
+                error!(error = ?e, "Failed to fetch session deadlines");
+                return;
+            }
+        };
+
+        for deadline in deadlines {
+            if open_session(
+                self.repositories.queue.as_ref(),
+                &deadline.guild_id,
+                &deadline.channel_id,
+                deadline.mode,
+                deadline.capacity,
+            ) {
+                info!(guild_id = %deadline.guild_id, closes_at = %deadline.closes_at, "Restored timed session");
+                announcements::update_presence(&self.ctx, self.repositories.queue.as_ref());
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn run_deadlines(&self) {
+        let deadlines = match self.repositories.deadlines.list_all().await {
+            Ok(deadlines) => deadlines,
+            Err(e) => {
+                error!(error = ?e, "Failed to fetch session deadlines");
+                return;
+            }
+        };
+
+        let now = Utc::now();
+        for deadline in deadlines {
+            if deadline.is_due(now) {
+                self.close_at_deadline(&deadline).await;
+            } else if deadline.should_remind(now) {
+                if let Err(e) = self
+                    .repositories
+                    .deadlines
+                    .mark_reminded(&deadline.guild_id)
+                    .await
+                {
+                    error!(guild_id = %deadline.guild_id, error = ?e, "Failed to mark deadline as reminded");
+                    continue;
+                }
+                let templates = self.templates(&deadline.guild_id).await;
+                self.announce(
+                    &deadline.channel_id,
+                    announcements::closing_soon(&templates, deadline.closes_at),
+                )
+                .await;
+            }
+        }
+    }
+
+    async fn close_at_deadline(&self, deadline: &SessionDeadline) {
+        let Some(session) = close_session(
+            self.repositories.queue.as_ref(),
+            self.repositories.orders.as_ref(),
+            self.repositories.deadlines.as_ref(),
+            &deadline.guild_id,
+            &self.timezone,
+        )
+        .await
+        else {
+            return;
+        };
+
+        info!(guild_id = %deadline.guild_id, "Closed queue at deadline");
+        self.events.publish(&deadline.guild_id, QueueEvent::Closed);
+        let templates = self.templates(&deadline.guild_id).await;
+        self.audit(
+            &deadline.guild_id,
+            AuditAction::Close,
+            t!(templates.locale, "audit-close-deadline"),
+        )
+        .await;
+        self.announce(
+            &deadline.channel_id,
+            announcements::closed(&templates, &session),
+        )
+        .await;
+        announcements::update_presence(&self.ctx, self.repositories.queue.as_ref());
+    }
+
+    /// Close the queues nobody has baked from for a while, in case the
+    /// oracles forgot `/stopp`. Queues with a deadline close at the deadline.
+    #[instrument(skip(self))]
+    async fn close_idle_sessions(&self) {
+        let now = Utc::now();
+        let deadlines = match self.repositories.deadlines.list_all().await {
+            Ok(deadlines) => deadlines,
+            Err(e) => {
+                error!(error = ?e, "Failed to fetch session deadlines");
+                return;
+            }
+        };
+
+        for (guild_id, session) in self.repositories.queue.sessions() {
+            if deadlines
+                .iter()
+                .any(|deadline| deadline.guild_id == guild_id)
+            {
+                continue;
+            }
+
+            let settings = match self.repositories.settings.get(&guild_id).await {
+                Ok(settings) => settings,
+                Err(e) => {
+                    error!(guild_id = %guild_id, error = ?e, "Failed to fetch guild settings");
+                    continue;
+                }
+            };
+
+            let Some(minutes) = settings.idle_close_minutes else {
+                continue;
+            };
+            if !is_idle(&session, minutes, now) {
+                continue;
+            }
+
+            let Some(closed) = close_session(
+                self.repositories.queue.as_ref(),
+                self.repositories.orders.as_ref(),
+                self.repositories.deadlines.as_ref(),
+                &guild_id,
+                &self.timezone,
+            )
+            .await
+            else {
+                continue;
+            };
+
+            info!(guild_id = %guild_id, minutes, "Closed idle queue");
+            self.events.publish(&guild_id, QueueEvent::Closed);
+            self.audit(
+                &guild_id,
+                AuditAction::Close,
+                t!(settings.locale, "audit-close-idle", minutes = minutes),
+            )
+            .await;
+            match session.channel_id.as_deref() {
+                Some(channel_id) => {
+                    let templates = Templates::load(
+                        self.repositories.templates.as_ref(),
+                        &guild_id,
+                        settings.locale,
+                    )
+                    .await;
+                    self.announce(
+                        channel_id,
+                        announcements::closed_for_inactivity(&templates, &closed, minutes),
+                    )
+                    .await
+                }
+                None => warn!(guild_id = %guild_id, "Idle queue has no channel to announce in"),
+            }
+            announcements::update_presence(&self.ctx, self.repositories.queue.as_ref());
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn run_schedules(&mut self) {
+        let now = Utc::now().with_timezone(&self.timezone).naive_local();
+        let today = now.date();
+        self.fired.retain(|(_, date, _)| *date == today);
+
+        let schedules = match self.repositories.schedules.list_all().await {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                error!(error = ?e, "Failed to fetch schedules");
+                return;
+            }
+        };
+
+        for schedule in schedules {
+            let Some(event) = schedule.due_event(now, GRACE) else {
+                continue;
+            };
+            if !self.fired.insert((schedule.id, today, event)) {
+                continue;
+            }
+
+            match self
+                .repositories
+                .schedules
+                .is_exception(&schedule.guild_id, today)
+                .await
+            {
+                Ok(false) => {}
+                Ok(true) => {
+                    info!(guild_id = %schedule.guild_id, id = schedule.id, "Skipping schedule today");
+                    continue;
+                }
+                Err(e) => {
+                    error!(guild_id = %schedule.guild_id, error = ?e, "Failed to check schedule exception");
+                    continue;
+                }
+            }
+
+            match event {
+                ScheduleEvent::Open => self.open(&schedule).await,
+                ScheduleEvent::Close => self.close(&schedule).await,
+            }
+        }
+    }
+
+    async fn open(&self, schedule: &Schedule) {
+        if !open_session(
+            self.repositories.queue.as_ref(),
+            &schedule.guild_id,
+            &schedule.channel_id,
+            SessionMode::Queue,
+            None,
+        ) {
+            return;
+        }
+
+        info!(guild_id = %schedule.guild_id, id = schedule.id, "Opened queue from schedule");
+        self.events.publish(
+            &schedule.guild_id,
+            QueueEvent::Opened {
+                mode: SessionMode::Queue,
+                capacity: None,
+                closes_at: None,
+            },
+        );
+        let templates = self.templates(&schedule.guild_id).await;
+        self.audit(
+            &schedule.guild_id,
+            AuditAction::Open,
+            t!(templates.locale, "audit-open-schedule", id = schedule.id),
+        )
+        .await;
+        self.announce(
+            &schedule.channel_id,
+            announcements::opened(&templates, SessionMode::Queue, None, None),
+        )
+        .await;
+        announcements::update_presence(&self.ctx, self.repositories.queue.as_ref());
+    }
+
+    async fn close(&self, schedule: &Schedule) {
+        let Some(session) = close_session(
+            self.repositories.queue.as_ref(),
+            self.repositories.orders.as_ref(),
+            self.repositories.deadlines.as_ref(),
+            &schedule.guild_id,
+            &self.timezone,
+        )
+        .await
+        else {
+            return;
+        };
+
+        info!(guild_id = %schedule.guild_id, id = schedule.id, "Closed queue from schedule");
+        self.events.publish(&schedule.guild_id, QueueEvent::Closed);
+        let templates = self.templates(&schedule.guild_id).await;
+        self.audit(
+            &schedule.guild_id,
+            AuditAction::Close,
+            t!(templates.locale, "audit-close-schedule", id = schedule.id),
+        )
+        .await;
+        self.announce(
+            &schedule.channel_id,
+            announcements::closed(&templates, &session),
+        )
+        .await;
+        announcements::update_presence(&self.ctx, self.repositories.queue.as_ref());
+    }
+
+    /// Language and templates of a guild, for the messages posted there
+    async fn templates(&self, guild_id: &str) -> Templates {
+        let locale = self
+            .repositories
+            .settings
+            .get(guild_id)
+            .await
+            .map(|settings| settings.locale)
+            .unwrap_or_default();
+        Templates::load(self.repositories.templates.as_ref(), guild_id, locale).await
+    }
+
+    async fn audit(&self, guild_id: &str, action: AuditAction, details: String) {
+        let entry = AuditEntry {
+            guild_id: guild_id.to_string(),
+            actor_id: None,
+            action,
+            details,
+            created_at: Utc::now(),
+        };
+
+        audit::record(
+            &self.ctx.http,
+            self.repositories.audit.as_ref(),
+            self.repositories.settings.as_ref(),
+            entry,
+        )
+        .await;
+    }
+
+    async fn announce(&self, channel_id: &str, message: String) {
+        let Ok(channel_id) = channel_id.parse::<u64>() else {
+            warn!(channel_id, "Invalid channel id");
+            return;
+        };
+
+        if let Err(e) = ChannelId::new(channel_id)
+            .say(&self.ctx.http, message)
+            .await
+        {
+            error!(channel_id, error = ?e, "Failed to post announcement");
+        }
+    }
+}
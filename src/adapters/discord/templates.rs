@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use tracing::error;
+
+use crate::adapters::discord::Context;
+use crate::adapters::discord::i18n::guild_locale;
+use crate::domain::template::render;
+use crate::domain::{Locale, TemplateKind, TemplateRepository};
+
+/// The wording a guild uses for its messages.
+///
+/// Messages the guild has not changed come from the catalog of its locale.
+pub struct Templates {
+    pub locale: Locale,
+    overrides: HashMap<TemplateKind, String>,
+}
+
+impl Templates {
+    pub async fn load(templates: &dyn TemplateRepository, guild_id: &str, locale: Locale) -> Self {
+        let overrides = templates.list(guild_id).await.unwrap_or_else(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch message templates");
+            HashMap::new()
+        });
+
+        Self { locale, overrides }
+    }
+
+    /// Render a message with the template of the guild, or the standard
+    /// wording if it has none
+    pub fn render(
+        &self,
+        kind: TemplateKind,
+        values: &[(&str, &str)],
+        default: impl FnOnce() -> String,
+    ) -> String {
+        match self.overrides.get(&kind) {
+            Some(template) => render(template, values),
+            None => default(),
+        }
+    }
+}
+
+/// Templates of the guild the command was run in
+pub async fn guild_templates(ctx: Context<'_>) -> Templates {
+    let locale = guild_locale(ctx).await;
+    let Some(guild_id) = ctx.guild_id() else {
+        return Templates {
+            locale,
+            overrides: HashMap::new(),
+        };
+    };
+
+    Templates::load(ctx.data().templates.as_ref(), &guild_id.to_string(), locale).await
+}
@@ -0,0 +1,639 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use serenity::all::ChannelId;
+use tracing::error;
+
+use crate::adapters::discord::i18n::t;
+use crate::adapters::discord::templates::Templates;
+use crate::adapters::discord::{announcements, audit};
+use crate::adapters::http::{
+    AppState, LaneQuery,
+    error::{ApiError, ErrorResponse},
+};
+use crate::domain::queue::resolve_lane;
+use crate::domain::session::{BakeError, bake, close_session, open_session};
+use crate::domain::{
+    ApiToken, AuditAction, AuditEntry, DailyStats, GuildSettings, QueueEntry, QueueEvent,
+    SessionDeadline, SessionMode,
+};
+use crate::telemetry::LEAVES;
+
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenMode {
+    #[default]
+    Queue,
+    Lottery,
+}
+
+#[derive(serde::Deserialize, utoipa::ToSchema)]
+pub struct OpenRequest {
+    #[serde(default)]
+    mode: OpenMode,
+    /// Number of waffles there is batter for
+    capacity: Option<u32>,
+    /// When ordering closes by itself
+    closes_at: Option<DateTime<Utc>>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenResponse {
+    channel_id: String,
+    closes_at: Option<DateTime<Utc>>,
+}
+
+/// Open the queue, announcing it in the queue channel
+#[utoipa::path(
+    post,
+    path = "/{guild_id}/open",
+    tag = "admin",
+    responses(
+        (status = 200, body = OpenResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (
+            status = 409,
+            description = "The queue is already open, or the guild has no ordering channel",
+            body = ErrorResponse,
+        ),
+    ),
+    security(("api_token" = [])),
+)]
+pub async fn open(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+    Extension(token): Extension<ApiToken>,
+    Json(request): Json<OpenRequest>,
+) -> Result<Json<OpenResponse>, ApiError> {
+    if request.capacity == Some(0) {
+        return Err(ApiError::BadRequest(
+            "Capacity must be at least 1".to_string(),
+        ));
+    }
+    if request.closes_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(ApiError::BadRequest(
+            "closes_at must be in the future".to_string(),
+        ));
+    }
+
+    let settings = state.repositories.settings.get(&guild_id).await?;
+    // There is no channel the request came from, so it has to be set up
+    let channel_id = settings
+        .ordering_channel_id
+        .clone()
+        .ok_or_else(|| ApiError::Conflict("The guild has no ordering channel".to_string()))?;
+
+    let mode = match request.mode {
+        OpenMode::Queue => SessionMode::Queue,
+        OpenMode::Lottery => SessionMode::LotterySignup,
+    };
+
+    if !open_session(
+        state.repositories.queue.as_ref(),
+        &guild_id,
+        &channel_id,
+        mode,
+        request.capacity,
+    ) {
+        return Err(ApiError::Conflict("The queue is already open".to_string()));
+    }
+
+    if let Some(closes_at) = request.closes_at {
+        let deadline = SessionDeadline {
+            guild_id: guild_id.clone(),
+            channel_id: channel_id.clone(),
+            mode,
+            capacity: request.capacity,
+            closes_at,
+            reminded: false,
+        };
+        if let Err(e) = state.repositories.deadlines.set(&deadline).await {
+            error!(guild_id = %guild_id, error = ?e, "Failed to save session deadline");
+        }
+    }
+
+    state.events.publish(
+        &guild_id,
+        QueueEvent::Opened {
+            mode,
+            capacity: request.capacity,
+            closes_at: request.closes_at,
+        },
+    );
+
+    let templates = templates(&state, &guild_id, &settings).await;
+    announce(
+        &state,
+        Some(&channel_id),
+        announcements::opened(&templates, mode, request.capacity, request.closes_at),
+    )
+    .await;
+    record(
+        &state,
+        &token,
+        &settings,
+        AuditAction::Open,
+        audit::open_details(settings.locale, mode, request.capacity, request.closes_at),
+    )
+    .await;
+
+    Ok(Json(OpenResponse {
+        channel_id,
+        closes_at: request.closes_at,
+    }))
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct CloseResponse {
+    /// Stats for the day, or None if they could not be fetched
+    stats: Option<DailyStats>,
+}
+
+/// Close the queue, announcing the stats for the day
+#[utoipa::path(
+    post,
+    path = "/{guild_id}/close",
+    tag = "admin",
+    responses(
+        (status = 200, body = CloseResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 409, description = "The queue is already closed", body = ErrorResponse),
+    ),
+    security(("api_token" = [])),
+)]
+pub async fn close(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+    Extension(token): Extension<ApiToken>,
+) -> Result<Json<CloseResponse>, ApiError> {
+    let settings = state.repositories.settings.get(&guild_id).await?;
+    let channel_id = session_channel(&state, &guild_id, &settings);
+
+    let session = close_session(
+        state.repositories.queue.as_ref(),
+        state.repositories.orders.as_ref(),
+        state.repositories.deadlines.as_ref(),
+        &guild_id,
+        &state.timezone,
+    )
+    .await
+    .ok_or_else(|| ApiError::Conflict("The queue is already closed".to_string()))?;
+    state.events.publish(&guild_id, QueueEvent::Closed);
+
+    let templates = templates(&state, &guild_id, &settings).await;
+    announce(
+        &state,
+        channel_id.as_deref(),
+        announcements::closed(&templates, &session),
+    )
+    .await;
+
+    let baked = session.stats.as_ref().map_or(0, |stats| stats.total_orders);
+    record(
+        &state,
+        &token,
+        &settings,
+        AuditAction::Close,
+        t!(settings.locale, "audit-close-api", count = baked),
+    )
+    .await;
+
+    Ok(Json(CloseResponse {
+        stats: session.stats,
+    }))
+}
+
+#[derive(serde::Deserialize, utoipa::ToSchema)]
+pub struct BakeRequest {
+    amount: usize,
+    /// Lane to bake for, the default lane if empty
+    variant: Option<String>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct BakeResponse {
+    lane: String,
+    waffles: u32,
+    /// Who got waffles, with how many each got
+    baked: Vec<QueueEntry>,
+}
+
+/// Hand out a batch of waffles from the front of a lane
+#[utoipa::path(
+    post,
+    path = "/{guild_id}/bake",
+    tag = "admin",
+    responses(
+        (status = 200, body = BakeResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 409, body = ErrorResponse),
+    ),
+    security(("api_token" = [])),
+)]
+pub async fn bake_batch(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+    Extension(token): Extension<ApiToken>,
+    Json(request): Json<BakeRequest>,
+) -> Result<Json<BakeResponse>, ApiError> {
+    let settings = state.repositories.settings.get(&guild_id).await?;
+    let channel_id = session_channel(&state, &guild_id, &settings);
+
+    let batch = bake(
+        state.repositories.queue.as_ref(),
+        state.repositories.orders.as_ref(),
+        &settings,
+        &guild_id,
+        request.variant.as_deref(),
+        request.amount,
+    )
+    .await
+    .map_err(|e| match e {
+        BakeError::Closed => ApiError::Conflict("The queue is closed".to_string()),
+        BakeError::NotDrawn => ApiError::Conflict("The lottery has not been drawn yet".to_string()),
+        BakeError::UnknownVariant(variant) => {
+            ApiError::BadRequest(format!("Unknown variant: {variant}"))
+        }
+    })?;
+
+    state.events.publish(&guild_id, batch.event());
+
+    let templates = templates(&state, &guild_id, &settings).await;
+    announce(
+        &state,
+        channel_id.as_deref(),
+        announcements::baked(&templates, &batch),
+    )
+    .await;
+    record(
+        &state,
+        &token,
+        &settings,
+        AuditAction::Bake,
+        audit::bake_details(settings.locale, &batch),
+    )
+    .await;
+
+    Ok(Json(BakeResponse {
+        lane: batch.lane,
+        waffles: batch.waffles,
+        baked: batch.baked,
+    }))
+}
+
+/// Take a user out of a lane without serving them
+#[utoipa::path(
+    delete,
+    path = "/{guild_id}/queue/{user_id}",
+    tag = "admin",
+    params(LaneQuery),
+    responses(
+        (status = 204),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    ),
+    security(("api_token" = [])),
+)]
+pub async fn remove(
+    State(state): State<Arc<AppState>>,
+    Path((guild_id, user_id)): Path<(String, String)>,
+    Extension(token): Extension<ApiToken>,
+    Query(query): Query<LaneQuery>,
+) -> Result<StatusCode, ApiError> {
+    let settings = state.repositories.settings.get(&guild_id).await?;
+    let lane = lane(&settings, query.lane.as_deref())?;
+
+    if !state
+        .repositories
+        .queue
+        .remove(&guild_id, &lane, &user_id)
+        .await
+    {
+        return Err(ApiError::NotFound(format!(
+            "User {user_id} is not in {lane}"
+        )));
+    }
+    counter!(LEAVES, "guild_id" => guild_id.clone(), "lane" => lane.clone()).increment(1);
+    state.events.publish(
+        &guild_id,
+        QueueEvent::Left {
+            lane: lane.clone(),
+            user_id: user_id.clone(),
+        },
+    );
+
+    record(
+        &state,
+        &token,
+        &settings,
+        AuditAction::Remove,
+        t!(
+            settings.locale,
+            "audit-remove",
+            user = audit::mentions([user_id.as_str()]),
+            lane = lane.as_str()
+        ),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize, utoipa::ToSchema)]
+pub struct MoveRequest {
+    /// Place in the queue to move to, counting from 1
+    position: usize,
+    lane: Option<String>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct MoveResponse {
+    /// Place the user ended up at, counting from 1
+    position: usize,
+}
+
+/// Move a user to another place in a lane
+#[utoipa::path(
+    post,
+    path = "/{guild_id}/queue/{user_id}/move",
+    tag = "admin",
+    responses(
+        (status = 200, body = MoveResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    ),
+    security(("api_token" = [])),
+)]
+pub async fn move_to(
+    State(state): State<Arc<AppState>>,
+    Path((guild_id, user_id)): Path<(String, String)>,
+    Extension(token): Extension<ApiToken>,
+    Json(request): Json<MoveRequest>,
+) -> Result<Json<MoveResponse>, ApiError> {
+    let settings = state.repositories.settings.get(&guild_id).await?;
+    let lane = lane(&settings, request.lane.as_deref())?;
+
+    let position = state
+        .repositories
+        .queue
+        .move_to(&guild_id, &lane, &user_id, request.position)
+        .await
+        .ok_or_else(|| ApiError::NotFound(format!("User {user_id} is not in {lane}")))?;
+    state.events.publish(
+        &guild_id,
+        QueueEvent::Moved {
+            lane: lane.clone(),
+            user_id: user_id.clone(),
+            position,
+        },
+    );
+
+    record(
+        &state,
+        &token,
+        &settings,
+        AuditAction::Move,
+        t!(
+            settings.locale,
+            "audit-move",
+            user = audit::mentions([user_id.as_str()]),
+            position = position,
+            lane = lane.as_str()
+        ),
+    )
+    .await;
+
+    Ok(Json(MoveResponse { position }))
+}
+
+fn lane(settings: &GuildSettings, input: Option<&str>) -> Result<String, ApiError> {
+    resolve_lane(input, &settings.variants).ok_or_else(|| {
+        ApiError::BadRequest(format!("Unknown variant: {}", input.unwrap_or_default()))
+    })
+}
+
+/// Channel the session of a guild is announced in
+pub(super) fn session_channel(
+    state: &AppState,
+    guild_id: &str,
+    settings: &GuildSettings,
+) -> Option<String> {
+    state
+        .repositories
+        .queue
+        .session(guild_id)
+        .and_then(|session| session.channel_id)
+        .or_else(|| settings.ordering_channel_id.clone())
+}
+
+pub(super) async fn templates(
+    state: &AppState,
+    guild_id: &str,
+    settings: &GuildSettings,
+) -> Templates {
+    Templates::load(
+        state.repositories.templates.as_ref(),
+        guild_id,
+        settings.locale,
+    )
+    .await
+}
+
+/// Post a message in Discord, like the command would have.
+///
+/// The change is already made, so failing to post is only reported.
+pub(super) async fn announce(state: &AppState, channel_id: Option<&str>, message: String) {
+    let Some(channel_id) = channel_id.and_then(|id| id.parse::<u64>().ok()) else {
+        return;
+    };
+
+    if let Err(e) = ChannelId::new(channel_id)
+        .say(state.discord.as_ref(), message)
+        .await
+    {
+        error!(channel_id, error = ?e, "Failed to post announcement");
+    }
+}
+
+/// Log an action done with an API token, naming the token
+pub(super) async fn record(
+    state: &AppState,
+    token: &ApiToken,
+    settings: &GuildSettings,
+    action: AuditAction,
+    details: String,
+) {
+    let entry = AuditEntry {
+        guild_id: token.guild_id.clone(),
+        actor_id: None,
+        action,
+        details: format!(
+            "{details}, {}",
+            t!(settings.locale, "audit-api", label = token.label.as_str())
+        ),
+        created_at: Utc::now(),
+    };
+
+    audit::record(
+        state.discord.as_ref(),
+        state.repositories.audit.as_ref(),
+        state.repositories.settings.as_ref(),
+        entry,
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::adapters::http::router;
+    use crate::domain::{Repositories, api_token::hash_token, queue::DEFAULT_LANE};
+    use crate::infrastructure::in_memory;
+
+    const GUILD: &str = "1";
+    const TOKEN: &str = "secret";
+
+    async fn setup() -> Repositories {
+        let repositories = in_memory::repositories();
+        repositories
+            .api_tokens
+            .create(GUILD, "test", &hash_token(TOKEN))
+            .await
+            .unwrap();
+        repositories
+            .api_tokens
+            .create("2", "other", &hash_token("other"))
+            .await
+            .unwrap();
+        repositories
+    }
+
+    async fn send(
+        repositories: &Repositories,
+        method: &str,
+        uri: &str,
+        token: Option<&str>,
+        body: &str,
+    ) -> StatusCode {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {token}"));
+        }
+
+        router(AppState::for_tests(repositories.clone()))
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_requires_token_of_guild() {
+        let repositories = setup().await;
+        repositories.queue.open(GUILD, SessionMode::Queue);
+
+        let uri = "/1/close";
+        assert_eq!(
+            send(&repositories, "POST", uri, None, "").await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send(&repositories, "POST", uri, Some("wrong"), "").await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send(&repositories, "POST", uri, Some("other"), "").await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert!(repositories.queue.is_open(GUILD));
+
+        assert_eq!(
+            send(&repositories, "POST", uri, Some(TOKEN), "").await,
+            StatusCode::OK
+        );
+        assert!(!repositories.queue.is_open(GUILD));
+    }
+
+    #[tokio::test]
+    async fn test_close_when_closed() {
+        let repositories = setup().await;
+
+        assert_eq!(
+            send(&repositories, "POST", "/1/close", Some(TOKEN), "").await,
+            StatusCode::CONFLICT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_and_move_unknown_user() {
+        let repositories = setup().await;
+        repositories.queue.open(GUILD, SessionMode::Queue);
+        for user_id in ["a", "b"] {
+            repositories
+                .queue
+                .push(
+                    GUILD,
+                    DEFAULT_LANE,
+                    QueueEntry::new(user_id.to_string(), user_id.to_string()),
+                )
+                .await;
+        }
+
+        let position = r#"{"position": 1}"#;
+        assert_eq!(
+            send(
+                &repositories,
+                "POST",
+                "/1/queue/c/move",
+                Some(TOKEN),
+                position
+            )
+            .await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            send(&repositories, "DELETE", "/1/queue/c", Some(TOKEN), "").await,
+            StatusCode::NOT_FOUND
+        );
+
+        assert_eq!(
+            send(
+                &repositories,
+                "POST",
+                "/1/queue/b/move",
+                Some(TOKEN),
+                position
+            )
+            .await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(&repositories, "DELETE", "/1/queue/a", Some(TOKEN), "").await,
+            StatusCode::NO_CONTENT
+        );
+        let users: Vec<String> = repositories
+            .queue
+            .list(GUILD, DEFAULT_LANE)
+            .await
+            .into_iter()
+            .map(|entry| entry.user_id)
+            .collect();
+        assert_eq!(users, ["b"]);
+    }
+}
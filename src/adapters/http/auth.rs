@@ -0,0 +1,57 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Path, Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+};
+use tracing::{info, warn};
+
+use crate::adapters::http::{AppState, error::ApiError};
+use crate::domain::api_token::hash_token;
+
+/// Only let requests through that carry an API token of the guild in the path,
+/// as `Authorization: Bearer <token>`.
+///
+/// The token is added to the request extensions, so handlers can tell who did what.
+pub async fn require_token(
+    State(state): State<Arc<AppState>>,
+    Path(params): Path<HashMap<String, String>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let guild_id = params.get("guild_id").ok_or(ApiError::Unauthorized)?;
+
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(ApiError::Unauthorized)?;
+
+    let token = state
+        .repositories
+        .api_tokens
+        .find(&hash_token(token))
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+
+    if &token.guild_id != guild_id {
+        warn!(
+            guild_id,
+            token_id = token.id,
+            "API token used for another guild"
+        );
+        return Err(ApiError::Unauthorized);
+    }
+
+    info!(
+        guild_id,
+        token_id = token.id,
+        label = token.label,
+        "Authenticated API request"
+    );
+    request.extensions_mut().insert(token);
+    Ok(next.run(request).await)
+}
@@ -0,0 +1,56 @@
+use axum::{
+    Json,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+/// Body of every error response
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct ErrorResponse {
+    error: String,
+}
+
+/// Why a request failed, sent back as `{"error": "..."}`
+#[derive(Debug)]
+pub enum ApiError {
+    /// Missing, unknown or revoked token, or a token of another guild
+    Unauthorized,
+    BadRequest(String),
+    NotFound(String),
+    /// The queue is not in a state where this can be done
+    Conflict(String),
+    Internal,
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        error!(error = ?e, "Failed to handle API request");
+        ApiError::Internal
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                "Missing or invalid API token".to_string(),
+            ),
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::Conflict(message) => (StatusCode::CONFLICT, message),
+            ApiError::Internal => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Something went wrong".to_string(),
+            ),
+        };
+
+        let body = Json(ErrorResponse { error: message });
+        if status == StatusCode::UNAUTHORIZED {
+            (status, [(header::WWW_AUTHENTICATE, "Bearer")], body).into_response()
+        } else {
+            (status, body).into_response()
+        }
+    }
+}
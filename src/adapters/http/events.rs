@@ -0,0 +1,97 @@
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::{Stream, StreamExt, stream};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+use crate::adapters::http::AppState;
+use crate::domain::{LaneQueue, QueueEvent, SessionMode};
+
+/// Everything in the queue of a guild, sent first so clients know where
+/// the events start from
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct Snapshot {
+    open: bool,
+    mode: Option<SessionMode>,
+    lanes: Vec<LaneQueue>,
+}
+
+pub async fn snapshot(state: &AppState, guild_id: &str) -> Snapshot {
+    let queue = state.repositories.queue.as_ref();
+
+    let mut lanes = Vec::new();
+    for lane in queue.lanes(guild_id).await {
+        let entries = queue.list(guild_id, &lane).await;
+        lanes.push(LaneQueue { lane, entries });
+    }
+
+    Snapshot {
+        open: queue.is_open(guild_id),
+        mode: queue.mode(guild_id),
+        lanes,
+    }
+}
+
+async fn snapshot_event(state: &AppState, guild_id: &str) -> Event {
+    json_event("snapshot", &snapshot(state, guild_id).await)
+}
+
+fn json_event(name: &str, data: &impl serde::Serialize) -> Event {
+    Event::default()
+        .event(name)
+        .json_data(data)
+        .unwrap_or_else(|_| Event::default().event(name))
+}
+
+fn queue_event(event: &QueueEvent) -> Event {
+    json_event(event.name(), event)
+}
+
+/// Stream changes to the queue of a guild as server-sent events, starting
+/// with a snapshot.
+///
+/// A client that falls too far behind gets a new snapshot instead of the
+/// events it missed.
+#[utoipa::path(
+    get,
+    path = "/{guild_id}/queue/events",
+    tag = "queue",
+    responses((
+        status = 200,
+        description = "A `Snapshot`, then a `QueueEvent` named by its type whenever the queue changes",
+        content_type = "text/event-stream",
+        body = QueueEvent,
+    )),
+)]
+pub async fn queue_events(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Subscribe before the snapshot, so nothing happens between them unseen
+    let receiver = state.events.subscribe();
+    let first = snapshot_event(&state, &guild_id).await;
+
+    let events = stream::unfold(
+        (receiver, state, guild_id),
+        |(mut receiver, state, guild_id)| async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) if event.guild_id == guild_id => queue_event(&event.event),
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!(guild_id, missed, "Queue event stream fell behind");
+                        snapshot_event(&state, &guild_id).await
+                    }
+                    Err(RecvError::Closed) => return None,
+                };
+                return Some((Ok(event), (receiver, state, guild_id)));
+            }
+        },
+    );
+
+    Sse::new(stream::once(async { Ok(first) }).chain(events)).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+
+use crate::adapters::http::{
+    AppState,
+    error::{ApiError, ErrorResponse},
+};
+use crate::domain::ordering::{JoinOutcome, Order, join};
+use crate::domain::queue::guest_user_id;
+use crate::domain::{QueueEvent, Refusal};
+
+/// Longest name a guest can order with, so it fits on the display
+pub const MAX_GUEST_NAME_LENGTH: usize = 32;
+
+#[derive(serde::Deserialize, utoipa::ToSchema)]
+pub struct GuestOrder {
+    name: String,
+    amount: Option<u32>,
+    /// Options from the menu, separated by commas
+    toppings: Option<String>,
+    variant: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketState {
+    Queued,
+    Waitlisted,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct Ticket {
+    ticket: u32,
+    name: String,
+    lane: String,
+    state: TicketState,
+    /// Place in the lane or on the waitlist, counting from 1
+    position: usize,
+    /// Where the guest can follow the ticket
+    status_url: String,
+}
+
+/// Let a guest without Discord join the queue, handing them a ticket number
+#[utoipa::path(
+    post,
+    path = "/{guild_id}/queue",
+    tag = "guests",
+    responses(
+        (status = 201, body = Ticket),
+        (status = 400, body = ErrorResponse),
+        (status = 409, description = "The queue is closed or out of batter", body = ErrorResponse),
+    ),
+)]
+pub async fn join_queue(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+    Json(request): Json<GuestOrder>,
+) -> Result<(StatusCode, Json<Ticket>), ApiError> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_GUEST_NAME_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "The name must be between 1 and {MAX_GUEST_NAME_LENGTH} characters"
+        )));
+    }
+
+    let settings = state.repositories.settings.get(&guild_id).await?;
+    let queue = state.repositories.queue.as_ref();
+    // Checked here as well, so a closed queue does not use up a ticket
+    if !queue.is_open(&guild_id) {
+        return Err(ApiError::Conflict("The queue is closed".to_string()));
+    }
+
+    let ticket = queue.next_ticket(&guild_id).await;
+    if ticket == 0 {
+        return Err(ApiError::Internal);
+    }
+
+    let outcome = join(
+        queue,
+        state.repositories.orders.as_ref(),
+        state.repositories.products.as_ref(),
+        &settings,
+        &guild_id,
+        Order {
+            user_id: guest_user_id(ticket),
+            display_name: name.clone(),
+            amount: request.amount.unwrap_or(1),
+            toppings: request.toppings,
+            variant: request.variant,
+            ticket: Some(ticket),
+        },
+        &state.timezone,
+    )
+    .await?;
+
+    let (lane, ticket_state, position) = match outcome {
+        JoinOutcome::Joined {
+            lane,
+            entry,
+            position,
+            ..
+        } => {
+            state.events.publish(
+                &guild_id,
+                QueueEvent::Joined {
+                    lane: lane.clone(),
+                    entry,
+                    position,
+                },
+            );
+            (lane, TicketState::Queued, position)
+        }
+        JoinOutcome::Waitlisted { lane, position } => (lane, TicketState::Waitlisted, position),
+        other => return Err(refused(other)),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        Json(Ticket {
+            ticket,
+            name,
+            lane,
+            state: ticket_state,
+            position,
+            status_url: format!("/{guild_id}/tickets/{ticket}"),
+        }),
+    ))
+}
+
+/// Where a ticket is in the queue. Tickets that are served or removed are not found.
+#[utoipa::path(
+    get,
+    path = "/{guild_id}/tickets/{ticket}",
+    tag = "guests",
+    responses((status = 200, body = Ticket), (status = 404, body = ErrorResponse)),
+)]
+pub async fn ticket_status(
+    State(state): State<Arc<AppState>>,
+    Path((guild_id, ticket)): Path<(String, u32)>,
+) -> Result<Json<Ticket>, ApiError> {
+    let queue = state.repositories.queue.as_ref();
+    let user_id = guest_user_id(ticket);
+    let status_url = format!("/{guild_id}/tickets/{ticket}");
+
+    for lane in queue.lanes(&guild_id).await {
+        let entries = queue.list(&guild_id, &lane).await;
+        if let Some(index) = entries.iter().position(|entry| entry.user_id == user_id) {
+            return Ok(Json(Ticket {
+                ticket,
+                name: entries[index].display_name.clone(),
+                lane,
+                state: TicketState::Queued,
+                position: index + 1,
+                status_url,
+            }));
+        }
+    }
+
+    let waitlist = queue.waitlist(&guild_id).await;
+    if let Some(index) = waitlist
+        .iter()
+        .position(|waiting| waiting.entry.user_id == user_id)
+    {
+        return Ok(Json(Ticket {
+            ticket,
+            name: waitlist[index].entry.display_name.clone(),
+            lane: waitlist[index].lane.clone(),
+            state: TicketState::Waitlisted,
+            position: index + 1,
+            status_url,
+        }));
+    }
+
+    Err(ApiError::NotFound(format!(
+        "Ticket {ticket} is not in the queue"
+    )))
+}
+
+fn refused(outcome: JoinOutcome) -> ApiError {
+    match outcome {
+        JoinOutcome::UnknownVariant(variant) => {
+            ApiError::BadRequest(format!("Unknown variant: {variant}"))
+        }
+        JoinOutcome::UnknownOption(option) => {
+            ApiError::BadRequest(format!("Not on the menu: {option}"))
+        }
+        JoinOutcome::Refused(Refusal::TooManyInOrder { max }) => {
+            ApiError::BadRequest(format!("At most {max} waffles per order"))
+        }
+        JoinOutcome::Refused(Refusal::LimitReached { limit, .. }) => {
+            ApiError::BadRequest(format!("At most {limit} waffles per person"))
+        }
+        JoinOutcome::Closed => ApiError::Conflict("The queue is closed".to_string()),
+        JoinOutcome::AlreadyDrawn => {
+            ApiError::Conflict("The lottery has already been drawn".to_string())
+        }
+        JoinOutcome::OutOfBatter => ApiError::Conflict("There is no batter left".to_string()),
+        JoinOutcome::BatterLeft { remaining } => {
+            ApiError::Conflict(format!("There is only batter for {remaining} more waffles"))
+        }
+        // A new ticket is never in the queue already, and has not been served
+        _ => ApiError::Internal,
+    }
+}
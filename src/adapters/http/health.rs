@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use axum::{Json, extract::State, http::StatusCode};
+use futures_util::future::join_all;
+use tracing::warn;
+
+use crate::adapters::http::AppState;
+use crate::domain::HealthCheck;
+
+/// How long a dependency gets to answer before it counts as down
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct Liveness {
+    status: &'static str,
+}
+
+/// The process is up and serving requests
+#[utoipa::path(get, path = "/healthz", tag = "health", responses((status = 200, body = Liveness)))]
+pub async fn healthz() -> Json<Liveness> {
+    Json(Liveness { status: "ok" })
+}
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct DependencyStatus {
+    ok: bool,
+    latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct Readiness {
+    ready: bool,
+    dependencies: BTreeMap<&'static str, DependencyStatus>,
+}
+
+/// Redis, PostgreSQL and the Discord gateway all answer.
+/// Answers 503 if any of them do not.
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    responses((status = 200, body = Readiness), (status = 503, body = Readiness)),
+)]
+pub async fn readyz(State(state): State<Arc<AppState>>) -> (StatusCode, Json<Readiness>) {
+    let readiness = check_all(&state.health_checks).await;
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness))
+}
+
+async fn check_all(checks: &[Arc<dyn HealthCheck>]) -> Readiness {
+    let results = join_all(checks.iter().map(|check| async move {
+        let result = tokio::time::timeout(CHECK_TIMEOUT, check.check()).await;
+        let status = match result {
+            Ok(Ok(latency)) => DependencyStatus {
+                ok: true,
+                latency_ms: latency.as_secs_f64() * 1000.0,
+                error: None,
+            },
+            Ok(Err(e)) => DependencyStatus {
+                ok: false,
+                latency_ms: 0.0,
+                error: Some(e.to_string()),
+            },
+            Err(_) => DependencyStatus {
+                ok: false,
+                latency_ms: CHECK_TIMEOUT.as_secs_f64() * 1000.0,
+                error: Some("Timed out".to_string()),
+            },
+        };
+        if let Some(error) = &status.error {
+            warn!(dependency = check.name(), error, "Dependency is not ready");
+        }
+        (check.name(), status)
+    }))
+    .await;
+
+    Readiness {
+        ready: results.iter().all(|(_, status)| status.ok),
+        dependencies: results.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake {
+        name: &'static str,
+        up: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl HealthCheck for Fake {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn check(&self) -> anyhow::Result<Duration> {
+            if self.up {
+                Ok(Duration::from_millis(3))
+            } else {
+                anyhow::bail!("Connection refused")
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_not_ready_if_any_dependency_is_down() {
+        let checks: Vec<Arc<dyn HealthCheck>> = vec![
+            Arc::new(Fake {
+                name: "redis",
+                up: true,
+            }),
+            Arc::new(Fake {
+                name: "postgres",
+                up: false,
+            }),
+        ];
+
+        let readiness = check_all(&checks).await;
+        assert!(!readiness.ready);
+        assert!(readiness.dependencies["redis"].ok);
+        assert_eq!(readiness.dependencies["redis"].latency_ms, 3.0);
+        assert_eq!(
+            readiness.dependencies["postgres"].error.as_deref(),
+            Some("Connection refused")
+        );
+
+        assert!(check_all(&checks[..1]).await.ready);
+    }
+}
@@ -1,55 +1,299 @@
+mod admin;
+mod auth;
+mod error;
+mod events;
+mod guest;
+mod health;
+mod openapi;
+mod prometheus;
+mod stats;
+mod ws;
+
+use anyhow::Context;
 use axum::{
     Json, Router,
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{HeaderValue, Method, header},
+    middleware,
+    response::Html,
     routing::get,
 };
+use chrono::Utc;
+use chrono_tz::Tz;
+use metrics_exporter_prometheus::PrometheusHandle;
+use serenity::all::Http;
+use tokio::net::TcpListener;
 use tower::ServiceBuilder;
-use tower_http::trace::TraceLayer;
-use tracing::info;
+use tower_http::{
+    cors::{AllowOrigin, CorsLayer},
+    trace::TraceLayer,
+};
+use tracing::{error, info};
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use std::{
+    collections::HashSet,
+    io,
+    sync::{Arc, Mutex},
+};
 
-use std::{io, sync::Arc};
+use crate::config::HttpConfig;
+use crate::domain::{
+    CapacityStatus, EventBroadcaster, HealthCheck, Locale, QueueEntry, Repositories,
+    capacity::capacity_status, order::local_day_start, queue::DEFAULT_LANE,
+    session::time_per_waffle,
+};
 
-use crate::domain::{OrderRepository, QueueEntry, QueueRepository};
+/// Page for a screen by the waffle iron, reading the guild from its own path.
+/// Compiled in, so it is found wherever the bot is started from.
+const DISPLAY_PAGE: &str = include_str!("../../../static/display.html");
 
 #[derive(Clone)]
 pub struct AppState {
-    queue: Arc<dyn QueueRepository>,
-    #[allow(dead_code)]
-    orders: Arc<dyn OrderRepository>,
+    repositories: Repositories,
+    events: EventBroadcaster,
+    /// Used to announce in Discord what is done through the API
+    discord: Arc<Http>,
+    /// What has to answer for the bot to be ready
+    health_checks: Vec<Arc<dyn HealthCheck>>,
+    metrics: PrometheusHandle,
+    /// Time zone days and weeks in the stats and limits follow
+    timezone: Tz,
+    /// Guilds with queue gauges, so they can be zeroed when the queue closes
+    reported_guilds: Arc<Mutex<HashSet<String>>>,
+}
+
+#[cfg(test)]
+impl AppState {
+    /// State for handler tests, with Discord unreachable and nothing to check
+    fn for_tests(repositories: Repositories) -> Arc<Self> {
+        Arc::new(Self {
+            repositories,
+            events: EventBroadcaster::new(),
+            discord: Arc::new(Http::new("")),
+            health_checks: vec![],
+            metrics: metrics_exporter_prometheus::PrometheusBuilder::new()
+                .build_recorder()
+                .handle(),
+            timezone: chrono_tz::Europe::Oslo,
+            reported_guilds: Arc::default(),
+        })
+    }
 }
 
 pub struct HttpAdapter {
-    queue: Arc<dyn QueueRepository>,
-    orders: Arc<dyn OrderRepository>,
+    repositories: Repositories,
+    events: EventBroadcaster,
+    discord: Arc<Http>,
+    health_checks: Vec<Arc<dyn HealthCheck>>,
+    metrics: PrometheusHandle,
+    timezone: Tz,
+    config: HttpConfig,
 }
 
 impl HttpAdapter {
-    pub fn new(queue: Arc<dyn QueueRepository>, orders: Arc<dyn OrderRepository>) -> Self {
-        Self { queue, orders }
+    pub fn new(
+        repositories: Repositories,
+        events: EventBroadcaster,
+        discord: Arc<Http>,
+        health_checks: Vec<Arc<dyn HealthCheck>>,
+        metrics: PrometheusHandle,
+        timezone: Tz,
+        config: HttpConfig,
+    ) -> Self {
+        Self {
+            repositories,
+            events,
+            discord,
+            health_checks,
+            metrics,
+            timezone,
+            config,
+        }
     }
 
-    pub async fn start(&self) -> Result<(), io::Error> {
+    /// Listen on the configured address, failing if it is taken or not allowed
+    pub async fn bind(&self) -> anyhow::Result<TcpListener> {
+        let addr = self.config.addr;
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind the HTTP server to {addr}"))?;
+        info!("listening on http://{}", listener.local_addr()?);
+        Ok(listener)
+    }
+
+    pub async fn serve(&self, listener: TcpListener) -> Result<(), io::Error> {
         let state = Arc::new(AppState {
-            queue: self.queue.clone(),
-            orders: self.orders.clone(),
+            repositories: self.repositories.clone(),
+            events: self.events.clone(),
+            discord: self.discord.clone(),
+            health_checks: self.health_checks.clone(),
+            metrics: self.metrics.clone(),
+            timezone: self.timezone,
+            reported_guilds: Arc::default(),
         });
 
-        let app = Router::new()
-            .route("/{guild_id}/queue", get(list_queue))
-            .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()))
-            .with_state(state);
-
-        let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-        info!("listening on http://{}", listener.local_addr().unwrap());
+        let app = router(state).layer(
+            ServiceBuilder::new()
+                .layer(TraceLayer::new_for_http())
+                .option_layer(cors(&self.config.cors_origins)),
+        );
 
         axum::serve(listener, app).await
     }
 }
 
+/// Every endpoint, without the layers that only matter when serving
+fn router(state: Arc<AppState>) -> Router {
+    let (api, spec) = api(state.clone()).split_for_parts();
+
+    api.route(
+        "/openapi.json",
+        get({
+            let spec = spec.clone();
+            move || async move { Json(spec) }
+        }),
+    )
+    .route("/{guild_id}/ws", get(ws::queue_socket))
+    .route("/{guild_id}/display", get(|| async { Html(DISPLAY_PAGE) }))
+    .merge(Scalar::with_url("/docs", spec))
+    .with_state(state)
+}
+
+/// The endpoints in the OpenAPI document, which is made from the same routes
+/// they are served by
+fn api(state: Arc<AppState>) -> OpenApiRouter<Arc<AppState>> {
+    // Everything that changes the queue needs an API token of the guild
+    let admin = OpenApiRouter::new()
+        .routes(routes!(admin::open))
+        .routes(routes!(admin::close))
+        .routes(routes!(admin::bake_batch))
+        .routes(routes!(admin::remove))
+        .routes(routes!(admin::move_to))
+        .routes(routes!(stats::stats))
+        .routes(routes!(stats::orders_csv))
+        .route_layer(middleware::from_fn_with_state(state, auth::require_token));
+
+    OpenApiRouter::with_openapi(openapi::ApiDoc::openapi())
+        .routes(routes!(health::healthz))
+        .routes(routes!(health::readyz))
+        .routes(routes!(prometheus::metrics))
+        .routes(routes!(list_queue, guest::join_queue))
+        .routes(routes!(guest::ticket_status))
+        .routes(routes!(events::queue_events))
+        .routes(routes!(queue_status))
+        .merge(admin)
+}
+
+/// Lets browsers on the allowed origins call the API, or None to send no
+/// CORS headers at all
+fn cors(origins: &[HeaderValue]) -> Option<CorsLayer> {
+    if origins.is_empty() {
+        return None;
+    }
+
+    let allow_origin = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(origins.iter().cloned())
+    };
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST, Method::DELETE])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]),
+    )
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LaneQuery {
+    lane: Option<String>,
+}
+
+/// The queue of a lane
+#[utoipa::path(
+    get,
+    path = "/{guild_id}/queue",
+    tag = "queue",
+    params(LaneQuery),
+    responses((status = 200, body = Vec<QueueEntry>)),
+)]
 async fn list_queue(
     State(state): State<Arc<AppState>>,
     Path(guild_id): Path<String>,
+    Query(query): Query<LaneQuery>,
 ) -> Json<Vec<QueueEntry>> {
-    let queue = state.queue.list(&guild_id).await;
+    let lane = query.lane.as_deref().unwrap_or(DEFAULT_LANE);
+    let queue = state.repositories.queue.list(&guild_id, lane).await;
     Json(queue)
 }
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+struct QueueStatus {
+    open: bool,
+    capacity: Option<CapacityStatus>,
+    /// Waffles that can still be ordered, if the session has a capacity
+    remaining_capacity: Option<u32>,
+    waitlist: usize,
+    /// Waffles baked in the guild today
+    baked_today: i64,
+    /// Average seconds each waffle has taken this session, to estimate waits
+    seconds_per_waffle: Option<i64>,
+    /// Language of the guild
+    locale: &'static str,
+}
+
+/// Whether the queue is open, its capacity, waitlist and pace
+#[utoipa::path(
+    get,
+    path = "/{guild_id}/status",
+    tag = "queue",
+    responses((status = 200, body = QueueStatus)),
+)]
+async fn queue_status(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+) -> Json<QueueStatus> {
+    let queue = state.repositories.queue.as_ref();
+    let capacity = capacity_status(queue, &guild_id).await;
+
+    let day_start = local_day_start(Utc::now(), &state.timezone);
+    let baked_today = match state
+        .repositories
+        .orders
+        .daily_stats(&guild_id, day_start)
+        .await
+    {
+        Ok(stats) => stats.total_orders,
+        Err(e) => {
+            error!(guild_id, error = ?e, "Failed to fetch daily stats");
+            0
+        }
+    };
+    let seconds_per_waffle = match queue.session(&guild_id) {
+        Some(session) => time_per_waffle(&session, queue.served_count(&guild_id).await)
+            .map(|time| time.num_seconds()),
+        None => None,
+    };
+    let locale = match state.repositories.settings.get(&guild_id).await {
+        Ok(settings) => settings.locale,
+        Err(e) => {
+            error!(guild_id, error = ?e, "Failed to fetch guild settings");
+            Locale::default()
+        }
+    };
+
+    Json(QueueStatus {
+        open: queue.is_open(&guild_id),
+        capacity,
+        remaining_capacity: capacity.map(|status| status.remaining()),
+        waitlist: queue.waitlist(&guild_id).await.len(),
+        baked_today,
+        seconds_per_waffle,
+        locale: locale.as_str(),
+    })
+}
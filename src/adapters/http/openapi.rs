@@ -0,0 +1,249 @@
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+use crate::adapters::http::{error::ErrorResponse, events};
+use crate::domain::QueueEvent;
+
+/// What the document says besides the paths, which come from the router
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Vaffelbot", description = "Waffle queue of a Discord guild"),
+    components(schemas(QueueEvent, events::Snapshot, ErrorResponse)),
+    modifiers(&ApiToken),
+    tags(
+        (name = "queue", description = "What is in the queue, for anyone"),
+        (name = "guests", description = "Ordering without Discord"),
+        (name = "admin", description = "Running the queue, with an API token"),
+        (name = "stats", description = "Orders over time, with an API token"),
+        (name = "health", description = "For orchestrators and monitoring"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Adds the bearer token the admin endpoints are guarded by
+struct ApiToken;
+
+impl Modify for ApiToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_token",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("A token of the guild made with `/api ny`"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{Body, to_bytes},
+        http::{Request, StatusCode},
+    };
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::adapters::http::{AppState, api, router};
+    use crate::domain::{GuildSettings, Repositories, api_token::hash_token};
+    use crate::infrastructure::in_memory;
+
+    /// The committed spec, regenerated with `UPDATE_OPENAPI=1 cargo test openapi`
+    const SPEC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    #[test]
+    fn test_openapi_matches_committed_spec() {
+        let generated = api(AppState::for_tests(in_memory::repositories()))
+            .into_openapi()
+            .to_pretty_json()
+            .unwrap()
+            + "\n";
+
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(SPEC, &generated).unwrap();
+            return;
+        }
+
+        let committed = std::fs::read_to_string(SPEC).unwrap();
+        assert!(
+            committed == generated,
+            "openapi.json is out of date, run `UPDATE_OPENAPI=1 cargo test openapi` and commit the result"
+        );
+    }
+
+    /// Whether `value` fits `schema`, following references into `spec`.
+    ///
+    /// Only the parts of JSON Schema utoipa generates are understood, and
+    /// objects may not have properties the schema does not name.
+    fn check(spec: &Value, schema: &Value, value: &Value, at: &str) -> Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            return check(spec, &spec["components"]["schemas"][name], value, at);
+        }
+        if let Some(options) = schema["oneOf"].as_array() {
+            return match options.iter().any(|s| check(spec, s, value, at).is_ok()) {
+                true => Ok(()),
+                false => Err(format!("{at}: {value} is none of the options")),
+            };
+        }
+
+        let kind = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(n) if n.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        let fits =
+            |expected: &Value| expected == kind || (expected == "number" && kind == "integer");
+        let types_fit = match &schema["type"] {
+            Value::Null => true,
+            Value::Array(types) => types.iter().any(fits),
+            expected => fits(expected),
+        };
+        if !types_fit {
+            return Err(format!("{at}: {kind} is not {}", schema["type"]));
+        }
+        if let Some(allowed) = schema["enum"].as_array()
+            && !allowed.contains(value)
+        {
+            return Err(format!("{at}: {value} is not one of {allowed:?}"));
+        }
+
+        match value {
+            Value::Object(object) => {
+                for required in schema["required"].as_array().into_iter().flatten() {
+                    if !object.contains_key(required.as_str().unwrap()) {
+                        return Err(format!("{at}: {required} is missing"));
+                    }
+                }
+                for (key, field) in object {
+                    let at = format!("{at}.{key}");
+                    match (&schema["properties"][key], &schema["additionalProperties"]) {
+                        (Value::Null, Value::Null) => {
+                            return Err(format!("{at} is not in the spec"));
+                        }
+                        (Value::Null, additional) => check(spec, additional, field, &at)?,
+                        (property, _) => check(spec, property, field, &at)?,
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let schema = match schema["prefixItems"].get(i) {
+                        Some(schema) => schema,
+                        None => &schema["items"],
+                    };
+                    check(spec, schema, item, &format!("{at}[{i}]"))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Send a request and check the JSON that comes back against what the spec
+    /// says `path` answers with that status
+    async fn assert_documented(
+        repositories: &Repositories,
+        spec: &Value,
+        (method, path, uri): (&str, &str, &str),
+        body: Value,
+        status: StatusCode,
+    ) -> Value {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", "Bearer secret")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router(AppState::for_tests(repositories.clone()))
+            .oneshot(request)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), status, "{method} {uri}");
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+        let schema = &spec["paths"][path][method.to_lowercase()]["responses"][status.as_str()]["content"]
+            ["application/json"]["schema"];
+        assert!(!schema.is_null(), "{method} {path} documents no {status}");
+        if let Err(e) = check(spec, schema, &value, "body") {
+            panic!("{method} {uri} answered what the spec does not say: {e}");
+        }
+        value
+    }
+
+    #[tokio::test]
+    async fn test_responses_match_spec() {
+        let repositories = in_memory::repositories();
+        let spec =
+            serde_json::to_value(api(AppState::for_tests(repositories.clone())).into_openapi())
+                .unwrap();
+        repositories
+            .api_tokens
+            .create("1", "test", &hash_token("secret"))
+            .await
+            .unwrap();
+        // Not a channel id, so nothing is posted to Discord
+        let settings = GuildSettings {
+            ordering_channel_id: Some("bestilling".to_string()),
+            ..Default::default()
+        };
+        repositories.settings.save("1", &settings).await.unwrap();
+
+        let ok = StatusCode::OK;
+        let get = |path| ("GET", path, path);
+        assert_documented(&repositories, &spec, get("/healthz"), json!(null), ok).await;
+        assert_documented(&repositories, &spec, get("/readyz"), json!(null), ok).await;
+
+        let open = ("POST", "/{guild_id}/open", "/1/open");
+        let request = json!({"capacity": 10});
+        assert_documented(&repositories, &spec, open, request.clone(), ok).await;
+        let conflict = StatusCode::CONFLICT;
+        assert_documented(&repositories, &spec, open, request, conflict).await;
+
+        let join = ("POST", "/{guild_id}/queue", "/1/queue");
+        for name in ["Kari", "Ola"] {
+            let order = json!({"name": name});
+            assert_documented(&repositories, &spec, join, order, StatusCode::CREATED).await;
+        }
+        let ticket = ("GET", "/{guild_id}/tickets/{ticket}", "/1/tickets/2");
+        assert_documented(&repositories, &spec, ticket, json!(null), ok).await;
+        let queue = ("GET", "/{guild_id}/queue", "/1/queue");
+        let entries = assert_documented(&repositories, &spec, queue, json!(null), ok).await;
+        let status = ("GET", "/{guild_id}/status", "/1/status");
+        assert_documented(&repositories, &spec, status, json!(null), ok).await;
+
+        let user_id = entries[1]["user_id"].as_str().unwrap();
+        let uri = format!("/1/queue/{user_id}/move");
+        let move_to = ("POST", "/{guild_id}/queue/{user_id}/move", uri.as_str());
+        assert_documented(&repositories, &spec, move_to, json!({"position": 1}), ok).await;
+
+        let bake = ("POST", "/{guild_id}/bake", "/1/bake");
+        assert_documented(&repositories, &spec, bake, json!({"amount": 1}), ok).await;
+        let stats = ("GET", "/{guild_id}/stats", "/1/stats");
+        assert_documented(&repositories, &spec, stats, json!(null), ok).await;
+
+        let close = ("POST", "/{guild_id}/close", "/1/close");
+        assert_documented(&repositories, &spec, close, json!(null), ok).await;
+        assert_documented(&repositories, &spec, close, json!(null), conflict).await;
+        let ticket = ("GET", "/{guild_id}/tickets/{ticket}", "/1/tickets/2");
+        assert_documented(
+            &repositories,
+            &spec,
+            ticket,
+            json!(null),
+            StatusCode::NOT_FOUND,
+        )
+        .await;
+    }
+}
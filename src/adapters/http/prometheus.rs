@@ -0,0 +1,51 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{extract::State, http::header, response::IntoResponse};
+use metrics::gauge;
+
+use crate::adapters::http::AppState;
+use crate::telemetry::{QUEUE_LENGTH, QUEUE_OPEN};
+
+/// Every metric in the Prometheus text format
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    responses((status = 200, content_type = "text/plain", body = String)),
+)]
+pub async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    update_queue_gauges(&state).await;
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+/// Set the queue gauges from the queues as they are now.
+///
+/// Guilds that have closed since the last scrape are set to zero, so their
+/// gauges do not keep the last value they had while open.
+async fn update_queue_gauges(state: &AppState) {
+    let queue = state.repositories.queue.as_ref();
+
+    let mut open = Vec::new();
+    for (guild_id, _) in queue.sessions() {
+        let mut length = 0;
+        for lane in queue.lanes(&guild_id).await {
+            length += queue.size(&guild_id, &lane).await;
+        }
+        open.push((guild_id, length));
+    }
+
+    let mut reported = state.reported_guilds.lock().unwrap();
+    let now_open: HashSet<String> = open.iter().map(|(guild_id, _)| guild_id.clone()).collect();
+    for guild_id in reported.difference(&now_open) {
+        gauge!(QUEUE_OPEN, "guild_id" => guild_id.clone()).set(0.0);
+        gauge!(QUEUE_LENGTH, "guild_id" => guild_id.clone()).set(0.0);
+    }
+    for (guild_id, length) in open {
+        gauge!(QUEUE_OPEN, "guild_id" => guild_id.clone()).set(1.0);
+        gauge!(QUEUE_LENGTH, "guild_id" => guild_id).set(length as f64);
+    }
+    *reported = now_open;
+}
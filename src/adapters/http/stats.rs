@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use chrono::{Days, NaiveDate, Utc};
+
+use crate::adapters::http::{
+    AppState,
+    error::{ApiError, ErrorResponse},
+};
+use crate::domain::limits::local_midnight;
+use crate::domain::order::{local_day_start, local_week_start};
+use crate::domain::{DailyStats, OrderRecord, PeriodStats};
+
+/// Users listed in each period of the stats
+const TOP_USERS: i64 = 10;
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct Stats {
+    today: PeriodStats,
+    /// Since Monday
+    week: PeriodStats,
+    all_time: PeriodStats,
+    /// (lane, count) for today
+    lanes_today: Vec<(String, i64)>,
+}
+
+/// Waffles today, this week and ever, with the top users
+#[utoipa::path(
+    get,
+    path = "/{guild_id}/stats",
+    tag = "stats",
+    responses((status = 200, body = Stats), (status = 401, body = ErrorResponse)),
+    security(("api_token" = [])),
+)]
+pub async fn stats(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+) -> Result<Json<Stats>, ApiError> {
+    let orders = state.repositories.orders.as_ref();
+    let now = Utc::now();
+    let day_start = local_day_start(now, &state.timezone);
+
+    let today = orders
+        .stats_since(&guild_id, Some(day_start), TOP_USERS)
+        .await?;
+    let week = orders
+        .stats_since(
+            &guild_id,
+            Some(local_week_start(now, &state.timezone)),
+            TOP_USERS,
+        )
+        .await?;
+    let all_time = orders.stats_since(&guild_id, None, TOP_USERS).await?;
+    let DailyStats { lanes, .. } = orders.daily_stats(&guild_id, day_start).await?;
+
+    Ok(Json(Stats {
+        today,
+        week,
+        all_time,
+        lanes_today: lanes,
+    }))
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DateRange {
+    /// First day to include
+    from: Option<NaiveDate>,
+    /// Last day to include
+    to: Option<NaiveDate>,
+}
+
+/// Every order in the date range as CSV, one row per user and batch
+#[utoipa::path(
+    get,
+    path = "/{guild_id}/orders.csv",
+    tag = "stats",
+    params(DateRange),
+    responses(
+        (
+            status = 200,
+            description = "Columns id, fulfilled_at, user_id, lane and quantity",
+            content_type = "text/csv",
+            body = String,
+        ),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+    ),
+    security(("api_token" = [])),
+)]
+pub async fn orders_csv(
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+    Query(range): Query<DateRange>,
+) -> Result<impl IntoResponse, ApiError> {
+    if let (Some(from), Some(to)) = (range.from, range.to)
+        && from > to
+    {
+        return Err(ApiError::BadRequest(
+            "from must not be after to".to_string(),
+        ));
+    }
+
+    let midnight = |date: NaiveDate| local_midnight(date, &state.timezone);
+    let from = range.from.and_then(midnight);
+    let to = range
+        .to
+        .and_then(|to| to.checked_add_days(Days::new(1)))
+        .and_then(midnight);
+    let orders = state
+        .repositories
+        .orders
+        .list_orders(&guild_id, from, to)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"orders.csv\"",
+            ),
+        ],
+        to_csv(&orders),
+    ))
+}
+
+fn to_csv(orders: &[OrderRecord]) -> String {
+    let mut csv = String::from("id,fulfilled_at,user_id,lane,quantity\r\n");
+    for order in orders {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\r\n",
+            order.id,
+            order.fulfilled_at.to_rfc3339(),
+            csv_field(&order.user_id),
+            csv_field(&order.lane),
+            order.quantity
+        ));
+    }
+    csv
+}
+
+/// Quote a field if it holds anything that would break the row
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_csv() {
+        let fulfilled_at = "2026-10-19T12:00:00Z".parse().unwrap();
+        let orders = vec![
+            OrderRecord {
+                id: 1,
+                user_id: "123".to_string(),
+                lane: "standard".to_string(),
+                quantity: 2,
+                fulfilled_at,
+            },
+            OrderRecord {
+                id: 2,
+                user_id: "guest:4".to_string(),
+                lane: "glutenfri, \"ekte\"".to_string(),
+                quantity: 1,
+                fulfilled_at,
+            },
+        ];
+
+        assert_eq!(
+            to_csv(&orders),
+            "id,fulfilled_at,user_id,lane,quantity\r\n\
+             1,2026-10-19T12:00:00+00:00,123,standard,2\r\n\
+             2,2026-10-19T12:00:00+00:00,guest:4,\"glutenfri, \"\"ekte\"\"\",1\r\n"
+        );
+    }
+}
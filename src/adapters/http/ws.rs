@@ -0,0 +1,551 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::Response,
+};
+use metrics::counter;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::adapters::discord::announcements;
+use crate::adapters::discord::audit;
+use crate::adapters::discord::i18n::t;
+use crate::adapters::http::{
+    AppState,
+    admin::{announce, record, session_channel, templates},
+    events::{Snapshot, snapshot},
+    guest::MAX_GUEST_NAME_LENGTH,
+};
+use crate::domain::api_token::hash_token;
+use crate::domain::ordering::{JoinOutcome, Order, join};
+use crate::domain::queue::resolve_lane;
+use crate::domain::session::{BakeError, bake};
+use crate::domain::{ApiToken, AuditAction, QueueEntry, QueueEvent, Refusal};
+use crate::telemetry::LEAVES;
+
+/// Version of the messages sent over the socket. Every frame carries it as `v`,
+/// and frames of another version are refused.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What a client can ask for
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Use an API token of the guild, needed for everything below
+    Auth { token: String },
+    Join {
+        user_id: String,
+        display_name: String,
+        amount: Option<u32>,
+        /// Options from the menu, separated by commas
+        toppings: Option<String>,
+        variant: Option<String>,
+    },
+    Leave {
+        user_id: String,
+        variant: Option<String>,
+    },
+    Bake {
+        amount: usize,
+        variant: Option<String>,
+    },
+}
+
+/// What the server sends, either on its own or as the answer to a client message
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    /// Sent first, and again if the client fell too far behind on events
+    Snapshot(Snapshot),
+    Event {
+        event: QueueEvent,
+    },
+    Authenticated {
+        label: String,
+    },
+    Joined {
+        lane: String,
+        position: usize,
+    },
+    Waitlisted {
+        lane: String,
+        position: usize,
+    },
+    Left {
+        lane: String,
+    },
+    Baked {
+        lane: String,
+        waffles: u32,
+        baked: Vec<QueueEntry>,
+    },
+    Error {
+        code: &'static str,
+        message: String,
+    },
+}
+
+impl ServerMessage {
+    fn error(code: &'static str, message: impl Into<String>) -> Self {
+        ServerMessage::Error {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ServerFrame {
+    v: u32,
+    /// The `id` of the client message this answers, if it had one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<serde_json::Value>,
+    #[serde(flatten)]
+    message: ServerMessage,
+}
+
+pub async fn queue_socket(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    Path(guild_id): Path<String>,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, state, guild_id))
+}
+
+async fn send(
+    socket: &mut WebSocket,
+    id: Option<serde_json::Value>,
+    message: ServerMessage,
+) -> Result<(), axum::Error> {
+    let frame = ServerFrame {
+        v: PROTOCOL_VERSION,
+        id,
+        message,
+    };
+    let text = serde_json::to_string(&frame).map_err(axum::Error::new)?;
+    socket.send(Message::Text(text.into())).await
+}
+
+/// Stream the queue of a guild to the client and carry out what it asks for
+/// until either side hangs up
+async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>, guild_id: String) {
+    // Subscribe before the snapshot, so nothing happens between them unseen
+    let mut events = state.events.subscribe();
+    let mut token: Option<ApiToken> = None;
+
+    let first = ServerMessage::Snapshot(snapshot(&state, &guild_id).await);
+    if send(&mut socket, None, first).await.is_err() {
+        return;
+    }
+
+    loop {
+        let sent = tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let (id, reply) = handle_text(&state, &guild_id, &mut token, &text).await;
+                    send(&mut socket, id, reply).await
+                }
+                Some(Ok(Message::Binary(_))) => {
+                    let reply = ServerMessage::error("invalid_message", "Messages must be JSON text");
+                    send(&mut socket, None, reply).await
+                }
+                // Pings are answered by axum
+                Some(Ok(Message::Ping(_) | Message::Pong(_))) => Ok(()),
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+            },
+            event = events.recv() => match event {
+                Ok(event) if event.guild_id == guild_id => {
+                    send(&mut socket, None, ServerMessage::Event { event: event.event }).await
+                }
+                Ok(_) => Ok(()),
+                Err(RecvError::Lagged(missed)) => {
+                    warn!(guild_id, missed, "Queue socket fell behind");
+                    let snapshot = ServerMessage::Snapshot(snapshot(&state, &guild_id).await);
+                    send(&mut socket, None, snapshot).await
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        if sent.is_err() {
+            break;
+        }
+    }
+}
+
+/// Parse a client message and carry it out.
+/// Returns the `id` of the message along with the answer to it.
+async fn handle_text(
+    state: &AppState,
+    guild_id: &str,
+    token: &mut Option<ApiToken>,
+    text: &str,
+) -> (Option<serde_json::Value>, ServerMessage) {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return (
+            None,
+            ServerMessage::error("invalid_message", "Messages must be JSON"),
+        );
+    };
+    let id = value.get("id").cloned();
+
+    match value.get("v").and_then(|v| v.as_u64()) {
+        Some(v) if v == u64::from(PROTOCOL_VERSION) => {}
+        _ => {
+            return (
+                id,
+                ServerMessage::error(
+                    "unsupported_version",
+                    format!("Only version {PROTOCOL_VERSION} is supported"),
+                ),
+            );
+        }
+    }
+
+    let message = match serde_json::from_value::<ClientMessage>(value) {
+        Ok(message) => message,
+        Err(e) => return (id, ServerMessage::error("invalid_message", e.to_string())),
+    };
+
+    let reply = match (message, token.clone()) {
+        (ClientMessage::Auth { token: secret }, _) => {
+            authenticate(state, guild_id, token, &secret).await
+        }
+        (_, None) => ServerMessage::error("unauthorized", "Send an auth message first"),
+        (
+            ClientMessage::Join {
+                user_id,
+                display_name,
+                amount,
+                toppings,
+                variant,
+            },
+            Some(_),
+        ) => match validate_join(user_id, display_name, amount) {
+            Ok((user_id, display_name, amount)) => {
+                let order = Order {
+                    user_id,
+                    display_name,
+                    amount,
+                    toppings,
+                    variant,
+                    ticket: None,
+                };
+                join_order(state, guild_id, order).await
+            }
+            Err(error) => error,
+        },
+        (ClientMessage::Leave { user_id, variant }, Some(token)) => {
+            leave(state, guild_id, &token, user_id, variant).await
+        }
+        (ClientMessage::Bake { amount, variant }, Some(token)) => {
+            bake_batch(state, guild_id, &token, amount, variant).await
+        }
+    };
+    (id, reply)
+}
+
+/// Check a join from a client before it reaches the queue. Only Discord users
+/// can be added this way, since guests get their ticket from the guest endpoint.
+fn validate_join(
+    user_id: String,
+    display_name: String,
+    amount: Option<u32>,
+) -> Result<(String, String, u32), ServerMessage> {
+    if !user_id.parse::<u64>().is_ok_and(|id| id != 0) {
+        return Err(ServerMessage::error(
+            "invalid_user_id",
+            "user_id must be a Discord user id",
+        ));
+    }
+
+    let display_name = display_name.trim().to_string();
+    if display_name.is_empty() || display_name.chars().count() > MAX_GUEST_NAME_LENGTH {
+        return Err(ServerMessage::error(
+            "invalid_name",
+            format!("display_name must be between 1 and {MAX_GUEST_NAME_LENGTH} characters"),
+        ));
+    }
+
+    let amount = amount.unwrap_or(1);
+    if amount == 0 {
+        return Err(ServerMessage::error(
+            "invalid_amount",
+            "amount must be at least 1",
+        ));
+    }
+
+    Ok((user_id, display_name, amount))
+}
+
+fn internal_error() -> ServerMessage {
+    ServerMessage::error("internal", "Something went wrong")
+}
+
+async fn authenticate(
+    state: &AppState,
+    guild_id: &str,
+    token: &mut Option<ApiToken>,
+    secret: &str,
+) -> ServerMessage {
+    match state
+        .repositories
+        .api_tokens
+        .find(&hash_token(secret))
+        .await
+    {
+        Ok(Some(found)) if found.guild_id == guild_id => {
+            info!(guild_id, token_id = found.id, "Authenticated queue socket");
+            let label = found.label.clone();
+            *token = Some(found);
+            ServerMessage::Authenticated { label }
+        }
+        Ok(_) => ServerMessage::error("unauthorized", "Invalid API token"),
+        Err(_) => internal_error(),
+    }
+}
+
+async fn join_order(state: &AppState, guild_id: &str, order: Order) -> ServerMessage {
+    let Ok(settings) = state.repositories.settings.get(guild_id).await else {
+        return internal_error();
+    };
+
+    let outcome = join(
+        state.repositories.queue.as_ref(),
+        state.repositories.orders.as_ref(),
+        state.repositories.products.as_ref(),
+        &settings,
+        guild_id,
+        order,
+        &state.timezone,
+    )
+    .await;
+    let Ok(outcome) = outcome else {
+        return internal_error();
+    };
+
+    match outcome {
+        JoinOutcome::Joined {
+            lane,
+            entry,
+            position,
+            ..
+        } => {
+            state.events.publish(
+                guild_id,
+                QueueEvent::Joined {
+                    lane: lane.clone(),
+                    entry,
+                    position,
+                },
+            );
+            ServerMessage::Joined { lane, position }
+        }
+        JoinOutcome::Waitlisted { lane, position } => ServerMessage::Waitlisted { lane, position },
+        JoinOutcome::Closed => ServerMessage::error("closed", "The queue is closed"),
+        JoinOutcome::AlreadyDrawn => {
+            ServerMessage::error("already_drawn", "The lottery has already been drawn")
+        }
+        JoinOutcome::UnknownVariant(variant) => {
+            ServerMessage::error("unknown_variant", format!("Unknown variant: {variant}"))
+        }
+        JoinOutcome::AlreadyWaitlisted { position } => ServerMessage::error(
+            "already_waitlisted",
+            format!("Already number {position} on the waitlist"),
+        ),
+        JoinOutcome::AlreadyQueued { lane, position, .. } => ServerMessage::error(
+            "already_queued",
+            format!("Already number {position} in {lane}"),
+        ),
+        JoinOutcome::Refused(Refusal::TooManyInOrder { max }) => {
+            ServerMessage::error("too_many", format!("At most {max} waffles per order"))
+        }
+        JoinOutcome::Refused(Refusal::LimitReached {
+            limit, remaining, ..
+        }) => ServerMessage::error(
+            "limit_reached",
+            format!("The limit is {limit} waffles, {remaining} left"),
+        ),
+        JoinOutcome::Refused(Refusal::Cooldown { until }) => ServerMessage::error(
+            "cooldown",
+            format!("Can order again at {}", until.to_rfc3339()),
+        ),
+        JoinOutcome::UnknownOption(option) => {
+            ServerMessage::error("unknown_option", format!("Not on the menu: {option}"))
+        }
+        JoinOutcome::OutOfBatter => {
+            ServerMessage::error("out_of_batter", "There is no batter left")
+        }
+        JoinOutcome::BatterLeft { remaining } => ServerMessage::error(
+            "out_of_batter",
+            format!("There is only batter for {remaining} more waffles"),
+        ),
+    }
+}
+
+async fn leave(
+    state: &AppState,
+    guild_id: &str,
+    token: &ApiToken,
+    user_id: String,
+    variant: Option<String>,
+) -> ServerMessage {
+    let Ok(settings) = state.repositories.settings.get(guild_id).await else {
+        return internal_error();
+    };
+
+    let Some(lane) = resolve_lane(variant.as_deref(), &settings.variants) else {
+        return ServerMessage::error(
+            "unknown_variant",
+            format!("Unknown variant: {}", variant.unwrap_or_default()),
+        );
+    };
+    if !state
+        .repositories
+        .queue
+        .remove(guild_id, &lane, &user_id)
+        .await
+    {
+        return ServerMessage::error("not_in_queue", format!("User {user_id} is not in {lane}"));
+    }
+
+    counter!(LEAVES, "guild_id" => guild_id.to_string(), "lane" => lane.clone()).increment(1);
+    state.events.publish(
+        guild_id,
+        QueueEvent::Left {
+            lane: lane.clone(),
+            user_id: user_id.clone(),
+        },
+    );
+    record(
+        state,
+        token,
+        &settings,
+        AuditAction::Remove,
+        t!(
+            settings.locale,
+            "audit-remove",
+            user = audit::mentions([user_id.as_str()]),
+            lane = lane.as_str()
+        ),
+    )
+    .await;
+
+    ServerMessage::Left { lane }
+}
+
+async fn bake_batch(
+    state: &AppState,
+    guild_id: &str,
+    token: &ApiToken,
+    amount: usize,
+    variant: Option<String>,
+) -> ServerMessage {
+    let Ok(settings) = state.repositories.settings.get(guild_id).await else {
+        return internal_error();
+    };
+    let channel_id = session_channel(state, guild_id, &settings);
+
+    let batch = match bake(
+        state.repositories.queue.as_ref(),
+        state.repositories.orders.as_ref(),
+        &settings,
+        guild_id,
+        variant.as_deref(),
+        amount,
+    )
+    .await
+    {
+        Ok(batch) => batch,
+        Err(BakeError::Closed) => return ServerMessage::error("closed", "The queue is closed"),
+        Err(BakeError::NotDrawn) => {
+            return ServerMessage::error("not_drawn", "The lottery has not been drawn yet");
+        }
+        Err(BakeError::UnknownVariant(variant)) => {
+            return ServerMessage::error("unknown_variant", format!("Unknown variant: {variant}"));
+        }
+    };
+
+    state.events.publish(guild_id, batch.event());
+    let templates = templates(state, guild_id, &settings).await;
+    announce(
+        state,
+        channel_id.as_deref(),
+        announcements::baked(&templates, &batch),
+    )
+    .await;
+    record(
+        state,
+        token,
+        &settings,
+        AuditAction::Bake,
+        audit::bake_details(settings.locale, &batch),
+    )
+    .await;
+
+    ServerMessage::Baked {
+        lane: batch.lane,
+        waffles: batch.waffles,
+        baked: batch.baked,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_messages_are_tagged_by_type() {
+        let message: ClientMessage = serde_json::from_str(
+            r#"{"v": 1, "id": 7, "type": "bake", "amount": 3, "variant": "glutenfri"}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            message,
+            ClientMessage::Bake { amount: 3, variant: Some(ref variant) } if variant == "glutenfri"
+        ));
+    }
+
+    #[test]
+    fn test_validate_join() {
+        let code = |result: Result<(String, String, u32), ServerMessage>| match result {
+            Err(ServerMessage::Error { code, .. }) => code,
+            _ => "ok",
+        };
+        let join = |user_id: &str, name: &str, amount| {
+            code(validate_join(user_id.to_string(), name.to_string(), amount))
+        };
+
+        assert_eq!(join("123456789", "Foo", None), "ok");
+        assert_eq!(join("guest:7", "Foo", None), "invalid_user_id");
+        assert_eq!(join("0", "Foo", None), "invalid_user_id");
+        assert_eq!(join("123456789", "  ", None), "invalid_name");
+        assert_eq!(join("123456789", &"a".repeat(33), None), "invalid_name");
+        assert_eq!(join("123456789", "Foo", Some(0)), "invalid_amount");
+    }
+
+    #[test]
+    fn test_server_frames_carry_version_and_id() {
+        let frame = ServerFrame {
+            v: PROTOCOL_VERSION,
+            id: Some(serde_json::json!("abc")),
+            message: ServerMessage::error("closed", "The queue is closed"),
+        };
+
+        assert_eq!(
+            serde_json::to_value(frame).unwrap(),
+            serde_json::json!({
+                "v": 1,
+                "id": "abc",
+                "type": "error",
+                "code": "closed",
+                "message": "The queue is closed",
+            })
+        );
+    }
+}
@@ -1,7 +1,23 @@
+use std::net::SocketAddr;
+
+use axum::http::HeaderValue;
+use chrono_tz::Tz;
+
 pub struct Config {
     pub redis_url: String,
     pub discord_token: String,
     pub database_url: String,
+    /// Time zone the schedules are in
+    pub timezone: Tz,
+    pub http: HttpConfig,
+}
+
+pub struct HttpConfig {
+    /// Whether the HTTP API is served at all
+    pub enabled: bool,
+    pub addr: SocketAddr,
+    /// Origins browsers may call the API from, `*` for any
+    pub cors_origins: Vec<HeaderValue>,
 }
 
 impl Config {
@@ -13,11 +29,69 @@ impl Config {
             std::env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
         let database_url =
             std::env::var("DATABASE_URL").expect("Expected DATABASE_URL in environment");
+        let timezone = std::env::var("TIMEZONE")
+            .map(|tz| {
+                tz.parse()
+                    .expect("Expected TIMEZONE to be a valid time zone")
+            })
+            .unwrap_or(chrono_tz::Europe::Oslo);
+
+        let http = HttpConfig {
+            enabled: std::env::var("HTTP_ENABLED")
+                .map(|enabled| {
+                    enabled
+                        .parse()
+                        .expect("Expected HTTP_ENABLED to be true or false")
+                })
+                .unwrap_or(true),
+            addr: std::env::var("HTTP_ADDR")
+                .map(|addr| {
+                    addr.parse()
+                        .expect("Expected HTTP_ADDR to be an address like 0.0.0.0:3000")
+                })
+                .unwrap_or(SocketAddr::from(([0, 0, 0, 0], 3000))),
+            cors_origins: std::env::var("CORS_ORIGINS")
+                .map(|origins| {
+                    parse_origins(&origins).expect("Expected CORS_ORIGINS to be valid origins")
+                })
+                .unwrap_or_default(),
+        };
 
         Self {
             redis_url,
             discord_token,
             database_url,
+            timezone,
+            http,
         }
     }
 }
+
+/// Origins separated by commas, like `https://example.com, http://localhost:5173`
+fn parse_origins(
+    origins: &str,
+) -> Result<Vec<HeaderValue>, axum::http::header::InvalidHeaderValue> {
+    origins
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(HeaderValue::from_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_origins() {
+        let origins = parse_origins(" https://example.com,, http://localhost:5173 ").unwrap();
+        assert_eq!(
+            origins,
+            vec!["https://example.com", "http://localhost:5173"]
+        );
+
+        assert!(parse_origins("").unwrap().is_empty());
+        assert!(parse_origins("https://example\u{1}.com").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// Prefix of every token, so a leaked one is easy to recognise
+const TOKEN_PREFIX: &str = "vfl_";
+
+/// Longest label an oracle can give a token
+pub const MAX_LABEL_LENGTH: usize = 100;
+
+/// A token that lets something outside Discord control the queue of a guild.
+///
+/// Only the hash of the token is stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiToken {
+    pub id: i64,
+    pub guild_id: String,
+    /// What the token is for, e.g. "Nettbrett i standen"
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Make a new random token
+pub fn generate_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    format!("{TOKEN_PREFIX}{}", to_hex(&bytes))
+}
+
+/// Hash a token the way it is stored
+pub fn hash_token(token: &str) -> String {
+    to_hex(&Sha256::digest(token.trim().as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[async_trait::async_trait]
+pub trait ApiTokenRepository: Send + Sync {
+    /// Store the hash of a new token and return its id
+    async fn create(&self, guild_id: &str, label: &str, token_hash: &str) -> anyhow::Result<i64>;
+
+    /// Get the tokens of a guild, oldest first
+    async fn list(&self, guild_id: &str) -> anyhow::Result<Vec<ApiToken>>;
+
+    /// Find the token with the given hash
+    async fn find(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>>;
+
+    /// Revoke a token of a guild
+    /// Returns false if the guild has no token with that id
+    async fn revoke(&self, guild_id: &str, id: i64) -> anyhow::Result<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_tokens_are_unique_and_hash_the_same() {
+        let token = generate_token();
+
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + 64);
+        assert_ne!(token, generate_token());
+
+        assert_eq!(hash_token(&token), hash_token(&format!(" {token}\n")));
+        assert_ne!(hash_token(&token), hash_token(&generate_token()));
+        assert_eq!(
+            hash_token("vfl_test"),
+            "6c181343485a54db544149b21c7bbda73912b3c34bf8ee0a701844f4a8eeae65"
+        );
+    }
+}
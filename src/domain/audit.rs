@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+
+/// Privileged things that are written to the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Open,
+    Close,
+    Bake,
+    Draw,
+    Capacity,
+    Remove,
+    Move,
+    ApiToken,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Open => "open",
+            AuditAction::Close => "close",
+            AuditAction::Bake => "bake",
+            AuditAction::Draw => "draw",
+            AuditAction::Capacity => "capacity",
+            AuditAction::Remove => "remove",
+            AuditAction::Move => "move",
+            AuditAction::ApiToken => "api_token",
+        }
+    }
+}
+
+impl std::str::FromStr for AuditAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(AuditAction::Open),
+            "close" => Ok(AuditAction::Close),
+            "bake" => Ok(AuditAction::Bake),
+            "draw" => Ok(AuditAction::Draw),
+            "capacity" => Ok(AuditAction::Capacity),
+            "remove" => Ok(AuditAction::Remove),
+            "move" => Ok(AuditAction::Move),
+            "api_token" => Ok(AuditAction::ApiToken),
+            other => Err(anyhow::anyhow!("Unknown audit action: {other}")),
+        }
+    }
+}
+
+/// Who did what and when
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub guild_id: String,
+    /// The user who did it, or None if the bot did it by itself
+    pub actor_id: Option<String>,
+    pub action: AuditAction,
+    pub details: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait::async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Store an entry in the audit log and return its id
+    async fn record(&self, entry: &AuditEntry) -> anyhow::Result<i64>;
+}
@@ -0,0 +1,85 @@
+use crate::domain::{QueueEntry, QueueRepository};
+
+/// What happens to orders that do not fit in the remaining capacity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowMode {
+    /// Tell the user that there is no more batter
+    #[default]
+    Refuse,
+    /// Put the user on a waitlist that is moved into the queue if capacity is added
+    Waitlist,
+}
+
+impl OverflowMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverflowMode::Refuse => "refuse",
+            OverflowMode::Waitlist => "waitlist",
+        }
+    }
+}
+
+impl std::str::FromStr for OverflowMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "refuse" => Ok(OverflowMode::Refuse),
+            "waitlist" => Ok(OverflowMode::Waitlist),
+            other => Err(anyhow::anyhow!("Unknown overflow mode: {other}")),
+        }
+    }
+}
+
+/// An entry waiting for capacity, along with the lane it wants to join
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct WaitlistEntry {
+    pub lane: String,
+    pub entry: QueueEntry,
+}
+
+/// How much of the batter of a session is spoken for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, utoipa::ToSchema)]
+pub struct CapacityStatus {
+    /// Waffles the session has batter for
+    pub capacity: u32,
+    /// Waffles baked since the queue was opened
+    pub served: u32,
+    /// Waffles people in the queue are waiting for
+    pub queued: u32,
+}
+
+impl CapacityStatus {
+    /// Waffles that can still be ordered
+    pub fn remaining(&self) -> u32 {
+        self.capacity.saturating_sub(self.served + self.queued)
+    }
+}
+
+/// Count the waffles waiting in every lane of a guild
+pub async fn queued_waffles(queue: &dyn QueueRepository, guild_id: &str) -> u32 {
+    let mut queued = 0;
+    for lane in queue.lanes(guild_id).await {
+        queued += queue
+            .list(guild_id, &lane)
+            .await
+            .iter()
+            .map(|entry| entry.quantity)
+            .sum::<u32>();
+    }
+    queued
+}
+
+/// Get the capacity status of the current session
+/// Returns None if the queue is closed or the session has no capacity
+pub async fn capacity_status(
+    queue: &dyn QueueRepository,
+    guild_id: &str,
+) -> Option<CapacityStatus> {
+    let capacity = queue.session(guild_id)?.capacity?;
+    Some(CapacityStatus {
+        capacity,
+        served: queue.served_count(guild_id).await,
+        queued: queued_waffles(queue, guild_id).await,
+    })
+}
@@ -0,0 +1,172 @@
+use chrono::{DateTime, NaiveTime, TimeDelta, TimeZone, Utc};
+
+use crate::domain::SessionMode;
+
+/// How long before the deadline the reminder is posted
+pub const REMINDER_BEFORE: TimeDelta = TimeDelta::minutes(10);
+
+/// Longest a session can be opened for with a duration
+pub const MAX_DURATION: TimeDelta = TimeDelta::hours(24);
+
+/// When an open session closes by itself
+///
+/// Sessions only live in memory, so the mode and capacity are kept here to
+/// reopen the session if the bot restarts before the deadline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionDeadline {
+    pub guild_id: String,
+    /// Channel the reminder and the close summary are posted in
+    pub channel_id: String,
+    pub mode: SessionMode,
+    pub capacity: Option<u32>,
+    pub closes_at: DateTime<Utc>,
+    /// Whether the reminder has been posted
+    pub reminded: bool,
+}
+
+impl SessionDeadline {
+    /// Whether it is time to post the reminder
+    pub fn should_remind(&self, now: DateTime<Utc>) -> bool {
+        !self.reminded && now >= self.closes_at - REMINDER_BEFORE && now < self.closes_at
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        now >= self.closes_at
+    }
+}
+
+#[async_trait::async_trait]
+pub trait DeadlineRepository: Send + Sync {
+    /// Set the deadline of a guild, replacing any earlier one
+    async fn set(&self, deadline: &SessionDeadline) -> anyhow::Result<()>;
+
+    async fn get(&self, guild_id: &str) -> anyhow::Result<Option<SessionDeadline>>;
+
+    /// List the deadlines of every guild
+    async fn list_all(&self) -> anyhow::Result<Vec<SessionDeadline>>;
+
+    async fn mark_reminded(&self, guild_id: &str) -> anyhow::Result<()>;
+
+    async fn remove(&self, guild_id: &str) -> anyhow::Result<()>;
+}
+
+/// Parse a duration like "90m", "2t", "1h30m" or "45"
+/// A plain number is read as minutes. Durations longer than [`MAX_DURATION`]
+/// are not accepted.
+pub fn parse_duration(input: &str) -> Option<TimeDelta> {
+    let input = input.trim().to_lowercase();
+    if let Ok(minutes) = input.parse::<u32>() {
+        return Some(TimeDelta::minutes(minutes.into()))
+            .filter(|d| *d > TimeDelta::zero() && *d <= MAX_DURATION);
+    }
+
+    let mut total = TimeDelta::zero();
+    let mut number = String::new();
+    for c in input.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 't' | 'm' => {
+                let value: i64 = number.parse().ok()?;
+                number.clear();
+                let part = if c == 'm' {
+                    TimeDelta::try_minutes(value)?
+                } else {
+                    TimeDelta::try_hours(value)?
+                };
+                total = total.checked_add(&part)?;
+            }
+            ' ' => {}
+            _ => return None,
+        }
+    }
+
+    if !number.is_empty() || total <= TimeDelta::zero() || total > MAX_DURATION {
+        return None;
+    }
+    Some(total)
+}
+
+/// Find when the clock next shows `time` today in `timezone`
+/// Returns None if it has already passed today.
+pub fn today_at<Tz: TimeZone>(
+    time: NaiveTime,
+    now: DateTime<Utc>,
+    timezone: &Tz,
+) -> Option<DateTime<Utc>> {
+    let local = now.with_timezone(timezone);
+    let at = local
+        .date_naive()
+        .and_time(time)
+        .and_local_timezone(timezone.clone())
+        .earliest()?
+        .with_timezone(&Utc);
+
+    (at > now).then_some(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90m"), Some(TimeDelta::minutes(90)));
+        assert_eq!(parse_duration("45"), Some(TimeDelta::minutes(45)));
+        assert_eq!(parse_duration("2t"), Some(TimeDelta::hours(2)));
+        assert_eq!(parse_duration("1h30m"), Some(TimeDelta::minutes(90)));
+        assert_eq!(parse_duration("1h 30m"), Some(TimeDelta::minutes(90)));
+    }
+
+    #[test]
+    fn test_parse_invalid_duration() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("1h30"), None);
+        assert_eq!(parse_duration("snart"), None);
+    }
+
+    #[test]
+    fn test_parse_too_long_duration() {
+        assert_eq!(parse_duration("24t"), Some(MAX_DURATION));
+        assert_eq!(parse_duration("1441"), None);
+        assert_eq!(parse_duration("24h1m"), None);
+        assert_eq!(parse_duration("99999999999h"), None);
+        assert_eq!(parse_duration("9999999999999999h"), None);
+        assert_eq!(parse_duration("99999999999999999999m"), None);
+        assert_eq!(parse_duration("153722867280912h153722867280912h"), None);
+    }
+
+    #[test]
+    fn test_today_at() {
+        let oslo = chrono_tz::Europe::Oslo;
+        // 10:00 in Oslo during summer time
+        let now = "2026-06-04T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let two = NaiveTime::from_hms_opt(14, 0, 0).unwrap();
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+        assert_eq!(
+            today_at(two, now, &oslo),
+            Some("2026-06-04T12:00:00Z".parse().unwrap())
+        );
+        assert_eq!(today_at(nine, now, &oslo), None);
+    }
+
+    #[test]
+    fn test_reminder_before_deadline() {
+        let closes_at = "2026-06-04T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let deadline = SessionDeadline {
+            guild_id: "guild".to_string(),
+            channel_id: "channel".to_string(),
+            mode: SessionMode::Queue,
+            capacity: None,
+            closes_at,
+            reminded: false,
+        };
+
+        assert!(!deadline.should_remind(closes_at - TimeDelta::minutes(11)));
+        assert!(deadline.should_remind(closes_at - TimeDelta::minutes(10)));
+        assert!(!deadline.should_remind(closes_at));
+        assert!(deadline.is_due(closes_at));
+    }
+}
@@ -0,0 +1,154 @@
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+use crate::domain::{QueueEntry, SessionMode};
+
+/// Events a subscriber can fall behind by before it misses some
+const CHANNEL_CAPACITY: usize = 256;
+
+/// The entries of one lane, in queue order
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+pub struct LaneQueue {
+    pub lane: String,
+    pub entries: Vec<QueueEntry>,
+}
+
+/// Something that changed the queue of a guild
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QueueEvent {
+    Opened {
+        mode: SessionMode,
+        capacity: Option<u32>,
+        closes_at: Option<DateTime<Utc>>,
+    },
+    Closed,
+    Joined {
+        lane: String,
+        entry: QueueEntry,
+        /// Place in the lane, counting from 1
+        position: usize,
+    },
+    Left {
+        lane: String,
+        user_id: String,
+    },
+    Moved {
+        lane: String,
+        user_id: String,
+        /// Place in the lane, counting from 1
+        position: usize,
+    },
+    Baked {
+        lane: String,
+        waffles: u32,
+        /// Who got waffles, with how many each got
+        baked: Vec<QueueEntry>,
+    },
+    /// The lottery was drawn and every lane now only holds the winners
+    Drawn {
+        lanes: Vec<LaneQueue>,
+    },
+}
+
+impl QueueEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            QueueEvent::Opened { .. } => "opened",
+            QueueEvent::Closed => "closed",
+            QueueEvent::Joined { .. } => "joined",
+            QueueEvent::Left { .. } => "left",
+            QueueEvent::Moved { .. } => "moved",
+            QueueEvent::Baked { .. } => "baked",
+            QueueEvent::Drawn { .. } => "drawn",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuildEvent {
+    pub guild_id: String,
+    pub event: QueueEvent,
+}
+
+/// Hands queue events from the adapters that change the queue to the ones
+/// that stream them.
+///
+/// Events are only kept in memory. Subscribers that fall behind miss events
+/// and are told how many.
+#[derive(Clone)]
+pub struct EventBroadcaster {
+    sender: broadcast::Sender<GuildEvent>,
+}
+
+impl EventBroadcaster {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, guild_id: &str, event: QueueEvent) {
+        debug!(guild_id, event = event.name(), "Publishing queue event");
+        // Nobody listening is not an error
+        let _ = self.sender.send(GuildEvent {
+            guild_id: guild_id.to_string(),
+            event,
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<GuildEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribers_get_events_published_after_subscribing() {
+        let events = EventBroadcaster::new();
+        events.publish("1", QueueEvent::Closed);
+
+        let mut receiver = events.subscribe();
+        events.publish(
+            "2",
+            QueueEvent::Left {
+                lane: "standard".to_string(),
+                user_id: "3".to_string(),
+            },
+        );
+
+        let event = receiver.try_recv().unwrap();
+        assert_eq!(event.guild_id, "2");
+        assert_eq!(event.event.name(), "left");
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_events_serialize_with_their_type() {
+        let json = serde_json::to_value(QueueEvent::Opened {
+            mode: SessionMode::LotterySignup,
+            capacity: Some(20),
+            closes_at: None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "opened",
+                "mode": "lottery_signup",
+                "capacity": 20,
+                "closes_at": null,
+            })
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use std::time::Duration;
+
+/// Something the bot needs to be able to serve, checked by `/readyz`
+#[async_trait::async_trait]
+pub trait HealthCheck: Send + Sync {
+    /// Name the dependency is reported under
+    fn name(&self) -> &'static str;
+
+    /// Check that the dependency answers
+    /// Returns how long it took to answer
+    async fn check(&self) -> anyhow::Result<Duration>;
+}
@@ -0,0 +1,265 @@
+use chrono::{DateTime, Days, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+use crate::domain::{GuildSettings, OrderRepository, QueueRepository};
+
+/// The period a per-user limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimitPeriod {
+    /// From the queue is opened until it is closed
+    #[default]
+    Session,
+    /// From midnight to midnight
+    Day,
+}
+
+impl LimitPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitPeriod::Session => "session",
+            LimitPeriod::Day => "day",
+        }
+    }
+}
+
+impl std::str::FromStr for LimitPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "session" => Ok(LimitPeriod::Session),
+            "day" => Ok(LimitPeriod::Day),
+            other => Err(anyhow::anyhow!("Unknown limit period: {other}")),
+        }
+    }
+}
+
+/// What a user has been served and is waiting for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserHistory {
+    /// Waffles served in the current limit period
+    pub served_in_period: u32,
+    /// Waffles the user is still waiting for in the queue
+    pub queued: u32,
+    /// When the user was last served
+    pub last_served_at: Option<DateTime<Utc>>,
+}
+
+/// Why an order was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum Refusal {
+    /// The order asks for more waffles than one order can have
+    TooManyInOrder { max: u32 },
+    /// The user would go over the per-user limit for the period.
+    /// `retry_at` is None when the limit only resets with a new session.
+    LimitReached {
+        limit: u32,
+        remaining: u32,
+        retry_at: Option<DateTime<Utc>>,
+    },
+    /// The user was served too recently to join again
+    Cooldown { until: DateTime<Utc> },
+}
+
+/// Check an order for `amount` waffles against the rules of a guild.
+///
+/// `period_end` is when the limit period resets, if it resets on its own.
+pub fn evaluate(
+    settings: &GuildSettings,
+    history: &UserHistory,
+    amount: u32,
+    now: DateTime<Utc>,
+    period_end: Option<DateTime<Utc>>,
+) -> Result<(), Refusal> {
+    if amount == 0 || amount > settings.max_waffles_per_order {
+        return Err(Refusal::TooManyInOrder {
+            max: settings.max_waffles_per_order,
+        });
+    }
+
+    if let (Some(minutes), Some(last_served_at)) =
+        (settings.rejoin_cooldown_minutes, history.last_served_at)
+    {
+        let until = last_served_at + Duration::minutes(minutes as i64);
+        if until > now {
+            return Err(Refusal::Cooldown { until });
+        }
+    }
+
+    if let Some(limit) = settings.max_waffles_per_user {
+        let used = history.served_in_period + history.queued;
+        if used + amount > limit {
+            return Err(Refusal::LimitReached {
+                limit,
+                remaining: limit.saturating_sub(used),
+                retry_at: period_end,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether a user may order `amount` waffles right now.
+///
+/// Looks up what the user has been served and is waiting for, and returns the
+/// reason the order is refused, or None if it is allowed.
+pub async fn check_order(
+    orders: &dyn OrderRepository,
+    queue: &dyn QueueRepository,
+    settings: &GuildSettings,
+    guild_id: &str,
+    user_id: &str,
+    amount: u32,
+    timezone: &Tz,
+) -> anyhow::Result<Option<Refusal>> {
+    let now = Utc::now();
+
+    let (period_start, period_end) = match settings.limit_period {
+        LimitPeriod::Session => {
+            let opened_at = queue.session(guild_id).map(|session| session.opened_at);
+            (opened_at.unwrap_or(now), None)
+        }
+        LimitPeriod::Day => {
+            let (start, end) = local_day(now, timezone);
+            (start, Some(end))
+        }
+    };
+
+    let served_in_period = match settings.max_waffles_per_user {
+        Some(_) => {
+            orders
+                .waffles_since(guild_id, user_id, period_start)
+                .await?
+        }
+        None => 0,
+    };
+
+    let last_served_at = match settings.rejoin_cooldown_minutes {
+        Some(_) => orders.last_served_at(guild_id, user_id).await?,
+        None => None,
+    };
+
+    let mut queued = 0;
+    for lane in queue.lanes(guild_id).await {
+        queued += queue
+            .list(guild_id, &lane)
+            .await
+            .iter()
+            .filter(|entry| entry.user_id == user_id)
+            .map(|entry| entry.quantity)
+            .sum::<u32>();
+    }
+
+    let history = UserHistory {
+        served_in_period: served_in_period as u32,
+        queued,
+        last_served_at,
+    };
+
+    Ok(evaluate(settings, &history, amount, now, period_end).err())
+}
+
+/// Start and end of the day `now` is in, in `timezone`
+fn local_day(now: DateTime<Utc>, timezone: &Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+    let today = now.with_timezone(timezone).date_naive();
+    let start_of = |date: NaiveDate| local_midnight(date, timezone).unwrap_or(now);
+    (start_of(today), start_of(today + Days::new(1)))
+}
+
+/// When `date` starts in `timezone`
+pub fn local_midnight(date: NaiveDate, timezone: &Tz) -> Option<DateTime<Utc>> {
+    date.and_hms_opt(0, 0, 0)?
+        .and_local_timezone(*timezone)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> GuildSettings {
+        GuildSettings {
+            max_waffles_per_order: 2,
+            max_waffles_per_user: Some(3),
+            rejoin_cooldown_minutes: Some(30),
+            ..GuildSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_allows_order_within_limits() {
+        let now = Utc::now();
+        let history = UserHistory {
+            served_in_period: 1,
+            queued: 0,
+            last_served_at: Some(now - Duration::minutes(31)),
+        };
+
+        assert_eq!(evaluate(&settings(), &history, 2, now, None), Ok(()));
+    }
+
+    #[test]
+    fn test_refuses_too_large_order() {
+        let now = Utc::now();
+
+        assert_eq!(
+            evaluate(&settings(), &UserHistory::default(), 3, now, None),
+            Err(Refusal::TooManyInOrder { max: 2 })
+        );
+    }
+
+    #[test]
+    fn test_refuses_during_cooldown() {
+        let now = Utc::now();
+        let served_at = now - Duration::minutes(10);
+        let history = UserHistory {
+            last_served_at: Some(served_at),
+            ..UserHistory::default()
+        };
+
+        assert_eq!(
+            evaluate(&settings(), &history, 1, now, None),
+            Err(Refusal::Cooldown {
+                until: served_at + Duration::minutes(30)
+            })
+        );
+    }
+
+    #[test]
+    fn test_limit_counts_queued_waffles() {
+        let now = Utc::now();
+        let tomorrow = now + Duration::days(1);
+        let history = UserHistory {
+            served_in_period: 1,
+            queued: 1,
+            last_served_at: None,
+        };
+
+        assert_eq!(
+            evaluate(&settings(), &history, 2, now, Some(tomorrow)),
+            Err(Refusal::LimitReached {
+                limit: 3,
+                remaining: 1,
+                retry_at: Some(tomorrow),
+            })
+        );
+        assert_eq!(evaluate(&settings(), &history, 1, now, None), Ok(()));
+    }
+
+    #[test]
+    fn test_no_limits_configured() {
+        let now = Utc::now();
+        let history = UserHistory {
+            served_in_period: 100,
+            queued: 0,
+            last_served_at: Some(now),
+        };
+
+        assert_eq!(
+            evaluate(&GuildSettings::default(), &history, 1, now, None),
+            Ok(())
+        );
+    }
+}
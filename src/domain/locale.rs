@@ -0,0 +1,45 @@
+/// Language the bot replies in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    /// Norwegian bokmål
+    #[default]
+    Nb,
+    /// Norwegian nynorsk
+    Nn,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::Nb, Locale::Nn, Locale::En];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::Nb => "nb",
+            Locale::Nn => "nn",
+            Locale::En => "en",
+        }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "nb" => Ok(Locale::Nb),
+            "nn" => Ok(Locale::Nn),
+            "en" => Ok(Locale::En),
+            other => Err(anyhow::anyhow!("Unknown locale: {other}")),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait UserLocaleRepository: Send + Sync {
+    /// Get the language a user has picked
+    /// Returns None if the user follows the guild
+    async fn get(&self, user_id: &str) -> anyhow::Result<Option<Locale>>;
+
+    /// Pick the language of a user, or None to follow the guild
+    async fn set(&self, user_id: &str, locale: Option<Locale>) -> anyhow::Result<()>;
+}
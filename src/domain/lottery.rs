@@ -0,0 +1,82 @@
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// A finished lottery draw, kept so the result can be verified afterwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LotteryDraw {
+    pub guild_id: String,
+    pub seed: u64,
+    /// User ids in the order they were fed to the draw
+    pub participants: Vec<String>,
+    /// User ids of the winners in the order they were drawn
+    pub winners: Vec<String>,
+    pub drawn_by: String,
+}
+
+#[async_trait::async_trait]
+pub trait LotteryRepository: Send + Sync {
+    /// Store a draw and return its id
+    async fn record_draw(&self, draw: &LotteryDraw) -> anyhow::Result<i64>;
+}
+
+/// Draw up to `n` winners out of `count` participants.
+///
+/// Returns the indices of the winners in the order they were drawn. The draw
+/// only depends on `seed` and `count`, so anyone with the logged seed and
+/// participant list can repeat it: a partial Fisher-Yates shuffle driven by
+/// ChaCha8 seeded with `seed_from_u64`, using rejection sampling to pick
+/// uniformly among the remaining participants.
+pub fn draw_winners(count: usize, n: usize, seed: u64) -> Vec<usize> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut indices: Vec<usize> = (0..count).collect();
+    let winners = n.min(count);
+
+    for i in 0..winners {
+        let j = i + uniform_below(&mut rng, (count - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+
+    indices.truncate(winners);
+    indices
+}
+
+fn uniform_below(rng: &mut ChaCha8Rng, bound: u64) -> u64 {
+    // Values at or above `zone` would make the lower results more likely
+    let zone = u64::MAX - (u64::MAX % bound);
+    loop {
+        let value = rng.next_u64();
+        if value < zone {
+            return value % bound;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_is_reproducible() {
+        assert_eq!(draw_winners(20, 5, 42), draw_winners(20, 5, 42));
+        assert_ne!(draw_winners(20, 5, 42), draw_winners(20, 5, 43));
+    }
+
+    #[test]
+    fn test_draw_picks_distinct_winners() {
+        let mut winners = draw_winners(10, 10, 7);
+        winners.sort();
+        assert_eq!(winners, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_draw_with_more_winners_than_participants() {
+        assert_eq!(draw_winners(3, 5, 1).len(), 3);
+        assert!(draw_winners(0, 5, 1).is_empty());
+    }
+
+    #[test]
+    fn test_draw_is_stable_across_versions() {
+        // Logged draws can only be verified if the algorithm never changes
+        assert_eq!(draw_winners(10, 3, 1234), vec![3, 6, 7]);
+    }
+}
@@ -1,5 +1,37 @@
+pub mod api_token;
+pub mod audit;
+pub mod capacity;
+pub mod deadline;
+pub mod events;
+pub mod health;
+pub mod limits;
+pub mod locale;
+pub mod lottery;
 pub mod order;
+pub mod ordering;
+pub mod product;
 pub mod queue;
+pub mod repositories;
+pub mod schedule;
+pub mod serving;
+pub mod session;
+pub mod settings;
+pub mod template;
 
-pub use order::{DailyStats, OrderRepository};
-pub use queue::{QueueEntry, QueueRepository};
+pub use api_token::{ApiToken, ApiTokenRepository};
+pub use audit::{AuditAction, AuditEntry, AuditLogRepository};
+pub use capacity::{CapacityStatus, OverflowMode, WaitlistEntry};
+pub use deadline::{DeadlineRepository, SessionDeadline};
+pub use events::{EventBroadcaster, GuildEvent, LaneQueue, QueueEvent};
+pub use health::HealthCheck;
+pub use limits::{LimitPeriod, Refusal};
+pub use locale::{Locale, UserLocaleRepository};
+pub use lottery::{LotteryDraw, LotteryRepository};
+pub use order::{DailyStats, OrderRecord, OrderRepository, PeriodStats};
+pub use product::ProductOptionRepository;
+pub use queue::{QueueEntry, QueueRepository, Session, SessionMode};
+pub use repositories::Repositories;
+pub use schedule::{Schedule, ScheduleEvent, ScheduleRepository};
+pub use serving::{ServingMode, ServingPolicy};
+pub use settings::{GuildSettings, SettingsRepository};
+pub use template::{TemplateError, TemplateKind, TemplateRepository};
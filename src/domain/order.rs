@@ -1,11 +1,127 @@
+use chrono::{DateTime, Datelike, Days, Utc};
+use chrono_tz::Tz;
+
+use crate::domain::{QueueEntry, limits::local_midnight};
+
 #[async_trait::async_trait]
 pub trait OrderRepository: Send + Sync {
-    async fn record_orders(&self, discord_user_ids: &[&str], guild_id: &str) -> anyhow::Result<()>;
-    async fn daily_stats(&self, guild_id: &str) -> anyhow::Result<DailyStats>;
+    /// Record the waffles handed out to each entry, using `quantity` as the count
+    async fn record_orders(
+        &self,
+        entries: &[QueueEntry],
+        guild_id: &str,
+        lane: &str,
+    ) -> anyhow::Result<()>;
+    /// Stats of the day that started at `day_start`
+    async fn daily_stats(
+        &self,
+        guild_id: &str,
+        day_start: DateTime<Utc>,
+    ) -> anyhow::Result<DailyStats>;
+
+    /// Count the waffles a user has been served since `since`
+    async fn waffles_since(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<i64>;
+
+    /// Get when a user was last served
+    /// Returns None if the user has never been served
+    async fn last_served_at(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>>;
+
+    /// Total waffles served since `since`, or ever if None, with the `top`
+    /// users who got the most
+    async fn stats_since(
+        &self,
+        guild_id: &str,
+        since: Option<DateTime<Utc>>,
+        top: i64,
+    ) -> anyhow::Result<PeriodStats>;
+
+    /// Get the orders served from `from` until `to`, oldest first.
+    /// Either end is open if None.
+    async fn list_orders(
+        &self,
+        guild_id: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Vec<OrderRecord>>;
+}
+
+/// Waffles served in some period
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+pub struct PeriodStats {
+    pub total: i64,
+    /// (discord_user_id, count), most served first. Guests are only in the total.
+    pub top_users: Vec<(String, i64)>,
+}
+
+/// Waffles handed out to one user in one batch
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct OrderRecord {
+    pub id: i64,
+    pub user_id: String,
+    pub lane: String,
+    pub quantity: i32,
+    pub fulfilled_at: DateTime<Utc>,
 }
 
+/// Start of the day `now` is in, in `timezone`
+pub fn local_day_start(now: DateTime<Utc>, timezone: &Tz) -> DateTime<Utc> {
+    local_midnight(now.with_timezone(timezone).date_naive(), timezone).unwrap_or(now)
+}
+
+/// Start of the week `now` is in, on Monday in `timezone`
+pub fn local_week_start(now: DateTime<Utc>, timezone: &Tz) -> DateTime<Utc> {
+    let today = now.with_timezone(timezone).date_naive();
+    let monday = today - Days::new(today.weekday().num_days_from_monday().into());
+    local_midnight(monday, timezone).unwrap_or(now)
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
 pub struct DailyStats {
     pub total_orders: i64,
-    /// (discord_user_id, count)
+    /// (discord_user_id, count). Guests are only in the total.
     pub top_users: Vec<(String, i64)>,
+    /// (lane, count), ordered by lane name
+    pub lanes: Vec<(String, i64)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OSLO: Tz = chrono_tz::Europe::Oslo;
+
+    #[test]
+    fn test_week_starts_on_monday() {
+        let now = Utc::now();
+        let start = local_week_start(now, &OSLO).with_timezone(&OSLO);
+
+        assert_eq!(start.weekday(), chrono::Weekday::Mon);
+        assert_eq!(start.time(), chrono::NaiveTime::MIN);
+        assert!(start <= now && now - start.with_timezone(&Utc) < chrono::Duration::days(7));
+        assert!(local_week_start(now, &OSLO) <= local_day_start(now, &OSLO));
+    }
+
+    #[test]
+    fn test_day_follows_timezone() {
+        // Half past midnight in Oslo, while it is still the day before in UTC
+        let now: DateTime<Utc> = "2026-10-19T22:30:00Z".parse().unwrap();
+
+        assert_eq!(
+            local_day_start(now, &OSLO),
+            "2026-10-19T22:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            local_day_start(now, &chrono_tz::UTC),
+            "2026-10-19T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
 }
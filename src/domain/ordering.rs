@@ -0,0 +1,186 @@
+use chrono::Utc;
+use chrono_tz::Tz;
+
+use crate::domain::{
+    GuildSettings, OrderRepository, OverflowMode, ProductOptionRepository, QueueEntry,
+    QueueRepository, Refusal, SessionMode, WaitlistEntry, capacity::capacity_status, limits,
+    limits::UserHistory, product::parse_options, queue::resolve_lane,
+};
+
+/// Someone asking for waffles
+#[derive(Debug, Clone)]
+pub struct Order {
+    pub user_id: String,
+    pub display_name: String,
+    pub amount: u32,
+    /// Options as the user typed them, separated by commas
+    pub toppings: Option<String>,
+    /// Variant as the user typed it, the default lane if None
+    pub variant: Option<String>,
+    /// Ticket of a guest ordering without Discord, None for Discord users
+    pub ticket: Option<u32>,
+}
+
+/// What came of an order
+#[derive(Debug, Clone, PartialEq)]
+pub enum JoinOutcome {
+    /// In the lane, at `position` counting from 1
+    Joined {
+        lane: String,
+        entry: QueueEntry,
+        position: usize,
+        lottery: bool,
+    },
+    /// On the waitlist until there is batter, at `position` counting from 1
+    Waitlisted {
+        lane: String,
+        position: usize,
+    },
+    Closed,
+    /// Winners of the lottery have been drawn, so signup is over
+    AlreadyDrawn,
+    UnknownVariant(String),
+    AlreadyWaitlisted {
+        position: usize,
+    },
+    AlreadyQueued {
+        lane: String,
+        position: usize,
+        lottery: bool,
+    },
+    Refused(Refusal),
+    UnknownOption(String),
+    /// The session has no batter left and the guild refuses what does not fit
+    OutOfBatter,
+    /// Only `remaining` waffles fit and the guild refuses what does not fit
+    BatterLeft {
+        remaining: u32,
+    },
+}
+
+/// Put an order in the queue of a guild, checking the limits, menu and
+/// capacity of the guild first. Daily limits follow days in `timezone`.
+pub async fn join(
+    queue: &dyn QueueRepository,
+    orders: &dyn OrderRepository,
+    products: &dyn ProductOptionRepository,
+    settings: &GuildSettings,
+    guild_id: &str,
+    order: Order,
+    timezone: &Tz,
+) -> anyhow::Result<JoinOutcome> {
+    let lottery = match queue.mode(guild_id) {
+        None => return Ok(JoinOutcome::Closed),
+        Some(SessionMode::LotteryDrawn) => return Ok(JoinOutcome::AlreadyDrawn),
+        Some(SessionMode::LotterySignup) => true,
+        Some(SessionMode::Queue) => false,
+    };
+
+    let Some(lane) = resolve_lane(order.variant.as_deref(), &settings.variants) else {
+        return Ok(JoinOutcome::UnknownVariant(
+            order.variant.unwrap_or_default(),
+        ));
+    };
+
+    let waitlist = queue.waitlist(guild_id).await;
+    if let Some(index) = waitlist
+        .iter()
+        .position(|waiting| waiting.lane == lane && waiting.entry.user_id == order.user_id)
+    {
+        return Ok(JoinOutcome::AlreadyWaitlisted {
+            position: index + 1,
+        });
+    }
+
+    // Everyone gets one ticket in the lottery, whichever variant they signed up for
+    let lanes = if lottery {
+        queue.lanes(guild_id).await
+    } else {
+        vec![lane.clone()]
+    };
+    for queued_lane in lanes {
+        if let Some(index) = queue.index_of(guild_id, &queued_lane, &order.user_id).await {
+            return Ok(JoinOutcome::AlreadyQueued {
+                lane: queued_lane,
+                position: index + 1,
+                lottery,
+            });
+        }
+    }
+
+    let refusal = match order.ticket {
+        // Guests get a new ticket every time, so they have no history to limit
+        Some(_) => limits::evaluate(
+            settings,
+            &UserHistory::default(),
+            order.amount,
+            Utc::now(),
+            None,
+        )
+        .err(),
+        None => {
+            limits::check_order(
+                orders,
+                queue,
+                settings,
+                guild_id,
+                &order.user_id,
+                order.amount,
+                timezone,
+            )
+            .await?
+        }
+    };
+    if let Some(refusal) = refusal {
+        return Ok(JoinOutcome::Refused(refusal));
+    }
+
+    let options = match order.toppings {
+        Some(toppings) => {
+            let catalog = products.list(guild_id).await?;
+            match parse_options(&toppings, &catalog) {
+                Ok(options) => options,
+                Err(unknown) => return Ok(JoinOutcome::UnknownOption(unknown)),
+            }
+        }
+        None => vec![],
+    };
+
+    let entry = match order.ticket {
+        Some(ticket) => QueueEntry::guest(ticket, order.display_name),
+        None => QueueEntry::new(order.user_id, order.display_name),
+    }
+    .with_quantity(order.amount)
+    .with_options(options);
+
+    // The lottery decides who gets waffles, so capacity only limits the queue
+    let status = capacity_status(queue, guild_id).await;
+    if let Some(status) = status.filter(|status| !lottery && order.amount > status.remaining()) {
+        return Ok(match settings.overflow_mode {
+            OverflowMode::Refuse if status.remaining() == 0 => JoinOutcome::OutOfBatter,
+            OverflowMode::Refuse => JoinOutcome::BatterLeft {
+                remaining: status.remaining(),
+            },
+            OverflowMode::Waitlist => {
+                let position = queue
+                    .push_waitlist(
+                        guild_id,
+                        WaitlistEntry {
+                            lane: lane.clone(),
+                            entry,
+                        },
+                    )
+                    .await;
+                JoinOutcome::Waitlisted { lane, position }
+            }
+        });
+    }
+
+    let position = queue.push(guild_id, &lane, entry.clone()).await;
+    Ok(JoinOutcome::Joined {
+        lane,
+        entry,
+        position,
+        lottery,
+    })
+}
@@ -0,0 +1,34 @@
+#[async_trait::async_trait]
+pub trait ProductOptionRepository: Send + Sync {
+    /// List the options a guild offers, sorted by name
+    async fn list(&self, guild_id: &str) -> anyhow::Result<Vec<String>>;
+
+    /// Add an option to the guild catalog
+    /// Returns false if the option already exists
+    async fn add(&self, guild_id: &str, name: &str) -> anyhow::Result<bool>;
+
+    /// Remove an option from the guild catalog
+    /// Returns false if the option did not exist
+    async fn remove(&self, guild_id: &str, name: &str) -> anyhow::Result<bool>;
+}
+
+/// Match a comma separated list of options against a catalog.
+///
+/// Matching ignores case and surrounding whitespace, and returns the names as
+/// they are spelled in the catalog. Returns the first unknown option as an error.
+pub fn parse_options(input: &str, catalog: &[String]) -> Result<Vec<String>, String> {
+    let mut options: Vec<String> = Vec::new();
+
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let known = catalog
+            .iter()
+            .find(|option| option.to_lowercase() == part.to_lowercase())
+            .ok_or_else(|| part.to_string())?;
+
+        if !options.contains(known) {
+            options.push(known.clone());
+        }
+    }
+
+    Ok(options)
+}
@@ -1,7 +1,83 @@
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+use chrono::{DateTime, Utc};
+
+use crate::domain::{WaitlistEntry, serving::ServingPolicy};
+
+/// Lane used when no variant is picked
+pub const DEFAULT_LANE: &str = "standard";
+
+/// How people get their waffles while the queue is open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionMode {
+    /// People are served in the order the serving policy picks
+    #[default]
+    Queue,
+    /// People sign up, and the oracle draws winners before baking
+    LotterySignup,
+    /// Winners have been drawn and signup is closed
+    LotteryDrawn,
+}
+
+impl SessionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionMode::Queue => "queue",
+            SessionMode::LotterySignup => "lottery_signup",
+            SessionMode::LotteryDrawn => "lottery_drawn",
+        }
+    }
+}
+
+impl std::str::FromStr for SessionMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queue" => Ok(SessionMode::Queue),
+            "lottery_signup" => Ok(SessionMode::LotterySignup),
+            "lottery_drawn" => Ok(SessionMode::LotteryDrawn),
+            other => Err(anyhow::anyhow!("Unknown session mode: {other}")),
+        }
+    }
+}
+
+/// An open queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub mode: SessionMode,
+    pub opened_at: DateTime<Utc>,
+    /// Number of waffles there is batter for, if the oracle has said so
+    pub capacity: Option<u32>,
+    /// Channel the session was opened from, where announcements go
+    pub channel_id: Option<String>,
+    /// When `/stekt` was last used
+    pub last_baked_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    /// When something last happened in the session
+    pub fn last_activity(&self) -> DateTime<Utc> {
+        self.last_baked_at.unwrap_or(self.opened_at)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, utoipa::ToSchema)]
 pub struct QueueEntry {
     pub user_id: String,
     pub display_name: String,
+    /// Number of waffles this entry is waiting for
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+    /// Toppings and other product options picked for the order
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Number a guest without Discord is called up by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<u32>,
+}
+
+fn default_quantity() -> u32 {
+    1
 }
 
 impl QueueEntry {
@@ -9,42 +85,216 @@ impl QueueEntry {
         Self {
             user_id,
             display_name,
+            quantity: default_quantity(),
+            options: Vec::new(),
+            ticket: None,
         }
     }
+
+    /// Entry of a guest who ordered on the web instead of in Discord
+    pub fn guest(ticket: u32, display_name: String) -> Self {
+        Self {
+            ticket: Some(ticket),
+            ..Self::new(guest_user_id(ticket), display_name)
+        }
+    }
+
+    pub fn with_quantity(mut self, quantity: u32) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    pub fn with_options(mut self, options: Vec<String>) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// User id of the guest holding a ticket. It never parses as a Discord id.
+pub fn guest_user_id(ticket: u32) -> String {
+    format!("guest:{ticket}")
+}
+
+/// Resolve the lane a user asked for against the variants a guild offers.
+///
+/// No input means the default lane. Matching ignores case and returns the name as
+/// it is spelled in the guild settings, or None if the guild has no such variant.
+pub fn resolve_lane(input: Option<&str>, variants: &[String]) -> Option<String> {
+    let input = match input.map(str::trim) {
+        None | Some("") => return Some(DEFAULT_LANE.to_string()),
+        Some(input) => input.to_lowercase(),
+    };
+
+    if input == DEFAULT_LANE {
+        return Some(DEFAULT_LANE.to_string());
+    }
+
+    variants
+        .iter()
+        .find(|variant| variant.to_lowercase() == input)
+        .cloned()
+}
+
+/// Move the entry of a user to `position` in `entries`, counting from 1.
+/// Positions past the end move the entry last.
+///
+/// Returns the position the entry ended up at, or None if the user has no entry
+pub fn move_entry(entries: &mut Vec<QueueEntry>, user_id: &str, position: usize) -> Option<usize> {
+    let from = entries.iter().position(|entry| entry.user_id == user_id)?;
+    let entry = entries.remove(from);
+    let to = position.clamp(1, entries.len() + 1) - 1;
+    entries.insert(to, entry);
+    Some(to + 1)
 }
 
 #[async_trait::async_trait]
 pub trait QueueRepository: Send + Sync {
     /// Open the queue to allow new entries
-    fn open(&self, guild_id: &str);
+    fn open(&self, guild_id: &str, mode: SessionMode);
 
-    /// Close the queue to prevent new entries
+    /// Close the queue to prevent new entries, clearing every lane
     async fn close(&self, guild_id: &str);
 
     /// Check if the queue is currently open
     fn is_open(&self, guild_id: &str) -> bool;
 
-    /// Find the position of a user in the queue by user_id
+    /// Get the current session
+    /// Returns None if the queue is closed
+    fn session(&self, guild_id: &str) -> Option<Session>;
+
+    /// Get the sessions of every guild with an open queue
+    fn sessions(&self) -> Vec<(String, Session)>;
+
+    /// Get the mode of the current session
+    /// Returns None if the queue is closed
+    fn mode(&self, guild_id: &str) -> Option<SessionMode> {
+        self.session(guild_id).map(|session| session.mode)
+    }
+
+    /// Change the mode of an open session
+    fn set_mode(&self, guild_id: &str, mode: SessionMode);
+
+    /// Change the capacity of an open session
+    fn set_capacity(&self, guild_id: &str, capacity: Option<u32>);
+
+    /// Set the channel announcements for an open session are posted in
+    fn set_channel(&self, guild_id: &str, channel_id: &str);
+
+    /// Note that `/stekt` was used, so the session is not idle
+    fn mark_baked(&self, guild_id: &str);
+
+    /// Hand out the next ticket number for a guest, counting from 1 each session
+    async fn next_ticket(&self, guild_id: &str) -> u32;
+
+    /// Get the number of waffles served since the queue was opened
+    async fn served_count(&self, guild_id: &str) -> u32;
+
+    /// Get the lanes of a guild that have had entries since the queue was opened
+    async fn lanes(&self, guild_id: &str) -> Vec<String>;
+
+    /// Find the position of a user in a lane by user_id
     /// Returns None if the user is not found
-    async fn index_of(&self, guild_id: &str, user_id: &str) -> Option<usize>;
+    async fn index_of(&self, guild_id: &str, lane: &str, user_id: &str) -> Option<usize>;
+
+    /// Get the current size of a lane
+    async fn size(&self, guild_id: &str, lane: &str) -> usize;
+
+    /// Add a user to the end of a lane
+    /// Returns the new size of the lane
+    async fn push(&self, guild_id: &str, lane: &str, entry: QueueEntry) -> usize;
+
+    /// Remove the entry at the front of a lane and return it
+    /// Returns None if the lane is empty
+    async fn pop(&self, guild_id: &str, lane: &str) -> Option<QueueEntry>;
 
-    /// Get the current size of the queue
-    async fn size(&self, guild_id: &str) -> usize;
+    /// Hand out up to `n` waffles from a lane, picking entries with `policy`
+    /// An entry that wants more waffles than are left is partially served and
+    /// stays in the lane with the remainder
+    async fn pop_n(
+        &self,
+        guild_id: &str,
+        lane: &str,
+        n: usize,
+        policy: &dyn ServingPolicy,
+    ) -> Vec<QueueEntry>;
 
-    /// Add a user to the end of the queue
-    /// Returns the new size of the queue
-    async fn push(&self, guild_id: &str, entry: QueueEntry) -> usize;
+    /// Take a user out of a lane without serving them
+    /// Returns false if the user was not in it
+    async fn remove(&self, guild_id: &str, lane: &str, user_id: &str) -> bool;
 
-    /// Remove the entry at the front of the queue and return it
-    /// Returns None if the queue is empty
-    async fn pop(&self, guild_id: &str) -> Option<QueueEntry>;
+    /// Move a user to `position` in a lane, counting from 1
+    /// Returns the position the user ended up at, or None if the user was not in the lane
+    async fn move_to(
+        &self,
+        guild_id: &str,
+        lane: &str,
+        user_id: &str,
+        position: usize,
+    ) -> Option<usize>;
 
-    /// Remove up to `n` entries from the front of the queue
-    async fn pop_n(&self, guild_id: &str, n: usize) -> Vec<QueueEntry>;
+    /// Get all entries in a lane
+    async fn list(&self, guild_id: &str, lane: &str) -> Vec<QueueEntry>;
 
-    /// Get all entries in the queue
-    async fn list(&self, guild_id: &str) -> Vec<QueueEntry>;
+    /// Add an entry to the end of the waitlist
+    /// Returns the new size of the waitlist
+    async fn push_waitlist(&self, guild_id: &str, entry: WaitlistEntry) -> usize;
 
-    /// Clear the queue
-    async fn clear(&self, guild_id: &str);
+    /// Get all entries on the waitlist
+    async fn waitlist(&self, guild_id: &str) -> Vec<WaitlistEntry>;
+
+    /// Move entries from the front of the waitlist into their lanes, as long as
+    /// their waffles fit within `waffles`
+    /// Returns the entries that were moved
+    async fn promote_waitlist(&self, guild_id: &str, waffles: u32) -> Vec<WaitlistEntry>;
+
+    /// Replace the entries of a lane
+    async fn replace(&self, guild_id: &str, lane: &str, entries: Vec<QueueEntry>);
+
+    /// Clear a lane
+    async fn clear(&self, guild_id: &str, lane: &str);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(user_ids: &[&str]) -> Vec<QueueEntry> {
+        user_ids
+            .iter()
+            .map(|user_id| QueueEntry::new(user_id.to_string(), format!("{user_id} user")))
+            .collect()
+    }
+
+    #[test]
+    fn test_move_entry() {
+        let mut queue = entries(&["foo", "bar", "baz"]);
+
+        assert_eq!(move_entry(&mut queue, "baz", 1), Some(1));
+        assert_eq!(queue, entries(&["baz", "foo", "bar"]));
+
+        assert_eq!(move_entry(&mut queue, "baz", 10), Some(3));
+        assert_eq!(queue, entries(&["foo", "bar", "baz"]));
+
+        assert_eq!(move_entry(&mut queue, "bar", 0), Some(1));
+        assert_eq!(queue, entries(&["bar", "foo", "baz"]));
+
+        assert_eq!(move_entry(&mut queue, "qux", 1), None);
+        assert_eq!(queue, entries(&["bar", "foo", "baz"]));
+    }
+
+    #[test]
+    fn test_guest_entries() {
+        let guest = QueueEntry::guest(12, "Kari".to_string());
+        assert_eq!(guest.ticket, Some(12));
+        assert!(guest.user_id.parse::<u64>().is_err());
+
+        let json = serde_json::to_string(&guest).unwrap();
+        assert_eq!(serde_json::from_str::<QueueEntry>(&json).unwrap(), guest);
+
+        // Entries queued before tickets existed have none
+        let entry: QueueEntry =
+            serde_json::from_str(r#"{"user_id": "1", "display_name": "Ola"}"#).unwrap();
+        assert_eq!(entry.ticket, None);
+        assert!(!serde_json::to_string(&entry).unwrap().contains("ticket"));
+    }
 }
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    ApiTokenRepository, AuditLogRepository, DeadlineRepository, LotteryRepository, OrderRepository,
+    ProductOptionRepository, QueueRepository, ScheduleRepository, SettingsRepository,
+    TemplateRepository, UserLocaleRepository,
+};
+
+/// The repositories the adapters are built from
+#[derive(Clone)]
+pub struct Repositories {
+    pub queue: Arc<dyn QueueRepository>,
+    pub orders: Arc<dyn OrderRepository>,
+    pub settings: Arc<dyn SettingsRepository>,
+    pub products: Arc<dyn ProductOptionRepository>,
+    pub lottery: Arc<dyn LotteryRepository>,
+    pub schedules: Arc<dyn ScheduleRepository>,
+    pub deadlines: Arc<dyn DeadlineRepository>,
+    pub audit: Arc<dyn AuditLogRepository>,
+    pub user_locales: Arc<dyn UserLocaleRepository>,
+    pub templates: Arc<dyn TemplateRepository>,
+    pub api_tokens: Arc<dyn ApiTokenRepository>,
+}
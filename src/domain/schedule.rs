@@ -0,0 +1,132 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Weekday};
+
+/// A weekly time slot where the queue of a guild opens and closes by itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub id: i64,
+    pub guild_id: String,
+    /// Channel the announcements are posted in
+    pub channel_id: String,
+    pub weekday: Weekday,
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+}
+
+/// Something the scheduler should do for a schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScheduleEvent {
+    Open,
+    Close,
+}
+
+impl Schedule {
+    /// Find the event that is due at `now`, in the time zone of the schedule.
+    ///
+    /// An event is due from its time until `grace` has passed, so a tick that
+    /// runs a little late or a restart right after does not miss it.
+    pub fn due_event(&self, now: NaiveDateTime, grace: TimeDelta) -> Option<ScheduleEvent> {
+        if now.weekday() != self.weekday {
+            return None;
+        }
+
+        let time = now.time();
+        let is_due = |at: NaiveTime| time >= at && time - at < grace;
+
+        if is_due(self.closes_at) {
+            Some(ScheduleEvent::Close)
+        } else if is_due(self.opens_at) {
+            Some(ScheduleEvent::Open)
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait ScheduleRepository: Send + Sync {
+    /// List the schedules of a guild, ordered by weekday and time
+    async fn list(&self, guild_id: &str) -> anyhow::Result<Vec<Schedule>>;
+
+    /// List the schedules of every guild
+    async fn list_all(&self) -> anyhow::Result<Vec<Schedule>>;
+
+    /// Add a schedule and return its id
+    async fn add(
+        &self,
+        guild_id: &str,
+        channel_id: &str,
+        weekday: Weekday,
+        opens_at: NaiveTime,
+        closes_at: NaiveTime,
+    ) -> anyhow::Result<i64>;
+
+    /// Remove a schedule
+    /// Returns false if the guild has no schedule with that id
+    async fn remove(&self, guild_id: &str, id: i64) -> anyhow::Result<bool>;
+
+    /// List the dates the schedules of a guild are skipped, starting at `from`
+    async fn exceptions(&self, guild_id: &str, from: NaiveDate) -> anyhow::Result<Vec<NaiveDate>>;
+
+    /// Skip the schedules of a guild on a date
+    /// Returns false if the date was already skipped
+    async fn add_exception(&self, guild_id: &str, date: NaiveDate) -> anyhow::Result<bool>;
+
+    /// Stop skipping the schedules of a guild on a date
+    /// Returns false if the date was not skipped
+    async fn remove_exception(&self, guild_id: &str, date: NaiveDate) -> anyhow::Result<bool>;
+
+    /// Check if the schedules of a guild are skipped on a date
+    async fn is_exception(&self, guild_id: &str, date: NaiveDate) -> anyhow::Result<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thursday_lunch() -> Schedule {
+        Schedule {
+            id: 1,
+            guild_id: "guild".to_string(),
+            channel_id: "channel".to_string(),
+            weekday: Weekday::Thu,
+            opens_at: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            closes_at: NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+        }
+    }
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{date} {time}"), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_due_event() {
+        let schedule = thursday_lunch();
+        let grace = TimeDelta::minutes(5);
+
+        // 2026-10-22 is a Thursday
+        assert_eq!(schedule.due_event(at("2026-10-22", "11:59"), grace), None);
+        assert_eq!(
+            schedule.due_event(at("2026-10-22", "12:00"), grace),
+            Some(ScheduleEvent::Open)
+        );
+        assert_eq!(
+            schedule.due_event(at("2026-10-22", "12:04"), grace),
+            Some(ScheduleEvent::Open)
+        );
+        assert_eq!(schedule.due_event(at("2026-10-22", "12:05"), grace), None);
+        assert_eq!(
+            schedule.due_event(at("2026-10-22", "14:01"), grace),
+            Some(ScheduleEvent::Close)
+        );
+    }
+
+    #[test]
+    fn test_due_event_other_weekday() {
+        let schedule = thursday_lunch();
+
+        assert_eq!(
+            schedule.due_event(at("2026-10-23", "12:00"), TimeDelta::minutes(5)),
+            None
+        );
+    }
+}
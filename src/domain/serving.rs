@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use crate::domain::QueueEntry;
+
+/// Decides who gets the waffles when an oracle bakes a batch.
+pub trait ServingPolicy: Send + Sync {
+    /// Hand out up to `n` waffles from `entries`, which are in queue order.
+    ///
+    /// `served` holds how many waffles each user has been given this session.
+    /// Returns the served entries, each with `quantity` set to the number of
+    /// waffles it received, and the entries left in the queue in their original
+    /// order. An entry that only gets some of its waffles stays in the queue with
+    /// the rest of its order.
+    fn allocate(
+        &self,
+        entries: Vec<QueueEntry>,
+        n: usize,
+        served: &HashMap<String, u32>,
+    ) -> (Vec<QueueEntry>, Vec<QueueEntry>);
+}
+
+/// Serve the queue strictly in the order people joined.
+pub struct Fifo;
+
+impl ServingPolicy for Fifo {
+    fn allocate(
+        &self,
+        entries: Vec<QueueEntry>,
+        n: usize,
+        _served: &HashMap<String, u32>,
+    ) -> (Vec<QueueEntry>, Vec<QueueEntry>) {
+        let order: Vec<usize> = (0..entries.len()).collect();
+        allocate_in_order(entries, &order, n)
+    }
+}
+
+/// Serve people who have not had a waffle this session before people who have,
+/// keeping the queue order within each group.
+pub struct FirstTimersFirst;
+
+impl ServingPolicy for FirstTimersFirst {
+    fn allocate(
+        &self,
+        entries: Vec<QueueEntry>,
+        n: usize,
+        served: &HashMap<String, u32>,
+    ) -> (Vec<QueueEntry>, Vec<QueueEntry>) {
+        let mut order: Vec<usize> = (0..entries.len()).collect();
+        // Sorting is stable, so each group keeps its queue order
+        order.sort_by_key(|&i| served.get(&entries[i].user_id).copied().unwrap_or(0) > 0);
+        allocate_in_order(entries, &order, n)
+    }
+}
+
+/// Which serving policy a guild uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ServingMode {
+    #[default]
+    Fifo,
+    FirstTimersFirst,
+}
+
+impl ServingMode {
+    pub fn policy(&self) -> &'static dyn ServingPolicy {
+        match self {
+            ServingMode::Fifo => &Fifo,
+            ServingMode::FirstTimersFirst => &FirstTimersFirst,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServingMode::Fifo => "fifo",
+            ServingMode::FirstTimersFirst => "first_timers_first",
+        }
+    }
+}
+
+impl std::str::FromStr for ServingMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fifo" => Ok(ServingMode::Fifo),
+            "first_timers_first" => Ok(ServingMode::FirstTimersFirst),
+            other => Err(anyhow::anyhow!("Unknown serving mode: {other}")),
+        }
+    }
+}
+
+/// Hand out up to `n` waffles, visiting the entries in the order given by `order`.
+fn allocate_in_order(
+    entries: Vec<QueueEntry>,
+    order: &[usize],
+    n: usize,
+) -> (Vec<QueueEntry>, Vec<QueueEntry>) {
+    let mut given = vec![0u32; entries.len()];
+    let mut left = n;
+
+    for &i in order {
+        if left == 0 {
+            break;
+        }
+        let wanted = entries[i].quantity.max(1) as usize;
+        let amount = wanted.min(left);
+        given[i] = amount as u32;
+        left -= amount;
+    }
+
+    let served = order
+        .iter()
+        .filter(|&&i| given[i] > 0)
+        .map(|&i| entries[i].clone().with_quantity(given[i]))
+        .collect();
+
+    let remaining = entries
+        .into_iter()
+        .zip(given)
+        .filter_map(|(entry, given)| {
+            let wanted = entry.quantity.max(1);
+            (given < wanted).then(|| entry.with_quantity(wanted - given))
+        })
+        .collect();
+
+    (served, remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(user_id: &str, quantity: u32) -> QueueEntry {
+        QueueEntry::new(user_id.to_string(), format!("{user_id} user")).with_quantity(quantity)
+    }
+
+    fn served(counts: &[(&str, u32)]) -> HashMap<String, u32> {
+        counts
+            .iter()
+            .map(|(user_id, count)| (user_id.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_fifo_serves_in_order() {
+        let entries = vec![entry("foo", 1), entry("bar", 1), entry("baz", 1)];
+
+        let (baked, remaining) = Fifo.allocate(entries, 2, &served(&[("foo", 3)]));
+
+        assert_eq!(baked, vec![entry("foo", 1), entry("bar", 1)]);
+        assert_eq!(remaining, vec![entry("baz", 1)]);
+    }
+
+    #[test]
+    fn test_fifo_partially_serves_large_order() {
+        let entries = vec![entry("foo", 2), entry("bar", 3), entry("baz", 1)];
+
+        let (baked, remaining) = Fifo.allocate(entries, 3, &HashMap::new());
+
+        assert_eq!(baked, vec![entry("foo", 2), entry("bar", 1)]);
+        assert_eq!(remaining, vec![entry("bar", 2), entry("baz", 1)]);
+    }
+
+    #[test]
+    fn test_fifo_with_more_waffles_than_orders() {
+        let entries = vec![entry("foo", 1), entry("bar", 2)];
+
+        let (baked, remaining) = Fifo.allocate(entries.clone(), 10, &HashMap::new());
+
+        assert_eq!(baked, entries);
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn test_first_timers_are_served_first() {
+        let entries = vec![entry("foo", 1), entry("bar", 1), entry("baz", 1)];
+
+        let (baked, remaining) = FirstTimersFirst.allocate(entries, 2, &served(&[("foo", 1)]));
+
+        assert_eq!(baked, vec![entry("bar", 1), entry("baz", 1)]);
+        assert_eq!(remaining, vec![entry("foo", 1)]);
+    }
+
+    #[test]
+    fn test_first_timers_keep_queue_order_within_groups() {
+        let entries = vec![
+            entry("foo", 1),
+            entry("bar", 1),
+            entry("baz", 1),
+            entry("qux", 1),
+        ];
+
+        let (baked, remaining) =
+            FirstTimersFirst.allocate(entries, 3, &served(&[("foo", 2), ("baz", 1)]));
+
+        assert_eq!(
+            baked,
+            vec![entry("bar", 1), entry("qux", 1), entry("foo", 1)]
+        );
+        assert_eq!(remaining, vec![entry("baz", 1)]);
+    }
+
+    #[test]
+    fn test_first_timers_partial_order_keeps_its_place() {
+        let entries = vec![entry("foo", 1), entry("bar", 3)];
+
+        let (baked, remaining) = FirstTimersFirst.allocate(entries, 2, &served(&[("foo", 1)]));
+
+        assert_eq!(baked, vec![entry("bar", 2)]);
+        assert_eq!(remaining, vec![entry("foo", 1), entry("bar", 1)]);
+    }
+
+    #[test]
+    fn test_serving_mode_round_trips() {
+        for mode in [ServingMode::Fifo, ServingMode::FirstTimersFirst] {
+            assert_eq!(mode.as_str().parse::<ServingMode>().unwrap(), mode);
+        }
+        assert!("lottery".parse::<ServingMode>().is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use tracing::error;
+
+use crate::domain::order::local_day_start;
+use crate::domain::queue::resolve_lane;
+use crate::domain::{
+    DailyStats, DeadlineRepository, GuildSettings, OrderRepository, QueueEntry, QueueEvent,
+    QueueRepository, Session, SessionMode,
+};
+
+/// What is left of a session after it was closed
+pub struct ClosedSession {
+    /// Stats for the day, or None if they could not be fetched
+    pub stats: Option<DailyStats>,
+}
+
+/// Open the queue of a guild, announcing things about it in `channel_id`
+/// Returns false if the queue was already open
+pub fn open_session(
+    queue: &dyn QueueRepository,
+    guild_id: &str,
+    channel_id: &str,
+    mode: SessionMode,
+    capacity: Option<u32>,
+) -> bool {
+    if queue.is_open(guild_id) {
+        return false;
+    }
+
+    queue.open(guild_id, mode);
+    queue.set_channel(guild_id, channel_id);
+    if capacity.is_some() {
+        queue.set_capacity(guild_id, capacity);
+    }
+    true
+}
+
+/// Close the queue of a guild, drop its deadline and fetch the stats of the
+/// day in `timezone`
+/// Returns None if the queue was already closed
+pub async fn close_session(
+    queue: &dyn QueueRepository,
+    orders: &dyn OrderRepository,
+    deadlines: &dyn DeadlineRepository,
+    guild_id: &str,
+    timezone: &Tz,
+) -> Option<ClosedSession> {
+    if let Err(e) = deadlines.remove(guild_id).await {
+        error!(guild_id, error = ?e, "Failed to remove session deadline");
+    }
+
+    if !queue.is_open(guild_id) {
+        return None;
+    }
+
+    queue.close(guild_id).await;
+
+    let day_start = local_day_start(Utc::now(), timezone);
+    let stats = match orders.daily_stats(guild_id, day_start).await {
+        Ok(stats) => Some(stats),
+        Err(e) => {
+            error!(guild_id, error = ?e, "Failed to fetch daily stats");
+            None
+        }
+    };
+
+    Some(ClosedSession { stats })
+}
+
+/// Why a batch could not be baked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BakeError {
+    /// The queue is closed
+    Closed,
+    /// Winners of the lottery have not been drawn yet
+    NotDrawn,
+    /// The guild has no variant with this name
+    UnknownVariant(String),
+}
+
+/// Waffles handed out from one batch
+pub struct Batch {
+    pub lane: String,
+    /// Who got waffles, each with `quantity` set to how many they got
+    pub baked: Vec<QueueEntry>,
+    pub waffles: u32,
+}
+
+impl Batch {
+    /// Event telling subscribers who got waffles
+    pub fn event(&self) -> QueueEvent {
+        QueueEvent::Baked {
+            lane: self.lane.clone(),
+            waffles: self.waffles,
+            baked: self.baked.clone(),
+        }
+    }
+}
+
+/// Hand out `amount` freshly baked waffles from the lane of `variant` and
+/// record who got them
+pub async fn bake(
+    queue: &dyn QueueRepository,
+    orders: &dyn OrderRepository,
+    settings: &GuildSettings,
+    guild_id: &str,
+    variant: Option<&str>,
+    amount: usize,
+) -> Result<Batch, BakeError> {
+    match queue.mode(guild_id) {
+        None => return Err(BakeError::Closed),
+        Some(SessionMode::LotterySignup) => return Err(BakeError::NotDrawn),
+        Some(_) => {}
+    }
+
+    let Some(lane) = resolve_lane(variant, &settings.variants) else {
+        return Err(BakeError::UnknownVariant(
+            variant.unwrap_or_default().to_string(),
+        ));
+    };
+
+    queue.mark_baked(guild_id);
+
+    let baked = queue
+        .pop_n(guild_id, &lane, amount, settings.serving_mode.policy())
+        .await;
+    let waffles = baked.iter().map(|e| e.quantity).sum();
+
+    if let Err(e) = orders.record_orders(&baked, guild_id, &lane).await {
+        error!(guild_id, error = ?e, "Failed to record orders");
+    }
+
+    Ok(Batch {
+        lane,
+        baked,
+        waffles,
+    })
+}
+
+/// Whether a session has gone `timeout_minutes` without anything being baked.
+/// Lottery signup is never idle, since nothing can be baked until the draw.
+pub fn is_idle(session: &Session, timeout_minutes: u32, now: DateTime<Utc>) -> bool {
+    session.mode != SessionMode::LotterySignup
+        && now - session.last_activity() >= TimeDelta::minutes(timeout_minutes.into())
+}
+
+/// Average time it has taken to bake each of the `served` waffles of a
+/// session, up to the last batch. None until something has been baked.
+pub fn time_per_waffle(session: &Session, served: u32) -> Option<TimeDelta> {
+    let last_baked_at = session.last_baked_at?;
+    if served == 0 {
+        return None;
+    }
+    Some((last_baked_at - session.opened_at) / served as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(opened_at: &str, last_baked_at: Option<&str>) -> Session {
+        Session {
+            mode: SessionMode::Queue,
+            opened_at: opened_at.parse().unwrap(),
+            capacity: None,
+            channel_id: None,
+            last_baked_at: last_baked_at.map(|at| at.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_idle_since_opening() {
+        let session = session("2026-10-22T10:00:00Z", None);
+
+        assert!(!is_idle(
+            &session,
+            60,
+            "2026-10-22T10:59:00Z".parse().unwrap()
+        ));
+        assert!(is_idle(
+            &session,
+            60,
+            "2026-10-22T11:00:00Z".parse().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_baking_resets_idle_time() {
+        let session = session("2026-10-22T10:00:00Z", Some("2026-10-22T10:45:00Z"));
+
+        assert!(!is_idle(
+            &session,
+            60,
+            "2026-10-22T11:30:00Z".parse().unwrap()
+        ));
+        assert!(is_idle(
+            &session,
+            60,
+            "2026-10-22T11:45:00Z".parse().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_lottery_signup_is_never_idle() {
+        let mut session = session("2026-10-22T10:00:00Z", None);
+        session.mode = SessionMode::LotterySignup;
+
+        assert!(!is_idle(
+            &session,
+            60,
+            "2026-10-22T14:00:00Z".parse().unwrap()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_variant_is_not_a_bake() {
+        let repositories = crate::infrastructure::in_memory::repositories();
+        repositories.queue.open("1", SessionMode::Queue);
+
+        let result = bake(
+            repositories.queue.as_ref(),
+            repositories.orders.as_ref(),
+            &GuildSettings::default(),
+            "1",
+            Some("sjokolade"),
+            1,
+        )
+        .await;
+
+        assert!(matches!(result, Err(BakeError::UnknownVariant(_))));
+        assert_eq!(repositories.queue.session("1").unwrap().last_baked_at, None);
+    }
+
+    #[test]
+    fn test_time_per_waffle() {
+        let baked = session("2026-10-22T10:00:00Z", Some("2026-10-22T10:30:00Z"));
+        let fresh = session("2026-10-22T10:00:00Z", None);
+
+        assert_eq!(time_per_waffle(&baked, 10), Some(TimeDelta::minutes(3)));
+        assert_eq!(time_per_waffle(&baked, 0), None);
+        assert_eq!(time_per_waffle(&fresh, 10), None);
+    }
+}
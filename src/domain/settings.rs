@@ -0,0 +1,67 @@
+use crate::domain::{LimitPeriod, Locale, OverflowMode, ServingMode, queue::DEFAULT_LANE};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuildSettings {
+    /// Maximum number of waffles a single `/vaffel` order can ask for
+    pub max_waffles_per_order: u32,
+    /// Named lanes served separately from the default one, e.g. "glutenfri"
+    pub variants: Vec<String>,
+    /// How `/stekt` picks who gets the waffles
+    pub serving_mode: ServingMode,
+    /// Maximum number of waffles one user can get per `limit_period`
+    pub max_waffles_per_user: Option<u32>,
+    pub limit_period: LimitPeriod,
+    /// Minutes a user has to wait after being served before joining again
+    pub rejoin_cooldown_minutes: Option<u32>,
+    /// What happens to orders when the session is out of capacity
+    pub overflow_mode: OverflowMode,
+    /// Minutes without `/stekt` before an open queue is closed by itself,
+    /// None to leave it open
+    pub idle_close_minutes: Option<u32>,
+    /// Channel `/vaffel` and `/kø` have to be used in, and where mentions go
+    pub ordering_channel_id: Option<String>,
+    /// Channel the oracle commands have to be used in
+    pub oracle_channel_id: Option<String>,
+    /// Channel privileged actions are logged in
+    pub audit_channel_id: Option<String>,
+    /// Language used for replies and announcements, unless a user picks another
+    pub locale: Locale,
+}
+
+impl GuildSettings {
+    /// All lanes of the guild, starting with the default lane
+    pub fn lanes(&self) -> Vec<String> {
+        std::iter::once(DEFAULT_LANE.to_string())
+            .chain(self.variants.iter().cloned())
+            .collect()
+    }
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            max_waffles_per_order: 1,
+            variants: Vec::new(),
+            serving_mode: ServingMode::default(),
+            max_waffles_per_user: None,
+            limit_period: LimitPeriod::default(),
+            rejoin_cooldown_minutes: None,
+            overflow_mode: OverflowMode::default(),
+            idle_close_minutes: None,
+            ordering_channel_id: None,
+            oracle_channel_id: None,
+            audit_channel_id: None,
+            locale: Locale::default(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait SettingsRepository: Send + Sync {
+    /// Get the settings for a guild
+    /// Returns the defaults if the guild has not configured anything
+    async fn get(&self, guild_id: &str) -> anyhow::Result<GuildSettings>;
+
+    /// Store the settings for a guild
+    async fn save(&self, guild_id: &str, settings: &GuildSettings) -> anyhow::Result<()>;
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+/// Longest template a guild can save, so the rendered message stays well
+/// under the message limit of Discord
+pub const MAX_TEMPLATE_LENGTH: usize = 1000;
+
+/// Messages a guild can change the wording of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TemplateKind {
+    /// Ordering is opened as a queue
+    Opened,
+    /// Signup for the lottery is opened
+    OpenedLottery,
+    /// Ordering closes by itself soon
+    ClosingSoon,
+    /// Ordering is closed, before the stats of the day
+    Closed,
+    /// Waffles are baked, pinging who gets them
+    Baked,
+    /// Someone joined the queue
+    Joined,
+}
+
+impl TemplateKind {
+    pub const ALL: [TemplateKind; 6] = [
+        TemplateKind::Opened,
+        TemplateKind::OpenedLottery,
+        TemplateKind::ClosingSoon,
+        TemplateKind::Closed,
+        TemplateKind::Baked,
+        TemplateKind::Joined,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateKind::Opened => "opened",
+            TemplateKind::OpenedLottery => "opened_lottery",
+            TemplateKind::ClosingSoon => "closing_soon",
+            TemplateKind::Closed => "closed",
+            TemplateKind::Baked => "baked",
+            TemplateKind::Joined => "joined",
+        }
+    }
+
+    /// Placeholders the template of this message can use
+    pub fn placeholders(&self) -> &'static [&'static str] {
+        match self {
+            TemplateKind::Opened | TemplateKind::OpenedLottery | TemplateKind::Closed => &[],
+            TemplateKind::ClosingSoon => &["time"],
+            TemplateKind::Baked => &["count", "mentions"],
+            TemplateKind::Joined => &["count", "position", "queue"],
+        }
+    }
+}
+
+impl std::str::FromStr for TemplateKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "opened" => Ok(TemplateKind::Opened),
+            "opened_lottery" => Ok(TemplateKind::OpenedLottery),
+            "closing_soon" => Ok(TemplateKind::ClosingSoon),
+            "closed" => Ok(TemplateKind::Closed),
+            "baked" => Ok(TemplateKind::Baked),
+            "joined" => Ok(TemplateKind::Joined),
+            other => Err(anyhow::anyhow!("Unknown template: {other}")),
+        }
+    }
+}
+
+/// Why a template can not be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    Empty,
+    TooLong,
+    /// A `{` without a matching `}`
+    Unclosed,
+    /// A `}` without a matching `{`. Use `}}` for a literal brace
+    UnopenedBrace,
+    /// A placeholder the message does not have
+    UnknownPlaceholder(String),
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a template into text and `{name}` placeholders.
+/// `{{` and `}}` are literal braces.
+fn parse(template: &str) -> Result<Vec<Part<'_>>, TemplateError> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(i) = rest.find(['{', '}']) {
+        if i > 0 {
+            parts.push(Part::Text(&rest[..i]));
+        }
+        let brace = &rest[i..i + 1];
+        rest = &rest[i + 1..];
+
+        if rest.starts_with(brace) {
+            parts.push(Part::Text(brace));
+            rest = &rest[1..];
+        } else if brace == "}" {
+            return Err(TemplateError::UnopenedBrace);
+        } else {
+            let end = rest.find('}').ok_or(TemplateError::Unclosed)?;
+            parts.push(Part::Placeholder(rest[..end].trim()));
+            rest = &rest[end + 1..];
+        }
+    }
+
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    Ok(parts)
+}
+
+/// Check that a template can be saved for a message
+pub fn validate(kind: TemplateKind, template: &str) -> Result<(), TemplateError> {
+    if template.trim().is_empty() {
+        return Err(TemplateError::Empty);
+    }
+    if template.chars().count() > MAX_TEMPLATE_LENGTH {
+        return Err(TemplateError::TooLong);
+    }
+
+    for part in parse(template)? {
+        if let Part::Placeholder(name) = part
+            && !kind.placeholders().contains(&name)
+        {
+            return Err(TemplateError::UnknownPlaceholder(name.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Fill in the placeholders of a template.
+///
+/// Templates are validated when saved, so a broken one is shown as is
+/// rather than failing the message.
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let Ok(parts) = parse(template) else {
+        return template.to_string();
+    };
+
+    parts
+        .into_iter()
+        .map(|part| match part {
+            Part::Text(text) => text,
+            Part::Placeholder(name) => values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+pub trait TemplateRepository: Send + Sync {
+    /// Get the templates a guild has changed
+    async fn list(&self, guild_id: &str) -> anyhow::Result<HashMap<TemplateKind, String>>;
+
+    /// Change the template of a message
+    async fn set(&self, guild_id: &str, kind: TemplateKind, template: &str) -> anyhow::Result<()>;
+
+    /// Go back to the standard wording of a message
+    /// Returns false if the guild had not changed it
+    async fn remove(&self, guild_id: &str, kind: TemplateKind) -> anyhow::Result<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_fills_in_placeholders() {
+        let message = render(
+            "🧇 {count} vafler til {mentions}! {{ikke {count}}}",
+            &[("count", "3"), ("mentions", "@Kari")],
+        );
+        assert_eq!(message, "🧇 3 vafler til @Kari! {ikke 3}");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            validate(TemplateKind::Baked, "{ count } til {mentions}"),
+            Ok(())
+        );
+        assert_eq!(
+            validate(TemplateKind::Opened, "  "),
+            Err(TemplateError::Empty)
+        );
+        assert_eq!(
+            validate(TemplateKind::Opened, "Åpent {count}"),
+            Err(TemplateError::UnknownPlaceholder("count".to_string()))
+        );
+        assert_eq!(
+            validate(TemplateKind::Joined, "Nummer {position"),
+            Err(TemplateError::Unclosed)
+        );
+        assert_eq!(
+            validate(TemplateKind::Joined, "Nummer position}"),
+            Err(TemplateError::UnopenedBrace)
+        );
+        assert_eq!(
+            validate(TemplateKind::Closed, &"x".repeat(MAX_TEMPLATE_LENGTH + 1)),
+            Err(TemplateError::TooLong)
+        );
+    }
+}
@@ -0,0 +1,528 @@
+//! Repositories kept in memory, so tests can run the domain and the adapters
+//! without Redis or PostgreSQL.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+
+use crate::domain::{
+    ApiToken, ApiTokenRepository, AuditEntry, AuditLogRepository, DailyStats, DeadlineRepository,
+    GuildSettings, Locale, LotteryDraw, LotteryRepository, OrderRecord, OrderRepository,
+    PeriodStats, ProductOptionRepository, QueueEntry, QueueRepository, Repositories, Schedule,
+    ScheduleRepository, ServingPolicy, Session, SessionDeadline, SessionMode, SettingsRepository,
+    TemplateKind, TemplateRepository, UserLocaleRepository, WaitlistEntry, queue::move_entry,
+};
+
+/// Repositories for one test, with nothing stored in them
+pub fn repositories() -> Repositories {
+    let empty = Arc::new(Empty);
+    Repositories {
+        queue: Arc::new(InMemoryQueueRepository::default()),
+        orders: empty.clone(),
+        settings: Arc::new(InMemorySettingsRepository::default()),
+        products: empty.clone(),
+        lottery: empty.clone(),
+        schedules: empty.clone(),
+        deadlines: Arc::new(InMemoryDeadlineRepository::default()),
+        audit: empty.clone(),
+        user_locales: empty.clone(),
+        templates: empty,
+        api_tokens: Arc::new(InMemoryApiTokenRepository::default()),
+    }
+}
+
+#[derive(Default)]
+struct GuildQueue {
+    session: Option<Session>,
+    lanes: HashMap<String, Vec<QueueEntry>>,
+    waitlist: Vec<WaitlistEntry>,
+    served: HashMap<String, u32>,
+    tickets: u32,
+}
+
+#[derive(Default)]
+pub struct InMemoryQueueRepository {
+    guilds: Mutex<HashMap<String, GuildQueue>>,
+}
+
+impl InMemoryQueueRepository {
+    fn with_guild<T>(&self, guild_id: &str, f: impl FnOnce(&mut GuildQueue) -> T) -> T {
+        let mut guilds = self.guilds.lock().unwrap();
+        f(guilds.entry(guild_id.to_string()).or_default())
+    }
+
+    fn with_session(&self, guild_id: &str, f: impl FnOnce(&mut Session)) {
+        self.with_guild(guild_id, |guild| {
+            if let Some(session) = guild.session.as_mut() {
+                f(session);
+            }
+        });
+    }
+}
+
+#[async_trait::async_trait]
+impl QueueRepository for InMemoryQueueRepository {
+    fn open(&self, guild_id: &str, mode: SessionMode) {
+        self.with_guild(guild_id, |guild| {
+            guild.session = Some(Session {
+                mode,
+                opened_at: Utc::now(),
+                capacity: None,
+                channel_id: None,
+                last_baked_at: None,
+            });
+        });
+    }
+
+    async fn close(&self, guild_id: &str) {
+        self.with_guild(guild_id, |guild| *guild = GuildQueue::default());
+    }
+
+    fn is_open(&self, guild_id: &str) -> bool {
+        self.session(guild_id).is_some()
+    }
+
+    fn session(&self, guild_id: &str) -> Option<Session> {
+        self.with_guild(guild_id, |guild| guild.session.clone())
+    }
+
+    fn sessions(&self) -> Vec<(String, Session)> {
+        let guilds = self.guilds.lock().unwrap();
+        guilds
+            .iter()
+            .filter_map(|(id, guild)| Some((id.clone(), guild.session.clone()?)))
+            .collect()
+    }
+
+    fn set_mode(&self, guild_id: &str, mode: SessionMode) {
+        self.with_session(guild_id, |session| session.mode = mode);
+    }
+
+    fn set_capacity(&self, guild_id: &str, capacity: Option<u32>) {
+        self.with_session(guild_id, |session| session.capacity = capacity);
+    }
+
+    fn set_channel(&self, guild_id: &str, channel_id: &str) {
+        self.with_session(guild_id, |session| {
+            session.channel_id = Some(channel_id.to_string())
+        });
+    }
+
+    fn mark_baked(&self, guild_id: &str) {
+        self.with_session(guild_id, |session| session.last_baked_at = Some(Utc::now()));
+    }
+
+    async fn next_ticket(&self, guild_id: &str) -> u32 {
+        self.with_guild(guild_id, |guild| {
+            guild.tickets += 1;
+            guild.tickets
+        })
+    }
+
+    async fn served_count(&self, guild_id: &str) -> u32 {
+        self.with_guild(guild_id, |guild| guild.served.values().sum())
+    }
+
+    async fn lanes(&self, guild_id: &str) -> Vec<String> {
+        self.with_guild(guild_id, |guild| {
+            let mut lanes: Vec<String> = guild.lanes.keys().cloned().collect();
+            lanes.sort();
+            lanes
+        })
+    }
+
+    async fn index_of(&self, guild_id: &str, lane: &str, user_id: &str) -> Option<usize> {
+        self.list(guild_id, lane)
+            .await
+            .iter()
+            .position(|entry| entry.user_id == user_id)
+    }
+
+    async fn size(&self, guild_id: &str, lane: &str) -> usize {
+        self.list(guild_id, lane).await.len()
+    }
+
+    async fn push(&self, guild_id: &str, lane: &str, entry: QueueEntry) -> usize {
+        self.with_guild(guild_id, |guild| {
+            let entries = guild.lanes.entry(lane.to_string()).or_default();
+            entries.push(entry);
+            entries.len()
+        })
+    }
+
+    async fn pop(&self, guild_id: &str, lane: &str) -> Option<QueueEntry> {
+        self.with_guild(guild_id, |guild| {
+            let entries = guild.lanes.get_mut(lane).filter(|e| !e.is_empty())?;
+            Some(entries.remove(0))
+        })
+    }
+
+    async fn pop_n(
+        &self,
+        guild_id: &str,
+        lane: &str,
+        n: usize,
+        policy: &dyn ServingPolicy,
+    ) -> Vec<QueueEntry> {
+        self.with_guild(guild_id, |guild| {
+            let entries = guild.lanes.remove(lane).unwrap_or_default();
+            let (served, remaining) = policy.allocate(entries, n, &guild.served);
+            for entry in &served {
+                *guild.served.entry(entry.user_id.clone()).or_default() += entry.quantity;
+            }
+            guild.lanes.insert(lane.to_string(), remaining);
+            served
+        })
+    }
+
+    async fn remove(&self, guild_id: &str, lane: &str, user_id: &str) -> bool {
+        self.with_guild(guild_id, |guild| {
+            let Some(entries) = guild.lanes.get_mut(lane) else {
+                return false;
+            };
+            let before = entries.len();
+            entries.retain(|entry| entry.user_id != user_id);
+            entries.len() != before
+        })
+    }
+
+    async fn move_to(
+        &self,
+        guild_id: &str,
+        lane: &str,
+        user_id: &str,
+        position: usize,
+    ) -> Option<usize> {
+        self.with_guild(guild_id, |guild| {
+            move_entry(guild.lanes.get_mut(lane)?, user_id, position)
+        })
+    }
+
+    async fn list(&self, guild_id: &str, lane: &str) -> Vec<QueueEntry> {
+        self.with_guild(guild_id, |guild| {
+            guild.lanes.get(lane).cloned().unwrap_or_default()
+        })
+    }
+
+    async fn push_waitlist(&self, guild_id: &str, entry: WaitlistEntry) -> usize {
+        self.with_guild(guild_id, |guild| {
+            guild.waitlist.push(entry);
+            guild.waitlist.len()
+        })
+    }
+
+    async fn waitlist(&self, guild_id: &str) -> Vec<WaitlistEntry> {
+        self.with_guild(guild_id, |guild| guild.waitlist.clone())
+    }
+
+    async fn promote_waitlist(&self, guild_id: &str, waffles: u32) -> Vec<WaitlistEntry> {
+        self.with_guild(guild_id, |guild| {
+            let mut left = waffles;
+            let mut promoted = Vec::new();
+            while let Some(waiting) = guild.waitlist.first() {
+                if waiting.entry.quantity > left {
+                    break;
+                }
+                let waiting = guild.waitlist.remove(0);
+                left -= waiting.entry.quantity;
+                guild
+                    .lanes
+                    .entry(waiting.lane.clone())
+                    .or_default()
+                    .push(waiting.entry.clone());
+                promoted.push(waiting);
+            }
+            promoted
+        })
+    }
+
+    async fn replace(&self, guild_id: &str, lane: &str, entries: Vec<QueueEntry>) {
+        self.with_guild(guild_id, |guild| {
+            guild.lanes.insert(lane.to_string(), entries);
+        });
+    }
+
+    async fn clear(&self, guild_id: &str, lane: &str) {
+        self.with_guild(guild_id, |guild| {
+            guild.lanes.remove(lane);
+        });
+    }
+}
+
+#[derive(Default)]
+pub struct InMemorySettingsRepository {
+    settings: Mutex<HashMap<String, GuildSettings>>,
+}
+
+#[async_trait::async_trait]
+impl SettingsRepository for InMemorySettingsRepository {
+    async fn get(&self, guild_id: &str) -> anyhow::Result<GuildSettings> {
+        let settings = self.settings.lock().unwrap();
+        Ok(settings.get(guild_id).cloned().unwrap_or_default())
+    }
+
+    async fn save(&self, guild_id: &str, settings: &GuildSettings) -> anyhow::Result<()> {
+        self.settings
+            .lock()
+            .unwrap()
+            .insert(guild_id.to_string(), settings.clone());
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryDeadlineRepository {
+    deadlines: Mutex<HashMap<String, SessionDeadline>>,
+}
+
+#[async_trait::async_trait]
+impl DeadlineRepository for InMemoryDeadlineRepository {
+    async fn set(&self, deadline: &SessionDeadline) -> anyhow::Result<()> {
+        self.deadlines
+            .lock()
+            .unwrap()
+            .insert(deadline.guild_id.clone(), deadline.clone());
+        Ok(())
+    }
+
+    async fn get(&self, guild_id: &str) -> anyhow::Result<Option<SessionDeadline>> {
+        Ok(self.deadlines.lock().unwrap().get(guild_id).cloned())
+    }
+
+    async fn list_all(&self) -> anyhow::Result<Vec<SessionDeadline>> {
+        Ok(self.deadlines.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn mark_reminded(&self, guild_id: &str) -> anyhow::Result<()> {
+        if let Some(deadline) = self.deadlines.lock().unwrap().get_mut(guild_id) {
+            deadline.reminded = true;
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, guild_id: &str) -> anyhow::Result<()> {
+        self.deadlines.lock().unwrap().remove(guild_id);
+        Ok(())
+    }
+}
+
+/// Tokens by their hash
+#[derive(Default)]
+pub struct InMemoryApiTokenRepository {
+    tokens: Mutex<Vec<(String, ApiToken)>>,
+}
+
+#[async_trait::async_trait]
+impl ApiTokenRepository for InMemoryApiTokenRepository {
+    async fn create(&self, guild_id: &str, label: &str, token_hash: &str) -> anyhow::Result<i64> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let id = tokens.len() as i64 + 1;
+        tokens.push((
+            token_hash.to_string(),
+            ApiToken {
+                id,
+                guild_id: guild_id.to_string(),
+                label: label.to_string(),
+                created_at: Utc::now(),
+            },
+        ));
+        Ok(id)
+    }
+
+    async fn list(&self, guild_id: &str) -> anyhow::Result<Vec<ApiToken>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .map(|(_, token)| token)
+            .filter(|token| token.guild_id == guild_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .find(|(hash, _)| hash == token_hash)
+            .map(|(_, token)| token.clone()))
+    }
+
+    async fn revoke(&self, guild_id: &str, id: i64) -> anyhow::Result<bool> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let before = tokens.len();
+        tokens.retain(|(_, token)| !(token.guild_id == guild_id && token.id == id));
+        Ok(tokens.len() != before)
+    }
+}
+
+/// Stores nothing, for the repositories a test does not look at
+pub struct Empty;
+
+#[async_trait::async_trait]
+impl OrderRepository for Empty {
+    async fn record_orders(
+        &self,
+        _entries: &[QueueEntry],
+        _guild_id: &str,
+        _lane: &str,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn daily_stats(
+        &self,
+        _guild_id: &str,
+        _day_start: DateTime<Utc>,
+    ) -> anyhow::Result<DailyStats> {
+        Ok(DailyStats {
+            total_orders: 0,
+            top_users: vec![],
+            lanes: vec![],
+        })
+    }
+
+    async fn waffles_since(
+        &self,
+        _guild_id: &str,
+        _user_id: &str,
+        _since: DateTime<Utc>,
+    ) -> anyhow::Result<i64> {
+        Ok(0)
+    }
+
+    async fn last_served_at(
+        &self,
+        _guild_id: &str,
+        _user_id: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        Ok(None)
+    }
+
+    async fn stats_since(
+        &self,
+        _guild_id: &str,
+        _since: Option<DateTime<Utc>>,
+        _top: i64,
+    ) -> anyhow::Result<PeriodStats> {
+        Ok(PeriodStats {
+            total: 0,
+            top_users: vec![],
+        })
+    }
+
+    async fn list_orders(
+        &self,
+        _guild_id: &str,
+        _from: Option<DateTime<Utc>>,
+        _to: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Vec<OrderRecord>> {
+        Ok(vec![])
+    }
+}
+
+#[async_trait::async_trait]
+impl ProductOptionRepository for Empty {
+    async fn list(&self, _guild_id: &str) -> anyhow::Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    async fn add(&self, _guild_id: &str, _name: &str) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
+    async fn remove(&self, _guild_id: &str, _name: &str) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
+#[async_trait::async_trait]
+impl LotteryRepository for Empty {
+    async fn record_draw(&self, _draw: &LotteryDraw) -> anyhow::Result<i64> {
+        Ok(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl ScheduleRepository for Empty {
+    async fn list(&self, _guild_id: &str) -> anyhow::Result<Vec<Schedule>> {
+        Ok(vec![])
+    }
+
+    async fn list_all(&self) -> anyhow::Result<Vec<Schedule>> {
+        Ok(vec![])
+    }
+
+    async fn add(
+        &self,
+        _guild_id: &str,
+        _channel_id: &str,
+        _weekday: Weekday,
+        _opens_at: NaiveTime,
+        _closes_at: NaiveTime,
+    ) -> anyhow::Result<i64> {
+        Ok(1)
+    }
+
+    async fn remove(&self, _guild_id: &str, _id: i64) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    async fn exceptions(
+        &self,
+        _guild_id: &str,
+        _from: NaiveDate,
+    ) -> anyhow::Result<Vec<NaiveDate>> {
+        Ok(vec![])
+    }
+
+    async fn add_exception(&self, _guild_id: &str, _date: NaiveDate) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
+    async fn remove_exception(&self, _guild_id: &str, _date: NaiveDate) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    async fn is_exception(&self, _guild_id: &str, _date: NaiveDate) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogRepository for Empty {
+    async fn record(&self, _entry: &AuditEntry) -> anyhow::Result<i64> {
+        Ok(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl UserLocaleRepository for Empty {
+    async fn get(&self, _user_id: &str) -> anyhow::Result<Option<Locale>> {
+        Ok(None)
+    }
+
+    async fn set(&self, _user_id: &str, _locale: Option<Locale>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TemplateRepository for Empty {
+    async fn list(&self, _guild_id: &str) -> anyhow::Result<HashMap<TemplateKind, String>> {
+        Ok(HashMap::new())
+    }
+
+    async fn set(
+        &self,
+        _guild_id: &str,
+        _kind: TemplateKind,
+        _template: &str,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn remove(&self, _guild_id: &str, _kind: TemplateKind) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
@@ -1,5 +1,29 @@
+#[cfg(test)]
+pub mod in_memory;
+pub mod postgres_api_token_repository;
+pub mod postgres_audit_log_repository;
+pub mod postgres_deadline_repository;
+pub mod postgres_health_check;
+pub mod postgres_lottery_repository;
 pub mod postgres_order_repository;
+pub mod postgres_product_option_repository;
+pub mod postgres_schedule_repository;
+pub mod postgres_settings_repository;
+pub mod postgres_template_repository;
+pub mod postgres_user_locale_repository;
+pub mod redis_health_check;
 pub mod redis_queue_repository;
 
+pub use postgres_api_token_repository::PostgresApiTokenRepository;
+pub use postgres_audit_log_repository::PostgresAuditLogRepository;
+pub use postgres_deadline_repository::PostgresDeadlineRepository;
+pub use postgres_health_check::PostgresHealthCheck;
+pub use postgres_lottery_repository::PostgresLotteryRepository;
 pub use postgres_order_repository::PostgresOrderRepository;
+pub use postgres_product_option_repository::PostgresProductOptionRepository;
+pub use postgres_schedule_repository::PostgresScheduleRepository;
+pub use postgres_settings_repository::PostgresSettingsRepository;
+pub use postgres_template_repository::PostgresTemplateRepository;
+pub use postgres_user_locale_repository::PostgresUserLocaleRepository;
+pub use redis_health_check::RedisHealthCheck;
 pub use redis_queue_repository::RedisQueueRepository;
@@ -0,0 +1,104 @@
+use sqlx::PgPool;
+use tracing::{debug, error, info, instrument};
+
+use crate::domain::{ApiToken, ApiTokenRepository};
+use crate::telemetry::Timer;
+
+pub struct PostgresApiTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresApiTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiTokenRepository for PostgresApiTokenRepository {
+    #[instrument(skip(self, token_hash), fields(guild_id))]
+    async fn create(&self, guild_id: &str, label: &str, token_hash: &str) -> anyhow::Result<i64> {
+        let _timer = Timer::postgres("api_token.create");
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO api_tokens (guild_id, label, token_hash)
+            VALUES ($1, $2, $3)
+            RETURNING id
+            "#,
+            guild_id,
+            label,
+            token_hash
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to create API token");
+            e
+        })?;
+
+        info!(guild_id, id, "Created API token");
+        Ok(id)
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn list(&self, guild_id: &str) -> anyhow::Result<Vec<ApiToken>> {
+        let _timer = Timer::postgres("api_token.list");
+        let tokens = sqlx::query_as!(
+            ApiToken,
+            r#"
+            SELECT id, guild_id, label, created_at
+            FROM api_tokens
+            WHERE guild_id = $1
+            ORDER BY id
+            "#,
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch API tokens");
+            e
+        })?;
+
+        debug!(guild_id, count = tokens.len(), "Retrieved API tokens");
+        Ok(tokens)
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn find(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>> {
+        let _timer = Timer::postgres("api_token.find");
+        let token = sqlx::query_as!(
+            ApiToken,
+            "SELECT id, guild_id, label, created_at FROM api_tokens WHERE token_hash = $1",
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Failed to look up API token");
+            e
+        })?;
+
+        Ok(token)
+    }
+
+    #[instrument(skip(self), fields(guild_id, id))]
+    async fn revoke(&self, guild_id: &str, id: i64) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("api_token.revoke");
+        let result = sqlx::query!(
+            "DELETE FROM api_tokens WHERE guild_id = $1 AND id = $2",
+            guild_id,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to revoke API token");
+            e
+        })?;
+
+        let revoked = result.rows_affected() > 0;
+        info!(guild_id, id, revoked, "Revoked API token");
+        Ok(revoked)
+    }
+}
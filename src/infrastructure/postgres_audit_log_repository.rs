@@ -0,0 +1,44 @@
+use sqlx::PgPool;
+use tracing::{error, info, instrument};
+
+use crate::domain::{AuditEntry, AuditLogRepository};
+use crate::telemetry::Timer;
+
+pub struct PostgresAuditLogRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuditLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogRepository for PostgresAuditLogRepository {
+    #[instrument(skip(self, entry), fields(guild_id = %entry.guild_id, action = entry.action.as_str()))]
+    async fn record(&self, entry: &AuditEntry) -> anyhow::Result<i64> {
+        let _timer = Timer::postgres("audit_log.record");
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO audit_log (guild_id, actor_id, action, details, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            entry.guild_id,
+            entry.actor_id,
+            entry.action.as_str(),
+            entry.details,
+            entry.created_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id = %entry.guild_id, error = ?e, "Failed to record audit log entry");
+            e
+        })?;
+
+        info!(guild_id = %entry.guild_id, id, action = entry.action.as_str(), "Recorded audit log entry");
+        Ok(id)
+    }
+}
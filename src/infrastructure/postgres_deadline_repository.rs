@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::{debug, error, info, instrument};
+
+use crate::domain::{DeadlineRepository, SessionDeadline};
+use crate::telemetry::Timer;
+
+pub struct PostgresDeadlineRepository {
+    pool: PgPool,
+}
+
+impl PostgresDeadlineRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+struct DeadlineRow {
+    guild_id: String,
+    channel_id: String,
+    mode: String,
+    capacity: Option<i32>,
+    closes_at: DateTime<Utc>,
+    reminded: bool,
+}
+
+impl TryFrom<DeadlineRow> for SessionDeadline {
+    type Error = anyhow::Error;
+
+    fn try_from(row: DeadlineRow) -> anyhow::Result<Self> {
+        Ok(SessionDeadline {
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            mode: row.mode.parse()?,
+            capacity: row.capacity.map(|capacity| capacity as u32),
+            closes_at: row.closes_at,
+            reminded: row.reminded,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl DeadlineRepository for PostgresDeadlineRepository {
+    #[instrument(skip(self, deadline), fields(guild_id = %deadline.guild_id))]
+    async fn set(&self, deadline: &SessionDeadline) -> anyhow::Result<()> {
+        let _timer = Timer::postgres("deadline.set");
+        sqlx::query!(
+            "INSERT INTO session_deadlines (guild_id, channel_id, mode, capacity, closes_at, reminded) \
+             VALUES ($1, $2, $3, $4, $5, $6) \
+             ON CONFLICT (guild_id) DO UPDATE SET \
+             channel_id = EXCLUDED.channel_id, \
+             mode = EXCLUDED.mode, \
+             capacity = EXCLUDED.capacity, \
+             closes_at = EXCLUDED.closes_at, \
+             reminded = EXCLUDED.reminded",
+            deadline.guild_id,
+            deadline.channel_id,
+            deadline.mode.as_str(),
+            deadline.capacity.map(|capacity| capacity as i32),
+            deadline.closes_at,
+            deadline.reminded
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id = %deadline.guild_id, error = ?e, "Failed to save session deadline");
+            e
+        })?;
+
+        info!(guild_id = %deadline.guild_id, closes_at = %deadline.closes_at, "Saved session deadline");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn get(&self, guild_id: &str) -> anyhow::Result<Option<SessionDeadline>> {
+        let _timer = Timer::postgres("deadline.get");
+        let row = sqlx::query_as!(
+            DeadlineRow,
+            "SELECT guild_id, channel_id, mode, capacity, closes_at, reminded \
+             FROM session_deadlines WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch session deadline");
+            e
+        })?;
+
+        debug!(
+            guild_id,
+            found = row.is_some(),
+            "Retrieved session deadline"
+        );
+        row.map(SessionDeadline::try_from).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn list_all(&self) -> anyhow::Result<Vec<SessionDeadline>> {
+        let _timer = Timer::postgres("deadline.list_all");
+        let rows = sqlx::query_as!(
+            DeadlineRow,
+            "SELECT guild_id, channel_id, mode, capacity, closes_at, reminded \
+             FROM session_deadlines"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Failed to fetch session deadlines");
+            e
+        })?;
+
+        rows.into_iter().map(SessionDeadline::try_from).collect()
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn mark_reminded(&self, guild_id: &str) -> anyhow::Result<()> {
+        let _timer = Timer::postgres("deadline.mark_reminded");
+        sqlx::query!(
+            "UPDATE session_deadlines SET reminded = TRUE WHERE guild_id = $1",
+            guild_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to mark session deadline as reminded");
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn remove(&self, guild_id: &str) -> anyhow::Result<()> {
+        let _timer = Timer::postgres("deadline.remove");
+        sqlx::query!(
+            "DELETE FROM session_deadlines WHERE guild_id = $1",
+            guild_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to remove session deadline");
+            e
+        })?;
+
+        info!(guild_id, "Removed session deadline");
+        Ok(())
+    }
+}
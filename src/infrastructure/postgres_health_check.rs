@@ -0,0 +1,39 @@
+use std::time::{Duration, Instant};
+
+use sqlx::PgPool;
+use tracing::{debug, error, instrument};
+
+use crate::domain::HealthCheck;
+
+pub struct PostgresHealthCheck {
+    pool: PgPool,
+}
+
+impl PostgresHealthCheck {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for PostgresHealthCheck {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    #[instrument(skip(self))]
+    async fn check(&self) -> anyhow::Result<Duration> {
+        let started = Instant::now();
+        sqlx::query_scalar!("SELECT 1")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| {
+                error!(error = ?e, "Failed to query PostgreSQL");
+                e
+            })?;
+
+        let latency = started.elapsed();
+        debug!(?latency, "Queried PostgreSQL");
+        Ok(latency)
+    }
+}
@@ -0,0 +1,48 @@
+use sqlx::PgPool;
+use tracing::{error, info, instrument};
+
+use crate::domain::{LotteryDraw, LotteryRepository};
+use crate::telemetry::Timer;
+
+pub struct PostgresLotteryRepository {
+    pool: PgPool,
+}
+
+impl PostgresLotteryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl LotteryRepository for PostgresLotteryRepository {
+    #[instrument(skip(self, draw), fields(guild_id = %draw.guild_id, seed = draw.seed))]
+    async fn record_draw(&self, draw: &LotteryDraw) -> anyhow::Result<i64> {
+        let _timer = Timer::postgres("lottery.record_draw");
+        let id = sqlx::query_scalar!(
+            "INSERT INTO lottery_draws (guild_id, seed, participants, winners, drawn_by) \
+             VALUES ($1, $2, $3, $4, $5) RETURNING id",
+            draw.guild_id,
+            draw.seed as i64,
+            &draw.participants[..],
+            &draw.winners[..],
+            draw.drawn_by
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id = %draw.guild_id, error = ?e, "Failed to record lottery draw");
+            e
+        })?;
+
+        info!(
+            guild_id = %draw.guild_id,
+            id,
+            seed = draw.seed,
+            participants = draw.participants.len(),
+            winners = draw.winners.len(),
+            "Recorded lottery draw"
+        );
+        Ok(id)
+    }
+}
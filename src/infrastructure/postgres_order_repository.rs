@@ -1,7 +1,9 @@
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use tracing::{debug, error, info, instrument};
 
-use crate::domain::{DailyStats, OrderRepository};
+use crate::domain::{DailyStats, OrderRecord, OrderRepository, PeriodStats, QueueEntry};
+use crate::telemetry::Timer;
 
 pub struct PostgresOrderRepository {
     pool: PgPool,
@@ -15,32 +17,44 @@ impl PostgresOrderRepository {
 
 #[async_trait::async_trait]
 impl OrderRepository for PostgresOrderRepository {
-    #[instrument(skip(self), fields(count = discord_user_ids.len(), guild_id))]
-    async fn record_orders(&self, discord_user_ids: &[&str], guild_id: &str) -> anyhow::Result<()> {
-        if discord_user_ids.is_empty() {
+    #[instrument(skip(self, entries), fields(count = entries.len(), guild_id, lane))]
+    async fn record_orders(
+        &self,
+        entries: &[QueueEntry],
+        guild_id: &str,
+        lane: &str,
+    ) -> anyhow::Result<()> {
+        let _timer = Timer::postgres("order.record_orders");
+        if entries.is_empty() {
             debug!("No orders to record");
             return Ok(());
         }
 
-        debug!(
-            count = discord_user_ids.len(),
-            guild_id, "Recording orders in batch"
-        );
+        debug!(count = entries.len(), guild_id, "Recording orders in batch");
 
-        let discord_user_ids_vec: Vec<String> =
-            discord_user_ids.iter().map(|&s| s.to_string()).collect();
-        let guild_ids: Vec<String> = vec![guild_id.to_string(); discord_user_ids.len()];
+        let discord_user_ids: Vec<String> = entries.iter().map(|e| e.user_id.clone()).collect();
+        let guild_ids: Vec<String> = vec![guild_id.to_string(); entries.len()];
+        let lanes: Vec<String> = vec![lane.to_string(); entries.len()];
+        let quantities: Vec<i32> = entries.iter().map(|e| e.quantity as i32).collect();
+        // Option names never contain commas, so each row's options travel as one string
+        let options: Vec<String> = entries.iter().map(|e| e.options.join(",")).collect();
 
         sqlx::query!(
-            "INSERT INTO orders (discord_user_id, guild_id) SELECT * FROM UNNEST($1::text[], $2::text[])",
-            &discord_user_ids_vec[..],
-            &guild_ids[..]
+            "INSERT INTO orders (discord_user_id, guild_id, quantity, options, lane) \
+             SELECT u.discord_user_id, u.guild_id, u.quantity, string_to_array(u.options, ','), u.lane \
+             FROM UNNEST($1::text[], $2::text[], $3::int[], $4::text[], $5::text[]) \
+             AS u(discord_user_id, guild_id, quantity, options, lane)",
+            &discord_user_ids[..],
+            &guild_ids[..],
+            &quantities[..],
+            &options[..],
+            &lanes[..]
         )
         .execute(&self.pool)
         .await
         .map_err(|e| {
             error!(
-                count = discord_user_ids.len(),
+                count = entries.len(),
                 guild_id,
                 error = ?e,
                 "Failed to record orders in database"
@@ -49,19 +63,25 @@ impl OrderRepository for PostgresOrderRepository {
         })?;
 
         info!(
-            count = discord_user_ids.len(),
+            count = entries.len(),
             guild_id, "Orders recorded successfully"
         );
         Ok(())
     }
 
     #[instrument(skip(self), fields(guild_id))]
-    async fn daily_stats(&self, guild_id: &str) -> anyhow::Result<DailyStats> {
+    async fn daily_stats(
+        &self,
+        guild_id: &str,
+        day_start: DateTime<Utc>,
+    ) -> anyhow::Result<DailyStats> {
+        let _timer = Timer::postgres("order.daily_stats");
         debug!(guild_id, "Fetching daily stats");
 
         let total = sqlx::query_scalar!(
-            "SELECT COUNT(*) FROM orders WHERE fulfilled_at::date = CURRENT_DATE AND guild_id = $1",
-            guild_id
+            "SELECT SUM(quantity)::bigint FROM orders WHERE fulfilled_at >= $2 AND guild_id = $1",
+            guild_id,
+            day_start
         )
         .fetch_one(&self.pool)
         .await
@@ -71,13 +91,15 @@ impl OrderRepository for PostgresOrderRepository {
         })?
         .unwrap_or(0);
 
+        // Ticket numbers start over every session, so a guest id is not one person
         let top_users: Vec<(String, i64)> = sqlx::query!(
-            "SELECT discord_user_id, COUNT(*) as count FROM orders \
-             WHERE fulfilled_at::date = CURRENT_DATE AND guild_id = $1 \
+            "SELECT discord_user_id, SUM(quantity)::bigint as count FROM orders \
+             WHERE fulfilled_at >= $2 AND guild_id = $1 AND discord_user_id NOT LIKE 'guest:%' \
              GROUP BY discord_user_id \
              ORDER BY count DESC \
              LIMIT 3",
-            guild_id
+            guild_id,
+            day_start
         )
         .fetch_all(&self.pool)
         .await
@@ -89,16 +111,211 @@ impl OrderRepository for PostgresOrderRepository {
         .map(|row| (row.discord_user_id, row.count.unwrap_or(0)))
         .collect();
 
+        let lanes: Vec<(String, i64)> = sqlx::query!(
+            "SELECT lane, SUM(quantity)::bigint as count FROM orders \
+             WHERE fulfilled_at >= $2 AND guild_id = $1 \
+             GROUP BY lane \
+             ORDER BY lane",
+            guild_id,
+            day_start
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch lane totals");
+            e
+        })?
+        .into_iter()
+        .map(|row| (row.lane, row.count.unwrap_or(0)))
+        .collect();
+
         info!(
             guild_id,
             total_orders = total,
             top_users_count = top_users.len(),
+            lanes_count = lanes.len(),
             "Retrieved daily stats"
         );
 
         Ok(DailyStats {
             total_orders: total,
             top_users,
+            lanes,
         })
     }
+
+    #[instrument(skip(self), fields(guild_id, user_id))]
+    async fn waffles_since(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<i64> {
+        let _timer = Timer::postgres("order.waffles_since");
+        let count = sqlx::query_scalar!(
+            "SELECT SUM(quantity)::bigint FROM orders \
+             WHERE guild_id = $1 AND discord_user_id = $2 AND fulfilled_at >= $3",
+            guild_id,
+            user_id,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, user_id, error = ?e, "Failed to count waffles for user");
+            e
+        })?
+        .unwrap_or(0);
+
+        debug!(guild_id, user_id, count, "Counted waffles for user");
+        Ok(count)
+    }
+
+    #[instrument(skip(self), fields(guild_id, user_id))]
+    async fn last_served_at(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let _timer = Timer::postgres("order.last_served_at");
+        let last_served_at = sqlx::query_scalar!(
+            "SELECT MAX(fulfilled_at) FROM orders WHERE guild_id = $1 AND discord_user_id = $2",
+            guild_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, user_id, error = ?e, "Failed to fetch last order for user");
+            e
+        })?;
+
+        debug!(guild_id, user_id, last_served_at = ?last_served_at, "Fetched last order for user");
+        Ok(last_served_at)
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn stats_since(
+        &self,
+        guild_id: &str,
+        since: Option<DateTime<Utc>>,
+        top: i64,
+    ) -> anyhow::Result<PeriodStats> {
+        let _timer = Timer::postgres("order.stats_since");
+        let total = sqlx::query_scalar!(
+            "SELECT SUM(quantity)::bigint FROM orders \
+             WHERE guild_id = $1 AND ($2::timestamptz IS NULL OR fulfilled_at >= $2)",
+            guild_id,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch order total");
+            e
+        })?
+        .unwrap_or(0);
+
+        let top_users: Vec<(String, i64)> = sqlx::query!(
+            "SELECT discord_user_id, SUM(quantity)::bigint as count FROM orders \
+             WHERE guild_id = $1 AND ($2::timestamptz IS NULL OR fulfilled_at >= $2) \
+             AND discord_user_id NOT LIKE 'guest:%' \
+             GROUP BY discord_user_id \
+             ORDER BY count DESC, discord_user_id \
+             LIMIT $3",
+            guild_id,
+            since,
+            top
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch top users");
+            e
+        })?
+        .into_iter()
+        .map(|row| (row.discord_user_id, row.count.unwrap_or(0)))
+        .collect();
+
+        debug!(guild_id, since = ?since, total, "Retrieved order stats");
+        Ok(PeriodStats { total, top_users })
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn list_orders(
+        &self,
+        guild_id: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Vec<OrderRecord>> {
+        let _timer = Timer::postgres("order.list_orders");
+        let orders = sqlx::query_as!(
+            OrderRecord,
+            "SELECT id, discord_user_id as user_id, lane, quantity, fulfilled_at FROM orders \
+             WHERE guild_id = $1 \
+             AND ($2::timestamptz IS NULL OR fulfilled_at >= $2) \
+             AND ($3::timestamptz IS NULL OR fulfilled_at < $3) \
+             ORDER BY fulfilled_at, id",
+            guild_id,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to list orders");
+            e
+        })?;
+
+        debug!(guild_id, count = orders.len(), "Listed orders");
+        Ok(orders)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::queue::guest_user_id;
+
+    use testcontainers::runners::AsyncRunner;
+    use testcontainers_modules::postgres::Postgres;
+
+    const TEST_GUILD: &str = "test-guild";
+
+    #[tokio::test]
+    async fn test_guests_are_not_top_users() {
+        let node = Postgres::default().start().await.unwrap();
+        let host_ip = node.get_host().await.unwrap();
+        let host_port = node.get_host_port_ipv4(5432).await.unwrap();
+        let pool = PgPool::connect(&format!(
+            "postgres://postgres:postgres@{host_ip}:{host_port}/postgres"
+        ))
+        .await
+        .unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        let repo = PostgresOrderRepository::new(pool);
+
+        // The first guest of two sessions, who are different people
+        for name in ["Kari", "Ola"] {
+            let guest = QueueEntry::new(guest_user_id(1), name.to_string()).with_quantity(2);
+            repo.record_orders(&[guest], TEST_GUILD, "standard")
+                .await
+                .unwrap();
+        }
+        let user = QueueEntry::new("1".to_string(), "Per".to_string());
+        repo.record_orders(&[user], TEST_GUILD, "standard")
+            .await
+            .unwrap();
+
+        let stats = repo.stats_since(TEST_GUILD, None, 3).await.unwrap();
+        assert_eq!(stats.total, 5);
+        assert_eq!(stats.top_users, vec![("1".to_string(), 1)]);
+
+        let daily = repo
+            .daily_stats(TEST_GUILD, Utc::now() - chrono::TimeDelta::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(daily.total_orders, 5);
+        assert_eq!(daily.top_users, vec![("1".to_string(), 1)]);
+    }
 }
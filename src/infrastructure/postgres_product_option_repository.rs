@@ -0,0 +1,76 @@
+use sqlx::PgPool;
+use tracing::{debug, error, info, instrument};
+
+use crate::domain::ProductOptionRepository;
+use crate::telemetry::Timer;
+
+pub struct PostgresProductOptionRepository {
+    pool: PgPool,
+}
+
+impl PostgresProductOptionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ProductOptionRepository for PostgresProductOptionRepository {
+    #[instrument(skip(self), fields(guild_id))]
+    async fn list(&self, guild_id: &str) -> anyhow::Result<Vec<String>> {
+        let _timer = Timer::postgres("product_option.list");
+        let options = sqlx::query_scalar!(
+            "SELECT name FROM product_options WHERE guild_id = $1 ORDER BY name",
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch product options");
+            e
+        })?;
+
+        debug!(guild_id, count = options.len(), "Retrieved product options");
+        Ok(options)
+    }
+
+    #[instrument(skip(self), fields(guild_id, name))]
+    async fn add(&self, guild_id: &str, name: &str) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("product_option.add");
+        let result = sqlx::query!(
+            "INSERT INTO product_options (guild_id, name) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            guild_id,
+            name
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, name, error = ?e, "Failed to add product option");
+            e
+        })?;
+
+        let added = result.rows_affected() > 0;
+        info!(guild_id, name, added, "Added product option");
+        Ok(added)
+    }
+
+    #[instrument(skip(self), fields(guild_id, name))]
+    async fn remove(&self, guild_id: &str, name: &str) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("product_option.remove");
+        let result = sqlx::query!(
+            "DELETE FROM product_options WHERE guild_id = $1 AND name = $2",
+            guild_id,
+            name
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, name, error = ?e, "Failed to remove product option");
+            e
+        })?;
+
+        let removed = result.rows_affected() > 0;
+        info!(guild_id, name, removed, "Removed product option");
+        Ok(removed)
+    }
+}
@@ -0,0 +1,231 @@
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use sqlx::PgPool;
+use tracing::{debug, error, info, instrument};
+
+use crate::domain::{Schedule, ScheduleRepository};
+use crate::telemetry::Timer;
+
+pub struct PostgresScheduleRepository {
+    pool: PgPool,
+}
+
+impl PostgresScheduleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+struct ScheduleRow {
+    id: i64,
+    guild_id: String,
+    channel_id: String,
+    weekday: i16,
+    opens_at: NaiveTime,
+    closes_at: NaiveTime,
+}
+
+impl TryFrom<ScheduleRow> for Schedule {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ScheduleRow) -> anyhow::Result<Self> {
+        let weekday = u8::try_from(row.weekday)
+            .ok()
+            .and_then(|day| Weekday::try_from(day.wrapping_sub(1)).ok())
+            .ok_or_else(|| anyhow::anyhow!("Invalid weekday: {}", row.weekday))?;
+
+        Ok(Schedule {
+            id: row.id,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            weekday,
+            opens_at: row.opens_at,
+            closes_at: row.closes_at,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ScheduleRepository for PostgresScheduleRepository {
+    #[instrument(skip(self), fields(guild_id))]
+    async fn list(&self, guild_id: &str) -> anyhow::Result<Vec<Schedule>> {
+        let _timer = Timer::postgres("schedule.list");
+        let rows = sqlx::query_as!(
+            ScheduleRow,
+            r#"
+            SELECT id, guild_id, channel_id, weekday, opens_at, closes_at
+            FROM schedules
+            WHERE guild_id = $1
+            ORDER BY weekday, opens_at
+            "#,
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch schedules");
+            e
+        })?;
+
+        debug!(guild_id, count = rows.len(), "Retrieved schedules");
+        rows.into_iter().map(Schedule::try_from).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn list_all(&self) -> anyhow::Result<Vec<Schedule>> {
+        let _timer = Timer::postgres("schedule.list_all");
+        let rows = sqlx::query_as!(
+            ScheduleRow,
+            "SELECT id, guild_id, channel_id, weekday, opens_at, closes_at FROM schedules"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Failed to fetch all schedules");
+            e
+        })?;
+
+        rows.into_iter().map(Schedule::try_from).collect()
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn add(
+        &self,
+        guild_id: &str,
+        channel_id: &str,
+        weekday: Weekday,
+        opens_at: NaiveTime,
+        closes_at: NaiveTime,
+    ) -> anyhow::Result<i64> {
+        let _timer = Timer::postgres("schedule.add");
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO schedules (guild_id, channel_id, weekday, opens_at, closes_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            guild_id,
+            channel_id,
+            weekday.number_from_monday() as i16,
+            opens_at,
+            closes_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to add schedule");
+            e
+        })?;
+
+        info!(guild_id, id, "Added schedule");
+        Ok(id)
+    }
+
+    #[instrument(skip(self), fields(guild_id, id))]
+    async fn remove(&self, guild_id: &str, id: i64) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("schedule.remove");
+        let result = sqlx::query!(
+            "DELETE FROM schedules WHERE guild_id = $1 AND id = $2",
+            guild_id,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, id, error = ?e, "Failed to remove schedule");
+            e
+        })?;
+
+        let removed = result.rows_affected() > 0;
+        info!(guild_id, id, removed, "Removed schedule");
+        Ok(removed)
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn exceptions(&self, guild_id: &str, from: NaiveDate) -> anyhow::Result<Vec<NaiveDate>> {
+        let _timer = Timer::postgres("schedule.exceptions");
+        let dates = sqlx::query_scalar!(
+            r#"
+            SELECT date FROM schedule_exceptions
+            WHERE guild_id = $1 AND date >= $2
+            ORDER BY date
+            "#,
+            guild_id,
+            from
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch schedule exceptions");
+            e
+        })?;
+
+        debug!(
+            guild_id,
+            count = dates.len(),
+            "Retrieved schedule exceptions"
+        );
+        Ok(dates)
+    }
+
+    #[instrument(skip(self), fields(guild_id, %date))]
+    async fn add_exception(&self, guild_id: &str, date: NaiveDate) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("schedule.add_exception");
+        let result = sqlx::query!(
+            "INSERT INTO schedule_exceptions (guild_id, date) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            guild_id,
+            date
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, %date, error = ?e, "Failed to add schedule exception");
+            e
+        })?;
+
+        let added = result.rows_affected() > 0;
+        info!(guild_id, %date, added, "Added schedule exception");
+        Ok(added)
+    }
+
+    #[instrument(skip(self), fields(guild_id, %date))]
+    async fn remove_exception(&self, guild_id: &str, date: NaiveDate) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("schedule.remove_exception");
+        let result = sqlx::query!(
+            "DELETE FROM schedule_exceptions WHERE guild_id = $1 AND date = $2",
+            guild_id,
+            date
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, %date, error = ?e, "Failed to remove schedule exception");
+            e
+        })?;
+
+        let removed = result.rows_affected() > 0;
+        info!(guild_id, %date, removed, "Removed schedule exception");
+        Ok(removed)
+    }
+
+    #[instrument(skip(self), fields(guild_id, %date))]
+    async fn is_exception(&self, guild_id: &str, date: NaiveDate) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("schedule.is_exception");
+        let exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM schedule_exceptions WHERE guild_id = $1 AND date = $2
+            ) AS "exists!"
+            "#,
+            guild_id,
+            date
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, %date, error = ?e, "Failed to check schedule exception");
+            e
+        })?;
+
+        Ok(exists)
+    }
+}
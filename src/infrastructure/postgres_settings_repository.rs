@@ -0,0 +1,105 @@
+use sqlx::PgPool;
+use tracing::{debug, error, info, instrument};
+
+use crate::domain::{GuildSettings, SettingsRepository};
+use crate::telemetry::Timer;
+
+pub struct PostgresSettingsRepository {
+    pool: PgPool,
+}
+
+impl PostgresSettingsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SettingsRepository for PostgresSettingsRepository {
+    #[instrument(skip(self), fields(guild_id))]
+    async fn get(&self, guild_id: &str) -> anyhow::Result<GuildSettings> {
+        let _timer = Timer::postgres("settings.get");
+        let row = sqlx::query!(
+            "SELECT max_waffles_per_order, variants, serving_mode, max_waffles_per_user, \
+             limit_period, rejoin_cooldown_minutes, overflow_mode, idle_close_minutes, \
+             ordering_channel_id, oracle_channel_id, audit_channel_id, locale \
+             FROM guild_settings WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch guild settings");
+            e
+        })?;
+
+        let settings = match row {
+            Some(row) => GuildSettings {
+                max_waffles_per_order: row.max_waffles_per_order as u32,
+                variants: row.variants,
+                serving_mode: row.serving_mode.parse()?,
+                max_waffles_per_user: row.max_waffles_per_user.map(|max| max as u32),
+                limit_period: row.limit_period.parse()?,
+                rejoin_cooldown_minutes: row.rejoin_cooldown_minutes.map(|minutes| minutes as u32),
+                overflow_mode: row.overflow_mode.parse()?,
+                idle_close_minutes: row.idle_close_minutes.map(|minutes| minutes as u32),
+                ordering_channel_id: row.ordering_channel_id,
+                oracle_channel_id: row.oracle_channel_id,
+                audit_channel_id: row.audit_channel_id,
+                locale: row.locale.parse()?,
+            },
+            None => GuildSettings::default(),
+        };
+
+        debug!(guild_id, settings = ?settings, "Retrieved guild settings");
+        Ok(settings)
+    }
+
+    #[instrument(skip(self, settings), fields(guild_id))]
+    async fn save(&self, guild_id: &str, settings: &GuildSettings) -> anyhow::Result<()> {
+        let _timer = Timer::postgres("settings.save");
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, max_waffles_per_order, variants, serving_mode, \
+             max_waffles_per_user, limit_period, rejoin_cooldown_minutes, overflow_mode, idle_close_minutes, \
+             ordering_channel_id, oracle_channel_id, audit_channel_id, locale) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) \
+             ON CONFLICT (guild_id) DO UPDATE SET \
+             max_waffles_per_order = EXCLUDED.max_waffles_per_order, \
+             variants = EXCLUDED.variants, \
+             serving_mode = EXCLUDED.serving_mode, \
+             max_waffles_per_user = EXCLUDED.max_waffles_per_user, \
+             limit_period = EXCLUDED.limit_period, \
+             rejoin_cooldown_minutes = EXCLUDED.rejoin_cooldown_minutes, \
+             overflow_mode = EXCLUDED.overflow_mode, \
+             idle_close_minutes = EXCLUDED.idle_close_minutes, \
+             ordering_channel_id = EXCLUDED.ordering_channel_id, \
+             oracle_channel_id = EXCLUDED.oracle_channel_id, \
+             audit_channel_id = EXCLUDED.audit_channel_id, \
+             locale = EXCLUDED.locale",
+            guild_id,
+            settings.max_waffles_per_order as i32,
+            &settings.variants[..],
+            settings.serving_mode.as_str(),
+            settings.max_waffles_per_user.map(|max| max as i32),
+            settings.limit_period.as_str(),
+            settings
+                .rejoin_cooldown_minutes
+                .map(|minutes| minutes as i32),
+            settings.overflow_mode.as_str(),
+            settings.idle_close_minutes.map(|minutes| minutes as i32),
+            settings.ordering_channel_id,
+            settings.oracle_channel_id,
+            settings.audit_channel_id,
+            settings.locale.as_str()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to save guild settings");
+            e
+        })?;
+
+        info!(guild_id, settings = ?settings, "Saved guild settings");
+        Ok(())
+    }
+}
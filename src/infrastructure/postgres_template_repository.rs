@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::domain::{TemplateKind, TemplateRepository};
+use crate::telemetry::Timer;
+
+pub struct PostgresTemplateRepository {
+    pool: PgPool,
+}
+
+impl PostgresTemplateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl TemplateRepository for PostgresTemplateRepository {
+    #[instrument(skip(self), fields(guild_id))]
+    async fn list(&self, guild_id: &str) -> anyhow::Result<HashMap<TemplateKind, String>> {
+        let _timer = Timer::postgres("template.list");
+        let rows = sqlx::query!(
+            "SELECT kind, template FROM message_templates WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch message templates");
+            e
+        })?;
+
+        let mut templates = HashMap::new();
+        for row in rows {
+            match row.kind.parse() {
+                Ok(kind) => {
+                    templates.insert(kind, row.template);
+                }
+                // Left behind by a template that no longer exists
+                Err(e) => warn!(guild_id, kind = row.kind, error = ?e, "Skipping message template"),
+            }
+        }
+
+        debug!(
+            guild_id,
+            count = templates.len(),
+            "Retrieved message templates"
+        );
+        Ok(templates)
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn set(&self, guild_id: &str, kind: TemplateKind, template: &str) -> anyhow::Result<()> {
+        let _timer = Timer::postgres("template.set");
+        sqlx::query!(
+            "INSERT INTO message_templates (guild_id, kind, template) VALUES ($1, $2, $3) \
+             ON CONFLICT (guild_id, kind) DO UPDATE SET template = EXCLUDED.template",
+            guild_id,
+            kind.as_str(),
+            template
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to save message template");
+            e
+        })?;
+
+        info!(guild_id, kind = kind.as_str(), "Saved message template");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn remove(&self, guild_id: &str, kind: TemplateKind) -> anyhow::Result<bool> {
+        let _timer = Timer::postgres("template.remove");
+        let result = sqlx::query!(
+            "DELETE FROM message_templates WHERE guild_id = $1 AND kind = $2",
+            guild_id,
+            kind.as_str()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(guild_id, error = ?e, "Failed to remove message template");
+            e
+        })?;
+
+        let removed = result.rows_affected() > 0;
+        info!(
+            guild_id,
+            kind = kind.as_str(),
+            removed,
+            "Removed message template"
+        );
+        Ok(removed)
+    }
+}
@@ -0,0 +1,65 @@
+use sqlx::PgPool;
+use tracing::{debug, error, info, instrument};
+
+use crate::domain::{Locale, UserLocaleRepository};
+use crate::telemetry::Timer;
+
+pub struct PostgresUserLocaleRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserLocaleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserLocaleRepository for PostgresUserLocaleRepository {
+    #[instrument(skip(self), fields(user_id))]
+    async fn get(&self, user_id: &str) -> anyhow::Result<Option<Locale>> {
+        let _timer = Timer::postgres("user_locale.get");
+        let locale = sqlx::query_scalar!(
+            "SELECT locale FROM user_locales WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(user_id, error = ?e, "Failed to fetch user locale");
+            e
+        })?;
+
+        debug!(user_id, locale = ?locale, "Retrieved user locale");
+        locale.map(|locale| locale.parse()).transpose()
+    }
+
+    #[instrument(skip(self), fields(user_id))]
+    async fn set(&self, user_id: &str, locale: Option<Locale>) -> anyhow::Result<()> {
+        let _timer = Timer::postgres("user_locale.set");
+        match locale {
+            Some(locale) => {
+                sqlx::query!(
+                    "INSERT INTO user_locales (user_id, locale) VALUES ($1, $2) \
+                 ON CONFLICT (user_id) DO UPDATE SET locale = EXCLUDED.locale",
+                    user_id,
+                    locale.as_str()
+                )
+                .execute(&self.pool)
+                .await
+            }
+            None => {
+                sqlx::query!("DELETE FROM user_locales WHERE user_id = $1", user_id)
+                    .execute(&self.pool)
+                    .await
+            }
+        }
+        .map_err(|e| {
+            error!(user_id, error = ?e, "Failed to save user locale");
+            e
+        })?;
+
+        info!(user_id, locale = ?locale, "Saved user locale");
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::{Duration, Instant};
+
+use tracing::{debug, error, instrument};
+
+use crate::domain::HealthCheck;
+
+pub struct RedisHealthCheck {
+    redis: redis::Client,
+}
+
+impl RedisHealthCheck {
+    pub fn new(redis: redis::Client) -> Self {
+        Self { redis }
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for RedisHealthCheck {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    #[instrument(skip(self))]
+    async fn check(&self) -> anyhow::Result<Duration> {
+        let started = Instant::now();
+        let mut con = self.redis.get_multiplexed_async_connection().await?;
+        let _: String = redis::cmd("PING")
+            .query_async(&mut con)
+            .await
+            .map_err(|e| {
+                error!(error = ?e, "Failed to ping Redis");
+                e
+            })?;
+
+        let latency = started.elapsed();
+        debug!(?latency, "Pinged Redis");
+        Ok(latency)
+    }
+}
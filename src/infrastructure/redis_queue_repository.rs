@@ -1,56 +1,299 @@
-use std::{collections::HashSet, sync::RwLock};
+use std::{collections::HashMap, sync::RwLock};
 
+use chrono::Utc;
+use metrics::counter;
 use redis::AsyncCommands;
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::domain::{QueueEntry, QueueRepository};
+use crate::domain::{
+    QueueEntry, QueueRepository, ServingPolicy, Session, SessionMode, WaitlistEntry,
+    queue::move_entry,
+};
+use crate::telemetry::{JOINS, Timer, WAFFLES_BAKED};
 
-fn queue_key(guild_id: &str) -> String {
-    format!("queue:{guild_id}")
+const MAX_TRANSACTION_RETRIES: usize = 5;
+
+fn queue_key(guild_id: &str, lane: &str) -> String {
+    format!("queue:{guild_id}:{lane}")
+}
+
+fn lanes_key(guild_id: &str) -> String {
+    format!("lanes:{guild_id}")
+}
+
+fn waitlist_key(guild_id: &str) -> String {
+    format!("waitlist:{guild_id}")
+}
+
+/// Waffles handed out per user since the queue was opened
+fn served_key(guild_id: &str) -> String {
+    format!("served:{guild_id}")
+}
+
+/// Last ticket number handed out to a guest since the queue was opened
+fn tickets_key(guild_id: &str) -> String {
+    format!("tickets:{guild_id}")
 }
 
 pub struct RedisQueueRepository {
     redis: redis::Client,
-    open_guilds: RwLock<HashSet<String>>,
+    open_guilds: RwLock<HashMap<String, Session>>,
 }
 
 impl RedisQueueRepository {
     pub fn new(redis: redis::Client) -> Self {
         Self {
             redis,
-            open_guilds: RwLock::new(HashSet::new()),
+            open_guilds: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Rewrite a lane with `change` in a transaction that is retried if someone
+    /// else touches the lane, so joins and bakes in between are not lost.
+    /// Nothing is written if `change` returns None.
+    async fn update_lane<T>(
+        &self,
+        guild_id: &str,
+        lane: &str,
+        change: impl Fn(&mut Vec<QueueEntry>) -> Option<T> + Send,
+    ) -> Option<T>
+    where
+        T: Send,
+    {
+        let key = queue_key(guild_id, lane);
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, lane, error = ?e, "Failed to get Redis connection for update_lane");
+                return None;
+            }
+        };
+
+        for _ in 0..MAX_TRANSACTION_RETRIES {
+            let watched: redis::RedisResult<()> =
+                redis::cmd("WATCH").arg(&key).query_async(&mut con).await;
+            if let Err(e) = watched {
+                error!(guild_id, lane, error = ?e, "Failed to watch queue in Redis");
+                return None;
+            }
+
+            let json_list: Vec<String> = match con.lrange(&key, 0, -1).await {
+                Ok(list) => list,
+                Err(e) => {
+                    error!(guild_id, lane, error = ?e, "Failed to fetch queue list from Redis");
+                    return None;
+                }
+            };
+            let mut entries: Vec<QueueEntry> = json_list
+                .iter()
+                .filter_map(|json_str| serde_json::from_str(json_str).ok())
+                .collect();
+
+            let Some(result) = change(&mut entries) else {
+                let unwatched: redis::RedisResult<()> =
+                    redis::cmd("UNWATCH").query_async(&mut con).await;
+                if let Err(e) = unwatched {
+                    warn!(guild_id, lane, error = ?e, "Failed to unwatch queue in Redis");
+                }
+                return None;
+            };
+
+            let mut pipe = redis::pipe();
+            pipe.atomic().del(&key).ignore();
+            if !entries.is_empty() {
+                let json_list: Vec<String> = entries
+                    .iter()
+                    .map(|entry| serde_json::to_string(entry).unwrap())
+                    .collect();
+                pipe.rpush(&key, json_list).ignore();
+            }
+
+            let written: redis::RedisResult<Option<()>> = pipe.query_async(&mut con).await;
+            match written {
+                Ok(Some(())) => return Some(result),
+                Ok(None) => debug!(guild_id, lane, "Queue changed during update, retrying"),
+                Err(e) => {
+                    error!(guild_id, lane, error = ?e, "Failed to update queue in Redis");
+                    return None;
+                }
+            }
         }
+
+        warn!(
+            guild_id,
+            lane, "Gave up updating queue after repeated conflicts"
+        );
+        None
     }
 }
 
 #[async_trait::async_trait]
 impl QueueRepository for RedisQueueRepository {
     #[instrument(skip(self), fields(guild_id))]
-    fn open(&self, guild_id: &str) {
-        info!(guild_id, "Opening queue for guild");
-        self.open_guilds
-            .write()
-            .unwrap()
-            .insert(guild_id.to_string());
+    fn open(&self, guild_id: &str, mode: SessionMode) {
+        info!(guild_id, mode = ?mode, "Opening queue for guild");
+        self.open_guilds.write().unwrap().insert(
+            guild_id.to_string(),
+            Session {
+                mode,
+                opened_at: Utc::now(),
+                capacity: None,
+                channel_id: None,
+                last_baked_at: None,
+            },
+        );
     }
 
     #[instrument(skip(self), fields(guild_id))]
     async fn close(&self, guild_id: &str) {
+        let _timer = Timer::redis("queue.close");
         info!(guild_id, "Closing queue for guild");
         self.open_guilds.write().unwrap().remove(guild_id);
-        self.clear(guild_id).await;
+
+        let lanes = self.lanes(guild_id).await;
+        for lane in &lanes {
+            self.clear(guild_id, lane).await;
+        }
+
+        if let Ok(mut con) = self.redis.get_multiplexed_async_connection().await {
+            let result: redis::RedisResult<()> = con
+                .del(&[
+                    lanes_key(guild_id),
+                    served_key(guild_id),
+                    waitlist_key(guild_id),
+                    tickets_key(guild_id),
+                ])
+                .await;
+            if let Err(e) = result {
+                error!(guild_id, error = ?e, "Failed to clear lanes in Redis");
+            }
+        } else {
+            error!(guild_id, "Failed to get Redis connection for close");
+        }
     }
 
     #[instrument(skip(self), fields(guild_id))]
     fn is_open(&self, guild_id: &str) -> bool {
-        let is_open = self.open_guilds.read().unwrap().contains(guild_id);
+        let is_open = self.open_guilds.read().unwrap().contains_key(guild_id);
         debug!(guild_id, is_open, "Checking if queue is open");
         is_open
     }
 
-    #[instrument(skip(self), fields(guild_id, user_id))]
-    async fn index_of(&self, guild_id: &str, user_id: &str) -> Option<usize> {
-        let key = queue_key(guild_id);
+    #[instrument(skip(self), fields(guild_id))]
+    fn session(&self, guild_id: &str) -> Option<Session> {
+        self.open_guilds.read().unwrap().get(guild_id).cloned()
+    }
+
+    #[instrument(skip(self))]
+    fn sessions(&self) -> Vec<(String, Session)> {
+        self.open_guilds
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(guild_id, session)| (guild_id.clone(), session.clone()))
+            .collect()
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    fn set_mode(&self, guild_id: &str, mode: SessionMode) {
+        match self.open_guilds.write().unwrap().get_mut(guild_id) {
+            Some(session) => {
+                info!(guild_id, mode = ?mode, "Changing session mode");
+                session.mode = mode;
+            }
+            None => warn!(guild_id, "Tried to change mode of a closed queue"),
+        }
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    fn set_capacity(&self, guild_id: &str, capacity: Option<u32>) {
+        match self.open_guilds.write().unwrap().get_mut(guild_id) {
+            Some(session) => {
+                info!(guild_id, capacity = ?capacity, "Changing session capacity");
+                session.capacity = capacity;
+            }
+            None => warn!(guild_id, "Tried to change capacity of a closed queue"),
+        }
+    }
+
+    #[instrument(skip(self), fields(guild_id, channel_id))]
+    fn set_channel(&self, guild_id: &str, channel_id: &str) {
+        match self.open_guilds.write().unwrap().get_mut(guild_id) {
+            Some(session) => session.channel_id = Some(channel_id.to_string()),
+            None => warn!(guild_id, "Tried to change channel of a closed queue"),
+        }
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    fn mark_baked(&self, guild_id: &str) {
+        if let Some(session) = self.open_guilds.write().unwrap().get_mut(guild_id) {
+            session.last_baked_at = Some(Utc::now());
+        }
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn next_ticket(&self, guild_id: &str) -> u32 {
+        let _timer = Timer::redis("queue.next_ticket");
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, error = ?e, "Failed to get Redis connection for next_ticket");
+                return 0;
+            }
+        };
+        let ticket = con
+            .incr(tickets_key(guild_id), 1)
+            .await
+            .unwrap_or_else(|e| {
+                error!(guild_id, error = ?e, "Failed to increment ticket number in Redis");
+                0
+            });
+        info!(guild_id, ticket, "Handed out guest ticket");
+        ticket
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn served_count(&self, guild_id: &str) -> u32 {
+        let _timer = Timer::redis("queue.served_count");
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, error = ?e, "Failed to get Redis connection for served_count");
+                return 0;
+            }
+        };
+        let counts: Vec<u32> = con.hvals(served_key(guild_id)).await.unwrap_or_else(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch served counts from Redis");
+            vec![]
+        });
+        let served = counts.iter().sum();
+        debug!(guild_id, served, "Retrieved served count");
+        served
+    }
+
+    #[instrument(skip(self), fields(guild_id))]
+    async fn lanes(&self, guild_id: &str) -> Vec<String> {
+        let _timer = Timer::redis("queue.lanes");
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, error = ?e, "Failed to get Redis connection for lanes");
+                return vec![];
+            }
+        };
+        let mut lanes: Vec<String> = con.smembers(lanes_key(guild_id)).await.unwrap_or_else(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch lanes from Redis");
+            vec![]
+        });
+        lanes.sort();
+        debug!(guild_id, lanes = ?lanes, "Retrieved lanes");
+        lanes
+    }
+
+    #[instrument(skip(self), fields(guild_id, lane, user_id))]
+    async fn index_of(&self, guild_id: &str, lane: &str, user_id: &str) -> Option<usize> {
+        let _timer = Timer::redis("queue.index_of");
+        let key = queue_key(guild_id, lane);
         let mut con = match self.redis.get_multiplexed_async_connection().await {
             Ok(con) => con,
             Err(e) => {
@@ -77,9 +320,10 @@ impl QueueRepository for RedisQueueRepository {
         position
     }
 
-    #[instrument(skip(self), fields(guild_id))]
-    async fn size(&self, guild_id: &str) -> usize {
-        let key = queue_key(guild_id);
+    #[instrument(skip(self), fields(guild_id, lane))]
+    async fn size(&self, guild_id: &str, lane: &str) -> usize {
+        let _timer = Timer::redis("queue.size");
+        let key = queue_key(guild_id, lane);
         let mut con = match self.redis.get_multiplexed_async_connection().await {
             Ok(con) => con,
             Err(e) => {
@@ -95,9 +339,10 @@ impl QueueRepository for RedisQueueRepository {
         size
     }
 
-    #[instrument(skip(self, entry), fields(guild_id, user_id = %entry.user_id))]
-    async fn push(&self, guild_id: &str, entry: QueueEntry) -> usize {
-        let key = queue_key(guild_id);
+    #[instrument(skip(self, entry), fields(guild_id, lane, user_id = %entry.user_id))]
+    async fn push(&self, guild_id: &str, lane: &str, entry: QueueEntry) -> usize {
+        let _timer = Timer::redis("queue.push");
+        let key = queue_key(guild_id, lane);
         let json = serde_json::to_string(&entry).unwrap();
         let mut con = match self.redis.get_multiplexed_async_connection().await {
             Ok(con) => con,
@@ -106,17 +351,31 @@ impl QueueRepository for RedisQueueRepository {
                 return 0;
             }
         };
-        let new_size = con.rpush(&key, json).await.unwrap_or_else(|e| {
-            error!(guild_id, user_id = %entry.user_id, error = ?e, "Failed to push to queue in Redis");
-            0
-        });
-        info!(guild_id, user_id = %entry.user_id, queue_size = new_size, "Added user to queue");
+        let result: redis::RedisResult<(usize,)> = redis::pipe()
+            .rpush(&key, json)
+            .sadd(lanes_key(guild_id), lane)
+            .ignore()
+            .query_async(&mut con)
+            .await;
+        let new_size = match result {
+            Ok((size,)) => {
+                counter!(JOINS, "guild_id" => guild_id.to_string(), "lane" => lane.to_string())
+                    .increment(1);
+                size
+            }
+            Err(e) => {
+                error!(guild_id, lane, user_id = %entry.user_id, error = ?e, "Failed to push to queue in Redis");
+                0
+            }
+        };
+        info!(guild_id, lane, user_id = %entry.user_id, queue_size = new_size, "Added user to queue");
         new_size
     }
 
-    #[instrument(skip(self), fields(guild_id))]
-    async fn pop(&self, guild_id: &str) -> Option<QueueEntry> {
-        let key = queue_key(guild_id);
+    #[instrument(skip(self), fields(guild_id, lane))]
+    async fn pop(&self, guild_id: &str, lane: &str) -> Option<QueueEntry> {
+        let _timer = Timer::redis("queue.pop");
+        let key = queue_key(guild_id, lane);
         let mut con = match self.redis.get_multiplexed_async_connection().await {
             Ok(con) => con,
             Err(e) => {
@@ -131,19 +390,31 @@ impl QueueRepository for RedisQueueRepository {
             .and_then(|json_str| serde_json::from_str(&json_str).ok());
 
         match &entry {
-            Some(e) => info!(guild_id, user_id = %e.user_id, "Popped user from queue"),
+            Some(e) => {
+                counter!(WAFFLES_BAKED, "guild_id" => guild_id.to_string(), "lane" => lane.to_string())
+                    .increment(e.quantity.into());
+                info!(guild_id, user_id = %e.user_id, "Popped user from queue");
+            }
             None => debug!(guild_id, "No entry to pop from queue"),
         }
         entry
     }
 
-    #[instrument(skip(self), fields(guild_id, n))]
-    async fn pop_n(&self, guild_id: &str, n: usize) -> Vec<QueueEntry> {
+    #[instrument(skip(self, policy), fields(guild_id, lane, n))]
+    async fn pop_n(
+        &self,
+        guild_id: &str,
+        lane: &str,
+        n: usize,
+        policy: &dyn ServingPolicy,
+    ) -> Vec<QueueEntry> {
+        let _timer = Timer::redis("queue.pop_n");
         if n == 0 {
             return vec![];
         }
 
-        let key = queue_key(guild_id);
+        let key = queue_key(guild_id, lane);
+        let served_key = served_key(guild_id);
         let mut con = match self.redis.get_multiplexed_async_connection().await {
             Ok(con) => con,
             Err(e) => {
@@ -151,20 +422,123 @@ impl QueueRepository for RedisQueueRepository {
                 return vec![];
             }
         };
-        let count = std::num::NonZeroUsize::new(n);
-        let result: redis::RedisResult<Vec<String>> = con.lpop(&key, count).await;
-        let entries: Vec<QueueEntry> = result
-            .unwrap_or_default()
-            .into_iter()
-            .filter_map(|json_str| serde_json::from_str(&json_str).ok())
-            .collect();
-        info!(guild_id, count = entries.len(), "Popped entries from queue");
-        entries
+
+        // The policy may serve entries from anywhere in the queue, so the whole
+        // queue is rewritten in a transaction that is retried if someone else
+        // touches it.
+        for _ in 0..MAX_TRANSACTION_RETRIES {
+            let watched: redis::RedisResult<()> = redis::cmd("WATCH")
+                .arg(&key)
+                .arg(&served_key)
+                .query_async(&mut con)
+                .await;
+            if let Err(e) = watched {
+                error!(guild_id, error = ?e, "Failed to watch queue in Redis");
+                return vec![];
+            }
+
+            let json_list: Vec<String> = match con.lrange(&key, 0, -1).await {
+                Ok(list) => list,
+                Err(e) => {
+                    error!(guild_id, error = ?e, "Failed to fetch queue list from Redis");
+                    return vec![];
+                }
+            };
+            let entries: Vec<QueueEntry> = json_list
+                .iter()
+                .filter_map(|json_str| serde_json::from_str(json_str).ok())
+                .collect();
+
+            let served_counts: HashMap<String, u32> = match con.hgetall(&served_key).await {
+                Ok(counts) => counts,
+                Err(e) => {
+                    error!(guild_id, error = ?e, "Failed to fetch served counts from Redis");
+                    return vec![];
+                }
+            };
+
+            let (served, remaining) = policy.allocate(entries, n, &served_counts);
+
+            let mut pipe = redis::pipe();
+            pipe.atomic().del(&key).ignore();
+            for entry in &served {
+                pipe.hincr(&served_key, &entry.user_id, entry.quantity)
+                    .ignore();
+            }
+            if !remaining.is_empty() {
+                let remaining_json: Vec<String> = remaining
+                    .iter()
+                    .map(|entry| serde_json::to_string(entry).unwrap())
+                    .collect();
+                pipe.rpush(&key, remaining_json).ignore();
+            }
+
+            let result: redis::RedisResult<Option<()>> = pipe.query_async(&mut con).await;
+            match result {
+                Ok(Some(())) => {
+                    let waffles: u32 = served.iter().map(|e| e.quantity).sum();
+                    counter!(WAFFLES_BAKED, "guild_id" => guild_id.to_string(), "lane" => lane.to_string())
+                        .increment(waffles.into());
+                    info!(
+                        guild_id,
+                        count = served.len(),
+                        waffles,
+                        "Popped entries from queue"
+                    );
+                    return served;
+                }
+                Ok(None) => debug!(guild_id, "Queue changed during pop_n, retrying"),
+                Err(e) => {
+                    error!(guild_id, error = ?e, "Failed to pop entries from queue in Redis");
+                    return vec![];
+                }
+            }
+        }
+
+        warn!(guild_id, "Gave up popping entries after repeated conflicts");
+        vec![]
     }
 
-    #[instrument(skip(self), fields(guild_id))]
-    async fn list(&self, guild_id: &str) -> Vec<QueueEntry> {
-        let key = queue_key(guild_id);
+    #[instrument(skip(self), fields(guild_id, lane, user_id))]
+    async fn remove(&self, guild_id: &str, lane: &str, user_id: &str) -> bool {
+        let _timer = Timer::redis("queue.remove");
+        let removed = self
+            .update_lane(guild_id, lane, |entries| {
+                let index = entries.iter().position(|entry| entry.user_id == user_id)?;
+                Some(entries.remove(index))
+            })
+            .await
+            .is_some();
+        if removed {
+            info!(guild_id, lane, user_id, "Removed entry from queue");
+        }
+        removed
+    }
+
+    #[instrument(skip(self), fields(guild_id, lane, user_id, position))]
+    async fn move_to(
+        &self,
+        guild_id: &str,
+        lane: &str,
+        user_id: &str,
+        position: usize,
+    ) -> Option<usize> {
+        let _timer = Timer::redis("queue.move_to");
+        let moved = self
+            .update_lane(guild_id, lane, |entries| {
+                move_entry(entries, user_id, position)
+            })
+            .await;
+        if let Some(position) = moved {
+            info!(guild_id, lane, user_id, position, "Moved entry in queue");
+        }
+        moved
+    }
+
+    #[instrument(skip(self), fields(guild_id, lane))]
+    async fn list(&self, guild_id: &str, lane: &str) -> Vec<QueueEntry> {
+        let _timer = Timer::redis("queue.list");
+        let key = queue_key(guild_id, lane);
         let mut con = match self.redis.get_multiplexed_async_connection().await {
             Ok(con) => con,
             Err(e) => {
@@ -189,13 +563,184 @@ impl QueueRepository for RedisQueueRepository {
         entries
     }
 
+    #[instrument(skip(self, entry), fields(guild_id, lane = %entry.lane, user_id = %entry.entry.user_id))]
+    async fn push_waitlist(&self, guild_id: &str, entry: WaitlistEntry) -> usize {
+        let _timer = Timer::redis("queue.push_waitlist");
+        let key = waitlist_key(guild_id);
+        let json = serde_json::to_string(&entry).unwrap();
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, error = ?e, "Failed to get Redis connection for push_waitlist");
+                return 0;
+            }
+        };
+        let new_size = con.rpush(&key, json).await.unwrap_or_else(|e| {
+            error!(guild_id, error = ?e, "Failed to push to waitlist in Redis");
+            0
+        });
+        info!(guild_id, user_id = %entry.entry.user_id, waitlist_size = new_size, "Added user to waitlist");
+        new_size
+    }
+
     #[instrument(skip(self), fields(guild_id))]
-    async fn clear(&self, guild_id: &str) {
-        let key = queue_key(guild_id);
+    async fn waitlist(&self, guild_id: &str) -> Vec<WaitlistEntry> {
+        let _timer = Timer::redis("queue.waitlist");
+        let key = waitlist_key(guild_id);
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, error = ?e, "Failed to get Redis connection for waitlist");
+                return vec![];
+            }
+        };
+        let json_list: Vec<String> = con.lrange(&key, 0, -1).await.unwrap_or_else(|e| {
+            error!(guild_id, error = ?e, "Failed to fetch waitlist from Redis");
+            vec![]
+        });
+        let entries: Vec<WaitlistEntry> = json_list
+            .into_iter()
+            .filter_map(|json_str| {
+                serde_json::from_str(&json_str).ok().or_else(|| {
+                    warn!(guild_id, json = %json_str, "Failed to deserialize waitlist entry");
+                    None
+                })
+            })
+            .collect();
+        debug!(guild_id, count = entries.len(), "Retrieved waitlist");
+        entries
+    }
+
+    #[instrument(skip(self), fields(guild_id, waffles))]
+    async fn promote_waitlist(&self, guild_id: &str, waffles: u32) -> Vec<WaitlistEntry> {
+        let _timer = Timer::redis("queue.promote_waitlist");
+        let key = waitlist_key(guild_id);
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, error = ?e, "Failed to get Redis connection for promote_waitlist");
+                return vec![];
+            }
+        };
+
+        for _ in 0..MAX_TRANSACTION_RETRIES {
+            let watched: redis::RedisResult<()> =
+                redis::cmd("WATCH").arg(&key).query_async(&mut con).await;
+            if let Err(e) = watched {
+                error!(guild_id, error = ?e, "Failed to watch waitlist in Redis");
+                return vec![];
+            }
+
+            let json_list: Vec<String> = match con.lrange(&key, 0, -1).await {
+                Ok(list) => list,
+                Err(e) => {
+                    error!(guild_id, error = ?e, "Failed to fetch waitlist from Redis");
+                    return vec![];
+                }
+            };
+
+            // Stop at the first entry that does not fit, so the waitlist stays in order
+            let mut left = waffles;
+            let mut promoted: Vec<WaitlistEntry> = Vec::new();
+            for json_str in &json_list {
+                let Ok(entry) = serde_json::from_str::<WaitlistEntry>(json_str) else {
+                    break;
+                };
+                if entry.entry.quantity > left {
+                    break;
+                }
+                left -= entry.entry.quantity;
+                promoted.push(entry);
+            }
+
+            if promoted.is_empty() {
+                let _: redis::RedisResult<()> = redis::cmd("UNWATCH").query_async(&mut con).await;
+                return vec![];
+            }
+
+            let mut pipe = redis::pipe();
+            pipe.atomic()
+                .ltrim(&key, promoted.len() as isize, -1)
+                .ignore();
+            for promoted_entry in &promoted {
+                pipe.rpush(
+                    queue_key(guild_id, &promoted_entry.lane),
+                    serde_json::to_string(&promoted_entry.entry).unwrap(),
+                )
+                .ignore()
+                .sadd(lanes_key(guild_id), &promoted_entry.lane)
+                .ignore();
+            }
+
+            let result: redis::RedisResult<Option<()>> = pipe.query_async(&mut con).await;
+            match result {
+                Ok(Some(())) => {
+                    for promoted_entry in &promoted {
+                        counter!(JOINS, "guild_id" => guild_id.to_string(), "lane" => promoted_entry.lane.clone())
+                            .increment(1);
+                    }
+                    info!(
+                        guild_id,
+                        count = promoted.len(),
+                        "Promoted entries from waitlist"
+                    );
+                    return promoted;
+                }
+                Ok(None) => debug!(guild_id, "Waitlist changed during promote, retrying"),
+                Err(e) => {
+                    error!(guild_id, error = ?e, "Failed to promote entries from waitlist");
+                    return vec![];
+                }
+            }
+        }
+
+        warn!(
+            guild_id,
+            "Gave up promoting from waitlist after repeated conflicts"
+        );
+        vec![]
+    }
+
+    #[instrument(skip(self, entries), fields(guild_id, lane, count = entries.len()))]
+    async fn replace(&self, guild_id: &str, lane: &str, entries: Vec<QueueEntry>) {
+        let _timer = Timer::redis("queue.replace");
+        let key = queue_key(guild_id, lane);
+        let mut con = match self.redis.get_multiplexed_async_connection().await {
+            Ok(con) => con,
+            Err(e) => {
+                error!(guild_id, lane, error = ?e, "Failed to get Redis connection for replace");
+                return;
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(&key).ignore();
+        if !entries.is_empty() {
+            let json_list: Vec<String> = entries
+                .iter()
+                .map(|entry| serde_json::to_string(entry).unwrap())
+                .collect();
+            pipe.rpush(&key, json_list)
+                .ignore()
+                .sadd(lanes_key(guild_id), lane)
+                .ignore();
+        }
+
+        let result: redis::RedisResult<()> = pipe.query_async(&mut con).await;
+        match result {
+            Ok(()) => info!(guild_id, lane, count = entries.len(), "Replaced queue"),
+            Err(e) => error!(guild_id, lane, error = ?e, "Failed to replace queue in Redis"),
+        }
+    }
+
+    #[instrument(skip(self), fields(guild_id, lane))]
+    async fn clear(&self, guild_id: &str, lane: &str) {
+        let _timer = Timer::redis("queue.clear");
+        let key = queue_key(guild_id, lane);
         if let Ok(mut con) = self.redis.get_multiplexed_async_connection().await {
             let result: redis::RedisResult<()> = con.del(&key).await;
             match result {
-                Ok(_) => info!(guild_id, "Cleared queue"),
+                Ok(_) => info!(guild_id, lane, "Cleared queue"),
                 Err(e) => error!(guild_id, error = ?e, "Failed to clear queue in Redis"),
             }
         } else {
@@ -209,12 +754,17 @@ mod tests {
     use std::env::home_dir;
 
     use super::*;
+    use crate::domain::{
+        queue::DEFAULT_LANE,
+        serving::{Fifo, FirstTimersFirst},
+    };
 
     use testcontainers::runners::AsyncRunner;
     use testcontainers_modules::redis::Redis;
     use tokio::sync::OnceCell;
 
     const TEST_GUILD: &str = "test-guild";
+    const LANE: &str = DEFAULT_LANE;
 
     struct TestRedis {
         _node: testcontainers::ContainerAsync<Redis>,
@@ -256,7 +806,7 @@ mod tests {
     async fn setup() -> RedisQueueRepository {
         let redis = init_redis().await;
         let queue = RedisQueueRepository::new(redis.client.clone());
-        queue.clear(TEST_GUILD).await;
+        queue.clear(TEST_GUILD, LANE).await;
         queue
     }
 
@@ -264,22 +814,22 @@ mod tests {
     async fn test_push_and_pop() {
         let queue = setup().await;
         let guild = "test-push-and-pop";
-        queue.clear(guild).await;
+        queue.clear(guild, LANE).await;
 
         let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string());
         let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
 
-        queue.push(guild, foo.clone()).await;
-        queue.push(guild, bar.clone()).await;
+        queue.push(guild, LANE, foo.clone()).await;
+        queue.push(guild, LANE, bar.clone()).await;
 
-        assert_eq!(queue.size(guild).await, 2);
-        assert_eq!(queue.index_of(guild, "bar").await, Some(1));
+        assert_eq!(queue.size(guild, LANE).await, 2);
+        assert_eq!(queue.index_of(guild, LANE, "bar").await, Some(1));
 
-        let popped = queue.pop(guild).await;
+        let popped = queue.pop(guild, LANE).await;
         assert_eq!(popped, Some(foo));
-        assert_eq!(queue.size(guild).await, 1);
+        assert_eq!(queue.size(guild, LANE).await, 1);
 
-        let remaining = queue.list(guild).await;
+        let remaining = queue.list(guild, LANE).await;
         assert_eq!(remaining, vec![bar]);
     }
 
@@ -287,68 +837,215 @@ mod tests {
     async fn test_list() {
         let queue = setup().await;
         let guild = "test-list";
-        queue.clear(guild).await;
+        queue.clear(guild, LANE).await;
 
         let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string());
         let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
 
-        queue.push(guild, foo.clone()).await;
-        queue.push(guild, bar.clone()).await;
+        queue.push(guild, LANE, foo.clone()).await;
+        queue.push(guild, LANE, bar.clone()).await;
 
-        let list = queue.list(guild).await;
+        let list = queue.list(guild, LANE).await;
         assert_eq!(list, vec![foo, bar]);
     }
 
+    #[tokio::test]
+    async fn test_remove_and_move_to() {
+        let queue = setup().await;
+        let guild = "test-remove-move";
+        queue.clear(guild, LANE).await;
+
+        for user_id in ["foo", "bar", "baz"] {
+            queue
+                .push(
+                    guild,
+                    LANE,
+                    QueueEntry::new(user_id.to_string(), user_id.to_string()),
+                )
+                .await;
+        }
+
+        assert_eq!(queue.move_to(guild, LANE, "baz", 1).await, Some(1));
+        assert_eq!(queue.move_to(guild, LANE, "nobody", 1).await, None);
+        assert!(queue.remove(guild, LANE, "foo").await);
+        assert!(!queue.remove(guild, LANE, "foo").await);
+
+        let user_ids: Vec<String> = queue
+            .list(guild, LANE)
+            .await
+            .into_iter()
+            .map(|entry| entry.user_id)
+            .collect();
+        assert_eq!(user_ids, vec!["baz", "bar"]);
+    }
+
     #[tokio::test]
     async fn test_index_of() {
         let queue = setup().await;
         let guild = "test-index-of";
-        queue.clear(guild).await;
+        queue.clear(guild, LANE).await;
 
         let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string());
         let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
 
-        queue.push(guild, foo).await;
-        queue.push(guild, bar).await;
+        queue.push(guild, LANE, foo).await;
+        queue.push(guild, LANE, bar).await;
 
-        assert_eq!(queue.index_of(guild, "foo").await, Some(0));
-        assert_eq!(queue.index_of(guild, "bar").await, Some(1));
-        assert_eq!(queue.index_of(guild, "baz").await, None);
+        assert_eq!(queue.index_of(guild, LANE, "foo").await, Some(0));
+        assert_eq!(queue.index_of(guild, LANE, "bar").await, Some(1));
+        assert_eq!(queue.index_of(guild, LANE, "baz").await, None);
     }
 
     #[tokio::test]
     async fn test_size() {
         let queue = setup().await;
         let guild = "test-size";
-        queue.clear(guild).await;
+        queue.clear(guild, LANE).await;
 
-        assert_eq!(queue.size(guild).await, 0);
+        assert_eq!(queue.size(guild, LANE).await, 0);
 
         let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string());
         let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
 
-        queue.push(guild, foo).await;
-        queue.push(guild, bar).await;
+        queue.push(guild, LANE, foo).await;
+        queue.push(guild, LANE, bar).await;
+
+        assert_eq!(queue.size(guild, LANE).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pop_n_with_quantities() {
+        let queue = setup().await;
+        let guild = "test-pop-n-with-quantities";
+        queue.clear(guild, LANE).await;
+
+        let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string()).with_quantity(2);
+        let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string()).with_quantity(3);
+
+        queue.push(guild, LANE, foo.clone()).await;
+        queue.push(guild, LANE, bar.clone()).await;
+
+        let baked = queue.pop_n(guild, LANE, 3, &Fifo).await;
+        assert_eq!(baked, vec![foo, bar.clone().with_quantity(1)]);
+
+        let remaining = queue.list(guild, LANE).await;
+        assert_eq!(remaining, vec![bar.clone().with_quantity(2)]);
+
+        let baked = queue.pop_n(guild, LANE, 5, &Fifo).await;
+        assert_eq!(baked, vec![bar.with_quantity(2)]);
+        assert_eq!(queue.size(guild, LANE).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pop_n_first_timers_first() {
+        let queue = setup().await;
+        let guild = "test-pop-n-first-timers-first";
+        queue.close(guild).await;
+
+        let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string());
+        let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
+
+        queue.push(guild, LANE, foo.clone()).await;
+        assert_eq!(
+            queue.pop_n(guild, LANE, 1, &FirstTimersFirst).await,
+            vec![foo.clone()]
+        );
+
+        queue.push(guild, LANE, foo.clone()).await;
+        queue.push(guild, LANE, bar.clone()).await;
+
+        let baked = queue.pop_n(guild, LANE, 1, &FirstTimersFirst).await;
+        assert_eq!(baked, vec![bar]);
+        assert_eq!(queue.list(guild, LANE).await, vec![foo]);
+    }
+
+    #[tokio::test]
+    async fn test_lanes_are_separate() {
+        let queue = setup().await;
+        let guild = "test-lanes-are-separate";
+        queue.close(guild).await;
+
+        let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string());
+        let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
+
+        queue.push(guild, LANE, foo.clone()).await;
+        queue.push(guild, "glutenfri", bar.clone()).await;
+        queue.push(guild, "glutenfri", foo.clone()).await;
+
+        assert_eq!(queue.lanes(guild).await, vec!["glutenfri", LANE]);
+        assert_eq!(queue.index_of(guild, LANE, "foo").await, Some(0));
+        assert_eq!(queue.index_of(guild, "glutenfri", "foo").await, Some(1));
+
+        let baked = queue.pop_n(guild, "glutenfri", 1, &Fifo).await;
+        assert_eq!(baked, vec![bar]);
+        assert_eq!(queue.list(guild, LANE).await, vec![foo]);
+
+        queue.close(guild).await;
+        assert_eq!(queue.size(guild, LANE).await, 0);
+        assert_eq!(queue.size(guild, "glutenfri").await, 0);
+        assert!(queue.lanes(guild).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_promote_waitlist() {
+        let queue = setup().await;
+        let guild = "test-promote-waitlist";
+        queue.close(guild).await;
+
+        let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string()).with_quantity(2);
+        let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
+        let foo_waiting = WaitlistEntry {
+            lane: "glutenfri".to_string(),
+            entry: foo.clone(),
+        };
+        let bar_waiting = WaitlistEntry {
+            lane: LANE.to_string(),
+            entry: bar.clone(),
+        };
+
+        queue.push_waitlist(guild, foo_waiting.clone()).await;
+        queue.push_waitlist(guild, bar_waiting.clone()).await;
+
+        assert!(queue.promote_waitlist(guild, 1).await.is_empty());
+
+        let promoted = queue.promote_waitlist(guild, 2).await;
+        assert_eq!(promoted, vec![foo_waiting]);
+        assert_eq!(queue.list(guild, "glutenfri").await, vec![foo]);
+        assert_eq!(queue.waitlist(guild).await, vec![bar_waiting]);
+
+        queue.close(guild).await;
+        assert!(queue.waitlist(guild).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_next_ticket() {
+        let queue = setup().await;
+        let guild = "test-next-ticket";
+        queue.close(guild).await;
+
+        assert_eq!(queue.next_ticket(guild).await, 1);
+        assert_eq!(queue.next_ticket(guild).await, 2);
 
-        assert_eq!(queue.size(guild).await, 2);
+        queue.close(guild).await;
+        assert_eq!(queue.next_ticket(guild).await, 1);
     }
 
     #[tokio::test]
     async fn test_clear() {
         let queue = setup().await;
         let guild = "test-clear";
-        queue.clear(guild).await;
+        queue.clear(guild, LANE).await;
 
         let foo = QueueEntry::new("foo".to_string(), "Foo User".to_string());
         let bar = QueueEntry::new("bar".to_string(), "Bar User".to_string());
 
-        queue.push(guild, foo).await;
-        queue.push(guild, bar).await;
+        queue.push(guild, LANE, foo).await;
+        queue.push(guild, LANE, bar).await;
 
-        assert_eq!(queue.size(guild).await, 2);
+        assert_eq!(queue.size(guild, LANE).await, 2);
 
-        queue.clear(guild).await;
+        queue.clear(guild, LANE).await;
 
-        assert_eq!(queue.size(guild).await, 0);
+        assert_eq!(queue.size(guild, LANE).await, 0);
     }
 }
@@ -1,18 +1,26 @@
 use std::sync::Arc;
 
 use sqlx::postgres::PgPoolOptions;
-use tracing::{error, instrument};
+use tracing::{error, info, instrument};
 
 use crate::{
     adapters::{DiscordAdapter, HttpAdapter},
     config::Config,
-    infrastructure::{PostgresOrderRepository, RedisQueueRepository},
+    domain::{EventBroadcaster, HealthCheck, Repositories},
+    infrastructure::{
+        PostgresApiTokenRepository, PostgresAuditLogRepository, PostgresDeadlineRepository,
+        PostgresHealthCheck, PostgresLotteryRepository, PostgresOrderRepository,
+        PostgresProductOptionRepository, PostgresScheduleRepository, PostgresSettingsRepository,
+        PostgresTemplateRepository, PostgresUserLocaleRepository, RedisHealthCheck,
+        RedisQueueRepository,
+    },
 };
 
 pub mod adapters;
 pub mod config;
 pub mod domain;
 pub mod infrastructure;
+pub mod telemetry;
 
 pub struct VaffelBot {
     config: Config,
@@ -25,6 +33,8 @@ impl VaffelBot {
 
     #[instrument(skip(self))]
     pub async fn run(self) -> anyhow::Result<()> {
+        let metrics = telemetry::install()?;
+
         let redis =
             redis::Client::open(self.config.redis_url.clone()).expect("Failed to connect to Redis");
         let pg_pool = PgPoolOptions::new()
@@ -35,23 +45,90 @@ impl VaffelBot {
 
         sqlx::migrate!().run(&pg_pool).await?;
 
-        let queue: Arc<dyn domain::QueueRepository> = Arc::new(RedisQueueRepository::new(redis));
+        let queue: Arc<dyn domain::QueueRepository> =
+            Arc::new(RedisQueueRepository::new(redis.clone()));
 
         let orders: Arc<dyn domain::OrderRepository> =
-            Arc::new(PostgresOrderRepository::new(pg_pool));
+            Arc::new(PostgresOrderRepository::new(pg_pool.clone()));
+
+        let settings: Arc<dyn domain::SettingsRepository> =
+            Arc::new(PostgresSettingsRepository::new(pg_pool.clone()));
+
+        let products: Arc<dyn domain::ProductOptionRepository> =
+            Arc::new(PostgresProductOptionRepository::new(pg_pool.clone()));
+
+        let lottery: Arc<dyn domain::LotteryRepository> =
+            Arc::new(PostgresLotteryRepository::new(pg_pool.clone()));
+
+        let schedules: Arc<dyn domain::ScheduleRepository> =
+            Arc::new(PostgresScheduleRepository::new(pg_pool.clone()));
+
+        let deadlines: Arc<dyn domain::DeadlineRepository> =
+            Arc::new(PostgresDeadlineRepository::new(pg_pool.clone()));
+
+        let audit: Arc<dyn domain::AuditLogRepository> =
+            Arc::new(PostgresAuditLogRepository::new(pg_pool.clone()));
+
+        let user_locales: Arc<dyn domain::UserLocaleRepository> =
+            Arc::new(PostgresUserLocaleRepository::new(pg_pool.clone()));
+
+        let templates: Arc<dyn domain::TemplateRepository> =
+            Arc::new(PostgresTemplateRepository::new(pg_pool.clone()));
+
+        let api_tokens: Arc<dyn domain::ApiTokenRepository> =
+            Arc::new(PostgresApiTokenRepository::new(pg_pool.clone()));
+
+        let repositories = Repositories {
+            queue,
+            orders,
+            settings,
+            products,
+            lottery,
+            schedules,
+            deadlines,
+            audit,
+            user_locales,
+            templates,
+            api_tokens,
+        };
+
+        let events = EventBroadcaster::new();
 
         let discord_adapter = DiscordAdapter::new(
             self.config.discord_token.clone(),
-            queue.clone(),
-            orders.clone(),
+            repositories.clone(),
+            events.clone(),
+            self.config.timezone,
         );
 
-        let http_adapter = HttpAdapter::new(queue.clone(), orders.clone());
-        let axum_handle = tokio::spawn(async move {
-            if let Err(why) = http_adapter.start().await {
-                error!(error = ?why, "HTTP server error");
-            }
-        });
+        let axum_handle = if self.config.http.enabled {
+            // The HTTP adapter posts in Discord without the gateway, so it only needs the REST client
+            let discord_http = Arc::new(serenity::all::Http::new(&self.config.discord_token));
+            let health_checks: Vec<Arc<dyn HealthCheck>> = vec![
+                Arc::new(RedisHealthCheck::new(redis)),
+                Arc::new(PostgresHealthCheck::new(pg_pool)),
+                Arc::new(discord_adapter.gateway()),
+            ];
+            let http_adapter = HttpAdapter::new(
+                repositories,
+                events,
+                discord_http,
+                health_checks,
+                metrics,
+                self.config.timezone,
+                self.config.http,
+            );
+            // Bound before anything starts, so a taken port stops the bot
+            let listener = http_adapter.bind().await?;
+            Some(tokio::spawn(async move {
+                if let Err(why) = http_adapter.serve(listener).await {
+                    error!(error = ?why, "HTTP server error");
+                }
+            }))
+        } else {
+            info!("The HTTP API is disabled");
+            None
+        };
 
         let bot_handle = tokio::spawn(async move {
             if let Err(why) = discord_adapter.start().await {
@@ -59,7 +136,14 @@ impl VaffelBot {
             }
         });
 
-        let _ = tokio::try_join!(axum_handle, bot_handle);
+        match axum_handle {
+            Some(axum_handle) => {
+                let _ = tokio::try_join!(axum_handle, bot_handle);
+            }
+            None => {
+                let _ = bot_handle.await;
+            }
+        }
 
         Ok(())
     }
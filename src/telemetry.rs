@@ -0,0 +1,113 @@
+use std::time::{Duration, Instant};
+
+use metrics::{Unit, describe_counter, describe_gauge, describe_histogram, histogram};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+pub const JOINS: &str = "vaffelbot_joins_total";
+pub const LEAVES: &str = "vaffelbot_leaves_total";
+pub const WAFFLES_BAKED: &str = "vaffelbot_waffles_baked_total";
+pub const COMMANDS: &str = "vaffelbot_commands_total";
+pub const COMMAND_DURATION: &str = "vaffelbot_command_duration_seconds";
+pub const QUEUE_LENGTH: &str = "vaffelbot_queue_length";
+pub const QUEUE_OPEN: &str = "vaffelbot_queue_open";
+pub const REDIS_DURATION: &str = "vaffelbot_redis_duration_seconds";
+pub const POSTGRES_DURATION: &str = "vaffelbot_postgres_duration_seconds";
+
+/// Buckets for every histogram, in seconds
+const BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// How often histograms are compacted between scrapes
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Start recording metrics, returning the handle `/metrics` renders them with
+pub fn install() -> anyhow::Result<PrometheusHandle> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets(BUCKETS)?
+        .install_recorder()?;
+
+    describe_counter!(JOINS, "Entries added to a lane of the queue");
+    describe_counter!(
+        LEAVES,
+        "Entries taken out of the queue without being served"
+    );
+    describe_counter!(WAFFLES_BAKED, "Waffles handed out from the queue");
+    describe_counter!(COMMANDS, "Discord commands run, by name and outcome");
+    describe_histogram!(
+        COMMAND_DURATION,
+        Unit::Seconds,
+        "Time spent handling a Discord command"
+    );
+    describe_gauge!(QUEUE_LENGTH, "Entries waiting in the queue of a guild");
+    describe_gauge!(QUEUE_OPEN, "Whether the queue of a guild is open");
+    describe_histogram!(REDIS_DURATION, Unit::Seconds, "Time spent on Redis calls");
+    describe_histogram!(
+        POSTGRES_DURATION,
+        Unit::Seconds,
+        "Time spent on PostgreSQL calls"
+    );
+
+    let upkeep = handle.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(UPKEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            upkeep.run_upkeep();
+        }
+    });
+
+    Ok(handle)
+}
+
+/// Records how long a call took in a histogram when dropped, so a repository
+/// method only needs to create one at the start
+pub struct Timer {
+    histogram: &'static str,
+    operation: &'static str,
+    started: Instant,
+}
+
+impl Timer {
+    pub fn redis(operation: &'static str) -> Self {
+        Self::new(REDIS_DURATION, operation)
+    }
+
+    pub fn postgres(operation: &'static str) -> Self {
+        Self::new(POSTGRES_DURATION, operation)
+    }
+
+    fn new(histogram: &'static str, operation: &'static str) -> Self {
+        Self {
+            histogram,
+            operation,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        histogram!(self.histogram, "operation" => self.operation)
+            .record(self.started.elapsed().as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_records_on_drop() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            let _timer = Timer::postgres("order.daily_stats");
+        });
+
+        assert!(handle.render().contains(
+            r#"vaffelbot_postgres_duration_seconds_count{operation="order.daily_stats"} 1"#
+        ));
+    }
+}